@@ -1,4 +1,4 @@
-use shared::models::download::{FtpDownloader, DownloadConfig};
+use shared::models::download::{FtpDownloader, DownloadConfig, LocalLayout, OverwritePolicy};
 use shared::models::directory::{FtpFileSystemProvider, DirectoryEntry, FileSystemProvider};
 use shared::models::file::File;
 
@@ -13,7 +13,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         preserve_structure: false,
         max_concurrent: 3,
         buffer_size: 16384,
-        overwrite: true,
+        overwrite: OverwritePolicy::Always,
+        layout: LocalLayout::MirrorFtp,
+        ..DownloadConfig::default()
     };
 
     let downloader = FtpDownloader::new_datasus().with_config(config);
@@ -38,7 +38,9 @@ async fn main() -> Result<()> {
         preserve_structure: true,
         max_concurrent: 2,
         buffer_size: 8192,
-        overwrite: true,
+        overwrite: OverwritePolicy::Always,
+        layout: LocalLayout::MirrorFtp,
+        ..DownloadConfig::default()
     };
 
     let downloader = FtpDownloader::new_datasus().with_config(config);
@@ -1,5 +1,6 @@
 pub mod errors;
 pub mod models;
+pub mod prelude;
 pub mod schemas;
 
 pub use errors::*;
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
@@ -14,17 +13,11 @@ pub type PathLike = PathBuf;
 
 /// Constants (reuse from sync version)
 pub use super::utils::DEFAULT_CACHE_DIR;
+use super::utils::resolve_cache_dir;
 
 lazy_static! {
-    /// Async cache path - equivalent to CACHEPATH in Python
-    pub static ref ASYNC_CACHE_PATH: String = {
-        env::var("PYSUS_CACHEPATH")
-            .unwrap_or_else(|_| {
-                dirs::home_dir()
-                    .map(|home| home.join(DEFAULT_CACHE_DIR).to_string_lossy().to_string())
-                    .unwrap_or_else(|| format!("./{}", DEFAULT_CACHE_DIR))
-            })
-    };
+    /// Async cache path - equivalent to CACHEPATH in Python, resolved by [`resolve_cache_dir`]
+    pub static ref ASYNC_CACHE_PATH: String = resolve_cache_dir().to_string_lossy().to_string();
 
     /// Async cache path as PathBuf - equivalent to __cachepath__ in Python
     pub static ref ASYNC_CACHE_PATH_BUF: PathBuf = {
@@ -358,6 +351,11 @@ pub mod content_cache {
         format!("ftp://{}:{}", host, path)
     }
     
+    /// Generate cache key for a provider listing (keyed per provider so listings don't collide)
+    pub fn generate_provider_cache_key(provider_name: &str, path: &str) -> String {
+        format!("content::{}::{}", provider_name, path)
+    }
+    
     /// Add content to cache with TTL
     pub async fn cache_content(key: String, content: String, ttl_seconds: u64) {
         let entry = CacheEntry::new(content, ttl_seconds);
@@ -382,6 +380,12 @@ pub mod content_cache {
         }
     }
     
+    /// Remove a single entry from the cache
+    pub async fn remove_cached_content(key: &str) -> bool {
+        let mut cache = CONTENT_CACHE.lock().await;
+        cache.remove(key).is_some()
+    }
+    
     /// Check if content exists in cache and is not expired
     pub async fn is_cached(key: &str) -> bool {
         let cache = CONTENT_CACHE.lock().await;
@@ -0,0 +1,140 @@
+//! ICD-10 (CID-10) codes, as stored in SIM cause-of-death and SIH diagnosis columns
+
+use polars::prelude::{DataFrame, DataType, IntoSeries, PolarsResult, StringChunked};
+
+/// An ICD-10 chapter: its roman numeral, first and last three-character categories, and title
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CidChapter {
+    pub numeral: &'static str,
+    pub first: &'static str,
+    pub last: &'static str,
+    pub title: &'static str,
+}
+
+/// The 22 ICD-10 chapters in code order
+pub const CID10_CHAPTERS: [CidChapter; 22] = [
+    CidChapter { numeral: "I", first: "A00", last: "B99", title: "Certain infectious and parasitic diseases" },
+    CidChapter { numeral: "II", first: "C00", last: "D48", title: "Neoplasms" },
+    CidChapter { numeral: "III", first: "D50", last: "D89", title: "Diseases of the blood and blood-forming organs and certain disorders involving the immune mechanism" },
+    CidChapter { numeral: "IV", first: "E00", last: "E90", title: "Endocrine, nutritional and metabolic diseases" },
+    CidChapter { numeral: "V", first: "F00", last: "F99", title: "Mental and behavioural disorders" },
+    CidChapter { numeral: "VI", first: "G00", last: "G99", title: "Diseases of the nervous system" },
+    CidChapter { numeral: "VII", first: "H00", last: "H59", title: "Diseases of the eye and adnexa" },
+    CidChapter { numeral: "VIII", first: "H60", last: "H95", title: "Diseases of the ear and mastoid process" },
+    CidChapter { numeral: "IX", first: "I00", last: "I99", title: "Diseases of the circulatory system" },
+    CidChapter { numeral: "X", first: "J00", last: "J99", title: "Diseases of the respiratory system" },
+    CidChapter { numeral: "XI", first: "K00", last: "K93", title: "Diseases of the digestive system" },
+    CidChapter { numeral: "XII", first: "L00", last: "L99", title: "Diseases of the skin and subcutaneous tissue" },
+    CidChapter { numeral: "XIII", first: "M00", last: "M99", title: "Diseases of the musculoskeletal system and connective tissue" },
+    CidChapter { numeral: "XIV", first: "N00", last: "N99", title: "Diseases of the genitourinary system" },
+    CidChapter { numeral: "XV", first: "O00", last: "O99", title: "Pregnancy, childbirth and the puerperium" },
+    CidChapter { numeral: "XVI", first: "P00", last: "P96", title: "Certain conditions originating in the perinatal period" },
+    CidChapter { numeral: "XVII", first: "Q00", last: "Q99", title: "Congenital malformations, deformations and chromosomal abnormalities" },
+    CidChapter { numeral: "XVIII", first: "R00", last: "R99", title: "Symptoms, signs and abnormal clinical and laboratory findings, not elsewhere classified" },
+    CidChapter { numeral: "XIX", first: "S00", last: "T98", title: "Injury, poisoning and certain other consequences of external causes" },
+    CidChapter { numeral: "XX", first: "V01", last: "Y98", title: "External causes of morbidity and mortality" },
+    CidChapter { numeral: "XXI", first: "Z00", last: "Z99", title: "Factors influencing health status and contact with health services" },
+    CidChapter { numeral: "XXII", first: "U00", last: "U99", title: "Codes for special purposes" },
+];
+
+/// Three-character category of a code, upper-cased
+///
+/// Accepts a letter and two digits, optionally followed by a subcategory
+/// digit, itself optionally after a dot (`I21`, `I219`, `i21.9`). DATASUS
+/// pads three-character codes with `X` or `-` (`R99X`), which is accepted too.
+fn cid_category(code: &str) -> Option<String> {
+    let code = code.trim().to_ascii_uppercase();
+    let bytes = code.as_bytes();
+    if bytes.len() < 3 || !bytes[0].is_ascii_uppercase() || !bytes[1..3].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    let subcategory = code[3..].strip_prefix('.').unwrap_or(&code[3..]);
+    let valid_subcategory = match subcategory.as_bytes() {
+        [] => true,
+        [sub] => sub.is_ascii_digit() || *sub == b'X' || *sub == b'-',
+        _ => false,
+    };
+    valid_subcategory.then(|| code[..3].to_string())
+}
+
+/// The chapter a CID-10 code falls in, subcodes included (`I219` is in `I21`, chapter IX)
+pub fn cid_chapter_info(code: &str) -> Option<&'static CidChapter> {
+    let category = cid_category(code)?;
+    CID10_CHAPTERS
+        .iter()
+        .find(|chapter| chapter.first <= category.as_str() && category.as_str() <= chapter.last)
+}
+
+/// Roman numeral of the chapter a CID-10 code falls in
+pub fn cid_chapter(code: &str) -> Option<&'static str> {
+    cid_chapter_info(code).map(|chapter| chapter.numeral)
+}
+
+/// Whether `code` is well-formed and within a chapter's range
+///
+/// Only the format and chapter ranges are checked, not whether the WHO
+/// list actually has the category.
+pub fn cid10_is_valid(code: &str) -> bool {
+    cid_chapter_info(code).is_some()
+}
+
+/// Add a `{col}_CHAPTER` column with the chapter numeral of each code in `col`
+///
+/// Null and invalid codes get a null chapter.
+pub fn with_cid_chapter(df: &DataFrame, col: &str) -> PolarsResult<DataFrame> {
+    let codes = df.column(col)?.cast(&DataType::String)?;
+    let chapters: StringChunked = codes
+        .str()?
+        .into_iter()
+        .map(|code| code.and_then(cid_chapter))
+        .collect();
+
+    let mut with_chapter = df.clone();
+    with_chapter.with_column(chapters.with_name(format!("{}_CHAPTER", col).into()).into_series())?;
+    Ok(with_chapter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_cid_chapter() {
+        assert_eq!(cid_chapter("A09"), Some("I"));
+        assert_eq!(cid_chapter("C509"), Some("II"));
+        assert_eq!(cid_chapter("D48"), Some("II"));
+        assert_eq!(cid_chapter("D50"), Some("III"));
+        assert_eq!(cid_chapter("H60"), Some("VIII"));
+        assert_eq!(cid_chapter("I219"), Some("IX"));
+        assert_eq!(cid_chapter("i21.9"), Some("IX"));
+        assert_eq!(cid_chapter("J189"), Some("X"));
+        assert_eq!(cid_chapter("R99X"), Some("XVIII"));
+        assert_eq!(cid_chapter("T71"), Some("XIX"));
+        assert_eq!(cid_chapter("X954"), Some("XX"));
+        assert_eq!(cid_chapter("U071"), Some("XXII"));
+        assert_eq!(cid_chapter_info("I219").unwrap().title, "Diseases of the circulatory system");
+    }
+
+    #[test]
+    fn test_cid10_is_valid() {
+        assert!(cid10_is_valid("I219"));
+        assert!(cid10_is_valid(" E11 "));
+        assert!(!cid10_is_valid("D49"));
+        assert!(!cid10_is_valid("K95"));
+        assert!(!cid10_is_valid("I2"));
+        assert!(!cid10_is_valid("I2190"));
+        assert!(!cid10_is_valid("121"));
+        assert!(!cid10_is_valid(""));
+    }
+
+    #[test]
+    fn test_with_cid_chapter() {
+        let df = df!("CAUSABAS" => [Some("I219"), Some("C341"), None, Some("ZZZ")]).unwrap();
+        let df = with_cid_chapter(&df, "CAUSABAS").unwrap();
+        let chapters: Vec<Option<&str>> = df.column("CAUSABAS_CHAPTER").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(chapters, [Some("IX"), Some("II"), None, None]);
+        assert!(with_cid_chapter(&df, "MISSING").is_err());
+    }
+}
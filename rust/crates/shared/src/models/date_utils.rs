@@ -217,6 +217,154 @@ pub async fn format_date_label_async(month: u8, year: i32) -> Result<String, Tim
     Ok(format!("{}/{}", month_name, year))
 }
 
+/// Partition period of a DATASUS dataset: a whole year or a single month
+///
+/// Parses `YYYY`, `YYYY-MM` and `YYYYMM`; displays (and serializes) as `YYYY`
+/// or `YYYY-MM`. Ordering is chronological, with a year sorting before its months.
+///
+/// # Example
+/// ```rust
+/// use shared::models::date_utils::Period;
+///
+/// assert_eq!("202401".parse::<Period>().unwrap(), Period::YearMonth(2024, 1));
+/// assert_eq!(Period::YearMonth(2024, 1).to_string(), "2024-01");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    Year(i32),
+    YearMonth(i32, u32),
+}
+
+impl Period {
+    /// Year component of the period
+    pub fn year(&self) -> i32 {
+        match self {
+            Period::Year(year) | Period::YearMonth(year, _) => *year,
+        }
+    }
+
+    /// Month component, if the period is monthly
+    pub fn month(&self) -> Option<u32> {
+        match self {
+            Period::Year(_) => None,
+            Period::YearMonth(_, month) => Some(*month),
+        }
+    }
+
+    /// Every period from `start` to `end`, both inclusive, a year or a month at a time
+    ///
+    /// Both ends must have the same granularity; empty when `start` is after `end`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use shared::models::date_utils::Period;
+    ///
+    /// let months: Vec<String> = Period::range(Period::YearMonth(2019, 12), Period::YearMonth(2020, 1))
+    ///     .unwrap()
+    ///     .map(|period| period.to_string())
+    ///     .collect();
+    /// assert_eq!(months, ["2019-12", "2020-01"]);
+    /// ```
+    pub fn range(start: Period, end: Period) -> Result<impl Iterator<Item = Period>, TimeFormatError> {
+        let monthly = match (start, end) {
+            (Period::Year(_), Period::Year(_)) => false,
+            (Period::YearMonth(..), Period::YearMonth(..)) => true,
+            _ => {
+                return Err(TimeFormatError::new(
+                    "YYYY..YYYY ou YYYY-MM..YYYY-MM",
+                    &format!("{}..{}", start, end),
+                    "Períodos de granularidades diferentes",
+                ));
+            }
+        };
+
+        // Months counted from year 0, so month ranges roll over years
+        let index = |period: Period| match period {
+            Period::Year(year) => year,
+            Period::YearMonth(year, month) => year * 12 + month as i32 - 1,
+        };
+        Ok((index(start)..=index(end)).map(move |index| {
+            if monthly {
+                Period::YearMonth(index.div_euclid(12), (index.rem_euclid(12) + 1) as u32)
+            } else {
+                Period::Year(index)
+            }
+        }))
+    }
+}
+
+impl Ord for Period {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.year(), self.month().unwrap_or(0)).cmp(&(other.year(), other.month().unwrap_or(0)))
+    }
+}
+
+impl PartialOrd for Period {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::str::FromStr for Period {
+    type Err = TimeFormatError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let invalid = || TimeFormatError::new("YYYY, YYYY-MM ou YYYYMM", value, "Período inválido");
+        if !value.is_ascii() {
+            return Err(invalid());
+        }
+
+        let (year_str, month_str) = match value.len() {
+            4 => (value, None),
+            6 => (&value[..4], Some(&value[4..])),
+            7 if value.as_bytes()[4] == b'-' => (&value[..4], Some(&value[5..])),
+            _ => return Err(invalid()),
+        };
+
+        if !year_str.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let year: i32 = year_str.parse().map_err(|_| invalid())?;
+
+        match month_str {
+            None => Ok(Period::Year(year)),
+            Some(month_str) => {
+                if !month_str.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                let month: u32 = month_str.parse().map_err(|_| invalid())?;
+                if !(1..=12).contains(&month) {
+                    return Err(TimeFormatError::new("1-12", month_str, "Mês deve estar entre 1 e 12"));
+                }
+                Ok(Period::YearMonth(year, month))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Year(year) => write!(f, "{:04}", year),
+            Period::YearMonth(year, month) => write!(f, "{:04}-{:02}", year, month),
+        }
+    }
+}
+
+impl serde::Serialize for Period {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Period {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,4 +432,65 @@ mod tests {
         assert_eq!(get_month_from_str_async("01").await.unwrap(), "Janeiro");
         assert_eq!(format_date_label_async(1, 2024).await.unwrap(), "Janeiro/2024");
     }
+
+    #[test]
+    fn test_period_parsing() {
+        assert_eq!("2024".parse::<Period>().unwrap(), Period::Year(2024));
+        assert_eq!("2024-03".parse::<Period>().unwrap(), Period::YearMonth(2024, 3));
+        assert_eq!("202403".parse::<Period>().unwrap(), Period::YearMonth(2024, 3));
+        assert_eq!(" 2024-12 ".parse::<Period>().unwrap(), Period::YearMonth(2024, 12));
+
+        // Test invalid inputs
+        assert!("24".parse::<Period>().is_err());
+        assert!("2024-13".parse::<Period>().is_err());
+        assert!("202400".parse::<Period>().is_err());
+        assert!("2024/03".parse::<Period>().is_err());
+        assert!("20a403".parse::<Period>().is_err());
+        assert!("2024-3".parse::<Period>().is_err());
+    }
+
+    #[test]
+    fn test_period_range() {
+        let months: Vec<Period> = Period::range("2019-11".parse().unwrap(), "2020-02".parse().unwrap()).unwrap().collect();
+        assert_eq!(
+            months,
+            [Period::YearMonth(2019, 11), Period::YearMonth(2019, 12), Period::YearMonth(2020, 1), Period::YearMonth(2020, 2)]
+        );
+
+        let years: Vec<Period> = Period::range(Period::Year(2018), Period::Year(2020)).unwrap().collect();
+        assert_eq!(years, [Period::Year(2018), Period::Year(2019), Period::Year(2020)]);
+        assert_eq!(Period::range(Period::YearMonth(2020, 3), Period::YearMonth(2020, 3)).unwrap().count(), 1);
+        assert_eq!(Period::range(Period::YearMonth(2020, 3), Period::YearMonth(2020, 2)).unwrap().count(), 0);
+
+        let mismatched = Period::range(Period::Year(2019), Period::YearMonth(2020, 2)).err().unwrap();
+        assert_eq!(mismatched.value, "2019..2020-02");
+    }
+
+    #[test]
+    fn test_period_display_and_serde() {
+        assert_eq!(Period::Year(2024).to_string(), "2024");
+        assert_eq!(Period::YearMonth(2024, 3).to_string(), "2024-03");
+
+        let json = serde_json::to_string(&Period::YearMonth(2024, 3)).unwrap();
+        assert_eq!(json, "\"2024-03\"");
+        assert_eq!(serde_json::from_str::<Period>(&json).unwrap(), Period::YearMonth(2024, 3));
+        assert_eq!(serde_json::from_str::<Period>("\"202403\"").unwrap(), Period::YearMonth(2024, 3));
+    }
+
+    #[test]
+    fn test_period_ordering() {
+        let mut periods = vec![
+            Period::YearMonth(2024, 2),
+            Period::Year(2024),
+            Period::YearMonth(2023, 12),
+            Period::YearMonth(2024, 1),
+        ];
+        periods.sort();
+        assert_eq!(periods, vec![
+            Period::YearMonth(2023, 12),
+            Period::Year(2024),
+            Period::YearMonth(2024, 1),
+            Period::YearMonth(2024, 2),
+        ]);
+    }
 }
@@ -4,7 +4,7 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tokio::fs::File as AsyncFile;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader as AsyncBufReader};
 use dbase::{Reader, FieldInfo};
@@ -302,20 +302,263 @@ fn split_lines_into_parts(lines: Vec<String>) -> Result<DbcFileParts, DbfEncodin
     })
 }
 
+/// Field descriptor as declared in a DBF header
+///
+/// Unlike `dbase::FieldInfo`, this keeps the declared decimal count,
+/// which is needed to tell integer numerics apart from decimal ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbfFieldDescriptor {
+    /// Field name
+    pub name: String,
+    /// Single-letter dBase type code (`C`, `N`, `F`, `D`, `L`, ...)
+    pub field_type: char,
+    /// Declared field width in bytes
+    pub width: u8,
+    /// Declared number of decimal places
+    pub decimal_count: u8,
+}
+
+/// Parse the field descriptors from the start of a DBF stream
+pub fn parse_dbf_field_descriptors<R: Read>(mut reader: R) -> Result<Vec<DbfFieldDescriptor>, DbfEncodingError> {
+    let mut main_header = [0u8; 32];
+    reader
+        .read_exact(&mut main_header)
+        .map_err(|_| DbfEncodingError::ParseError("Missing or truncated DBF header".to_string()))?;
+
+    let header_size = usize::from(u16::from_le_bytes([main_header[8], main_header[9]]));
+    if header_size < 33 {
+        return Err(DbfEncodingError::ParseError(
+            format!("Invalid header size: {} (must be >= 33)", header_size)
+        ));
+    }
+
+    let mut descriptor_bytes = vec![0u8; header_size - 32];
+    reader
+        .read_exact(&mut descriptor_bytes)
+        .map_err(|_| DbfEncodingError::ParseError("Truncated DBF field descriptors".to_string()))?;
+
+    let mut descriptors = Vec::new();
+    for chunk in descriptor_bytes.chunks(32) {
+        // 0x0D terminates the field descriptor array
+        if chunk[0] == 0x0D || chunk.len() < 32 {
+            break;
+        }
+
+        let name_end = chunk[..11].iter().position(|&b| b == 0).unwrap_or(11);
+        descriptors.push(DbfFieldDescriptor {
+            name: String::from_utf8_lossy(&chunk[..name_end]).trim().to_string(),
+            field_type: char::from(chunk[11]),
+            width: chunk[16],
+            decimal_count: chunk[17],
+        });
+    }
+
+    Ok(descriptors)
+}
+
+/// Read the field descriptors declared in a DBF file header
+pub fn read_dbf_field_descriptors<P: AsRef<Path>>(file_path: P) -> Result<Vec<DbfFieldDescriptor>, DbfEncodingError> {
+    let file = File::open(file_path)?;
+    parse_dbf_field_descriptors(BufReader::new(file))
+}
+
+/// Text encoding used to decode DBF character fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DbaseEncoding {
+    /// ISO-8859-1, what most DATASUS files use
+    #[default]
+    Latin1,
+    /// IBM code page 850 (DOS Latin-1)
+    Cp850,
+    /// Already UTF-8
+    Utf8,
+}
+
+impl DbaseEncoding {
+    /// Map a DBF language driver ID (header byte 29) to an encoding, if known
+    pub fn from_language_driver(driver_id: u8) -> Option<Self> {
+        match driver_id {
+            0x02 => Some(Self::Cp850),
+            // Windows ANSI code pages decode Latin-1 text identically
+            0x03 | 0x57 | 0x58 | 0x59 => Some(Self::Latin1),
+            _ => None,
+        }
+    }
+
+    /// Decode raw field bytes, replacing anything invalid
+    pub fn decode_lossy(self, bytes: &[u8]) -> String {
+        use yore::CodePage;
+
+        match self {
+            // Latin-1 bytes are exactly the first 256 code points
+            Self::Latin1 => bytes.iter().map(|&byte| char::from(byte)).collect(),
+            Self::Cp850 => yore::code_pages::CP850.decode_lossy(bytes).into_owned(),
+            Self::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+}
+
+/// Read the language driver ID (byte 29) from a DBF header
+pub fn read_dbf_language_driver<P: AsRef<Path>>(file_path: P) -> Result<u8, DbfEncodingError> {
+    let mut main_header = [0u8; 32];
+    File::open(file_path)?
+        .read_exact(&mut main_header)
+        .map_err(|_| DbfEncodingError::ParseError("Missing or truncated DBF header".to_string()))?;
+    Ok(main_header[29])
+}
+
+/// Find the memo file (`.dbt` or `.fpt`, any case) sharing a DBF's file stem
+///
+/// Memo fields only hold block numbers; their text lives in this sibling file.
+pub fn find_memo_file<P: AsRef<Path>>(dbf_path: P) -> Option<PathBuf> {
+    let dbf_path = dbf_path.as_ref();
+    let stem = dbf_path.file_stem()?;
+    let parent = match dbf_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(parent)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_stem() == Some(stem)
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("dbt") || ext.eq_ignore_ascii_case("fpt"))
+        })
+        .collect();
+    // Deterministic pick if a directory somehow holds both
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Open a DBF for its header alone, without the memo file memo fields would need
+fn open_dbf_header_reader(file_path: &Path) -> Result<Reader<BufReader<File>>, DbfEncodingError> {
+    let file = File::open(file_path)
+        .map_err(|e| DbfEncodingError::IoError(format!("Failed to open dbase file: {}", e)))?;
+    dbase::ReaderBuilder::<_, dbase::UnicodeLossy>::new(BufReader::new(file))
+        .build()
+        .map_err(|e| DbfEncodingError::IoError(format!("Failed to open dbase file: {}", e)))
+}
+
+/// Encoding declared by a DBF header's language driver ID, if the header is readable and the ID known
+pub fn dbf_codepage<P: AsRef<Path>>(file_path: P) -> Option<DbaseEncoding> {
+    read_dbf_language_driver(file_path)
+        .ok()
+        .and_then(DbaseEncoding::from_language_driver)
+}
+
+/// File format found by [`detect_dbase_format`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbaseFormat {
+    /// DBF header followed by a CRC32 and PKWARE-imploded records
+    Dbc,
+    /// Plain DBF
+    Dbf,
+    /// Neither (too short, unknown version byte, or inconsistent sizes)
+    Unknown,
+}
+
+/// DBF version bytes (header byte 0) seen in dBase, FoxPro and Visual FoxPro files
+const DBF_VERSION_BYTES: [u8; 15] = [
+    0x02, 0x03, 0x04, 0x05, 0x30, 0x31, 0x32, 0x43, 0x63, 0x83, 0x8B, 0x8E, 0xCB, 0xF5, 0xFB,
+];
+
+/// Tell a DBC from a DBF by content rather than extension
+///
+/// Both start with the same DBF header. A DBF's length is the header plus
+/// record count times record size (plus an optional 0x1A end marker), and its
+/// first record starts with a deletion flag (space or `*`). A DBC instead has
+/// a 4-byte CRC32 after the header, then a PKWARE stream whose first two
+/// bytes are the literal mode (0 or 1) and dictionary size (4 to 6).
+pub fn detect_dbase_format<P: AsRef<Path>>(path: P) -> DbaseFormat {
+    use std::io::{Seek, SeekFrom};
+
+    let Ok(mut file) = File::open(path) else {
+        return DbaseFormat::Unknown;
+    };
+    let Ok(file_len) = file.metadata().map(|metadata| metadata.len()) else {
+        return DbaseFormat::Unknown;
+    };
+    let mut main_header = [0u8; 32];
+    if file.read_exact(&mut main_header).is_err() || !DBF_VERSION_BYTES.contains(&main_header[0]) {
+        return DbaseFormat::Unknown;
+    }
+
+    let record_count = u64::from(u32::from_le_bytes([main_header[4], main_header[5], main_header[6], main_header[7]]));
+    let header_size = u64::from(u16::from_le_bytes([main_header[8], main_header[9]]));
+    let record_size = u64::from(u16::from_le_bytes([main_header[10], main_header[11]]));
+    if header_size < 33 || header_size > file_len {
+        return DbaseFormat::Unknown;
+    }
+
+    // Up to the first record's deletion flag in a DBF, or through the PKWARE header in a DBC
+    let mut after_header = [0u8; 6];
+    let available = (file_len - header_size).min(after_header.len() as u64) as usize;
+    if file.seek(SeekFrom::Start(header_size)).is_err() || file.read_exact(&mut after_header[..available]).is_err() {
+        return DbaseFormat::Unknown;
+    }
+
+    let dbf_len = header_size + record_count * record_size;
+    let first_record_ok = record_count == 0 || matches!(after_header[0], b' ' | b'*');
+    if (file_len == dbf_len || file_len == dbf_len + 1) && first_record_ok {
+        return DbaseFormat::Dbf;
+    }
+    if available == after_header.len() && after_header[4] <= 1 && (4..=6).contains(&after_header[5]) {
+        return DbaseFormat::Dbc;
+    }
+    // Trailing bytes past the records still make a readable DBF
+    if file_len > dbf_len && first_record_ok {
+        return DbaseFormat::Dbf;
+    }
+    DbaseFormat::Unknown
+}
+
+/// Whether `path` should be read as a DBC: by content, or by extension when [`detect_dbase_format`] can't tell
+pub fn is_dbc_file<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    match detect_dbase_format(path) {
+        DbaseFormat::Dbc => true,
+        DbaseFormat::Dbf => false,
+        DbaseFormat::Unknown => path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("dbc")),
+    }
+}
+
+/// Smallest integer type that can hold any value of a numeric field with the given width
+///
+/// Widths above 18 digits can overflow `i64` and are read as `Float64`.
+/// When `shrink_numeric_types` is false every integer numeric becomes `Int64`.
+pub fn integer_type_for_width(width: u8, shrink_numeric_types: bool) -> DataType {
+    match width {
+        19.. => DataType::Float64,
+        _ if !shrink_numeric_types => DataType::Int64,
+        0..=4 => DataType::Int16,
+        5..=9 => DataType::Int32,
+        _ => DataType::Int64,
+    }
+}
+
 /// Convert dbase FieldInfo to Arrow DataType
-fn dbase_field_to_arrow_type(field_info: &FieldInfo) -> DataType {
+fn dbase_field_to_arrow_type(
+    field_info: &FieldInfo,
+    descriptor: Option<&DbfFieldDescriptor>,
+    shrink_numeric_types: bool,
+) -> DataType {
     match field_info.field_type() {
         dbase::FieldType::Character => DataType::Utf8,
-        dbase::FieldType::Currency => DataType::Decimal128(19, 4), // Standard currency precision
-        dbase::FieldType::Numeric => {
-            // Since we can't access decimal_count directly, we'll use length as a heuristic
-            // Smaller numeric fields are likely integers, larger ones likely decimals
-            if field_info.length() <= 9 {
-                DataType::Int32
-            } else {
-                DataType::Float64
-            }
-        }
+        dbase::FieldType::Currency => DataType::Float64, // Stored as a scaled 8-byte integer, read as f64
+        dbase::FieldType::Numeric => match descriptor.map(|d| d.decimal_count) {
+            Some(0) => integer_type_for_width(field_info.length(), shrink_numeric_types),
+            Some(_) => DataType::Float64,
+            // Without the raw descriptor, fall back to using length as a heuristic
+            None if field_info.length() <= 9 => DataType::Int32,
+            None => DataType::Float64,
+        },
         dbase::FieldType::Float => DataType::Float64,
         dbase::FieldType::Date => DataType::Date32,
         dbase::FieldType::DateTime => DataType::Timestamp(arrow::datatypes::TimeUnit::Millisecond, None),
@@ -328,14 +571,26 @@ fn dbase_field_to_arrow_type(field_info: &FieldInfo) -> DataType {
 
 /// Synchronously read dbase file header and convert to Arrow Schema
 pub fn dbase_header_to_arrow_schema<P: AsRef<Path>>(file_path: P) -> Result<Schema, DbfEncodingError> {
-    let reader = Reader::from_path(file_path)
-        .map_err(|e| DbfEncodingError::IoError(format!("Failed to open dbase file: {}", e)))?;
+    dbase_header_to_arrow_schema_with_options(file_path, true)
+}
+
+/// Synchronously read dbase file header and convert to Arrow Schema
+///
+/// `shrink_numeric_types` narrows integer numerics to the smallest type
+/// allowed by their declared width (see [`integer_type_for_width`]).
+pub fn dbase_header_to_arrow_schema_with_options<P: AsRef<Path>>(
+    file_path: P,
+    shrink_numeric_types: bool,
+) -> Result<Schema, DbfEncodingError> {
+    let reader = open_dbf_header_reader(file_path.as_ref())?;
+    let descriptors = read_dbf_field_descriptors(file_path.as_ref()).unwrap_or_default();
     
     let fields: Vec<Field> = reader
         .fields()
         .iter()
         .map(|field_info| {
-            let data_type = dbase_field_to_arrow_type(field_info);
+            let descriptor = descriptors.iter().find(|d| d.name == field_info.name());
+            let data_type = dbase_field_to_arrow_type(field_info, descriptor, shrink_numeric_types);
             Field::new(field_info.name(), data_type, true) // Allow nulls by default
         })
         .collect();
@@ -347,21 +602,35 @@ pub fn dbase_header_to_arrow_schema<P: AsRef<Path>>(file_path: P) -> Result<Sche
 pub fn dbase_header_to_arrow_schema_with_metadata<P: AsRef<Path>>(
     file_path: P,
 ) -> Result<(Schema, Vec<FieldInfo>), DbfEncodingError> {
-    let reader = Reader::from_path(file_path)
-        .map_err(|e| DbfEncodingError::IoError(format!("Failed to open dbase file: {}", e)))?;
+    dbase_header_to_arrow_schema_with_metadata_and_options(file_path, true)
+}
+
+/// Synchronously read dbase file header with additional metadata and convert to Arrow Schema
+///
+/// `shrink_numeric_types` works as in [`dbase_header_to_arrow_schema_with_options`].
+pub fn dbase_header_to_arrow_schema_with_metadata_and_options<P: AsRef<Path>>(
+    file_path: P,
+    shrink_numeric_types: bool,
+) -> Result<(Schema, Vec<FieldInfo>), DbfEncodingError> {
+    let reader = open_dbf_header_reader(file_path.as_ref())?;
+    let descriptors = read_dbf_field_descriptors(file_path.as_ref()).unwrap_or_default();
     
     let field_infos: Vec<FieldInfo> = reader.fields().to_vec();
     
     let fields: Vec<Field> = field_infos
         .iter()
         .map(|field_info| {
-            let data_type = dbase_field_to_arrow_type(field_info);
+            let descriptor = descriptors.iter().find(|d| d.name == field_info.name());
+            let data_type = dbase_field_to_arrow_type(field_info, descriptor, shrink_numeric_types);
             let mut field = Field::new(field_info.name(), data_type, true);
             
             // Add dbase-specific metadata
             let mut metadata = std::collections::HashMap::new();
             metadata.insert("dbase_type".to_string(), format!("{:?}", field_info.field_type()));
             metadata.insert("dbase_length".to_string(), field_info.length().to_string());
+            if let Some(descriptor) = descriptor {
+                metadata.insert("dbase_decimal_count".to_string(), descriptor.decimal_count.to_string());
+            }
             
             field = field.with_metadata(metadata);
             field
@@ -391,9 +660,67 @@ pub async fn dbase_header_to_arrow_schema_with_metadata_async<P: AsRef<Path>>(
         .map_err(|e| DbfEncodingError::IoError(format!("Task join error: {}", e)))?
 }
 
+/// How strictly a DBC file's header is checked before decompressing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DbcParseMode {
+    /// Fail on any anomaly in the declared header size
+    #[default]
+    Strict,
+    /// Recover the header size from the field descriptor terminator (0x0D) when the declared one is unusable
+    Lenient,
+}
+
+/// Outcome of parsing a DBC header under a [`DbcParseMode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbcHeaderReport {
+    /// Mode the header was parsed under
+    pub mode: DbcParseMode,
+    /// Header size stored in the file (bytes 8-9)
+    pub declared_header_size: usize,
+    /// Header size actually used to find the compressed records
+    pub header_size: usize,
+    /// Problems found with the declared header size (empty for a well-formed header)
+    pub anomalies: Vec<String>,
+}
+
+impl DbcHeaderReport {
+    /// Whether the header size had to be recovered rather than taken as declared
+    pub fn recovered(&self) -> bool {
+        self.header_size != self.declared_header_size
+    }
+}
+
+/// Longest DBF header searched for a terminator: 32-byte main header, 255 descriptors and the 0x0D
+const MAX_DBF_HEADER_SIZE: usize = 32 + 255 * 32 + 1;
+
+/// Position in `header` (the DBF header after its first 10 bytes) of the descriptor terminator
+///
+/// The terminator can only sit where a descriptor would start: at DBF
+/// offset 32, 64, ...
+fn find_descriptor_terminator(header: &[u8]) -> Option<usize> {
+    (22..header.len()).step_by(32).find(|&i| header[i] == 0x0D)
+}
+
 /// Transform a DBC reader into a DBF reader for streaming decompression
 /// This follows the same approach as the datasus-dbc crate
-pub fn dbc_to_dbf_reader<R: Read>(mut dbc_reader: R) -> Result<DbfReader<R>, DbfEncodingError> {
+///
+/// Parses the header in [`DbcParseMode::Strict`] mode; see [`dbc_to_dbf_reader_with_mode`].
+pub fn dbc_to_dbf_reader<R: Read>(dbc_reader: R) -> Result<DbfReader<R>, DbfEncodingError> {
+    dbc_to_dbf_reader_with_mode(dbc_reader, DbcParseMode::Strict, None).map(|(reader, _)| reader)
+}
+
+/// Transform a DBC reader into a DBF reader, checking the header size under `mode`
+///
+/// The header size is anomalous when it is below the 10-byte pre-header,
+/// runs past `file_len` (when known), or doesn't cover the field descriptor
+/// terminator. `Strict` fails with every anomaly listed; `Lenient` reads up
+/// to the terminator instead and writes the recovered size into the DBF
+/// header it produces.
+pub fn dbc_to_dbf_reader_with_mode<R: Read>(
+    mut dbc_reader: R,
+    mode: DbcParseMode,
+    file_len: Option<u64>,
+) -> Result<(DbfReader<R>, DbcHeaderReport), DbfEncodingError> {
     // Read the 10-byte pre-header
     let mut pre_header: [u8; 10] = Default::default();
     dbc_reader
@@ -401,20 +728,58 @@ pub fn dbc_to_dbf_reader<R: Read>(mut dbc_reader: R) -> Result<DbfReader<R>, Dbf
         .map_err(|_| DbfEncodingError::ParseError("Missing or invalid DBC header".to_string()))?;
 
     // Extract header size from bytes 8-9 (little-endian)
-    let header_size: usize = usize::from(pre_header[8]) + (usize::from(pre_header[9]) << 8);
-    
-    // Validate header size
-    if header_size < 10 {
-        return Err(DbfEncodingError::ParseError(
-            format!("Invalid header size: {} (must be >= 10)", header_size)
-        ));
+    let declared_header_size: usize = usize::from(pre_header[8]) + (usize::from(pre_header[9]) << 8);
+
+    let mut anomalies = Vec::new();
+    if declared_header_size < 10 {
+        anomalies.push(format!("header size {} is below the 10-byte pre-header", declared_header_size));
     }
-    
+    // The header is followed by a 4-byte CRC32
+    if let Some(file_len) = file_len.filter(|&len| declared_header_size as u64 + 4 > len) {
+        anomalies.push(format!("header size {} runs past the end of the {}-byte file", declared_header_size, file_len));
+    }
+
     // Read the header content (excluding the 10 bytes already read)
-    let mut header: Vec<u8> = vec![0; header_size - 10];
-    dbc_reader
-        .read_exact(&mut header)
-        .map_err(|_| DbfEncodingError::ParseError("Invalid header size in DBC file".to_string()))?;
+    let mut header: Vec<u8> = Vec::new();
+    if anomalies.is_empty() {
+        header.resize(declared_header_size - 10, 0);
+        dbc_reader
+            .read_exact(&mut header)
+            .map_err(|_| DbfEncodingError::ParseError("Invalid header size in DBC file".to_string()))?;
+        if find_descriptor_terminator(&header).is_none() {
+            anomalies.push(format!("no field descriptor terminator within the declared {} header bytes", declared_header_size));
+        }
+    }
+
+    let header_size = if anomalies.is_empty() {
+        declared_header_size
+    } else if mode == DbcParseMode::Strict {
+        return Err(DbfEncodingError::ParseError(format!(
+            "DBC header rejected in strict mode: {}",
+            anomalies.join("; ")
+        )));
+    } else {
+        // Read on one byte at a time until a descriptor slot holds the terminator
+        let mut byte = [0u8; 1];
+        while find_descriptor_terminator(&header).is_none() {
+            if header.len() + 10 >= MAX_DBF_HEADER_SIZE {
+                return Err(DbfEncodingError::ParseError(format!(
+                    "DBC header unrecoverable in lenient mode: {}; no field descriptor terminator found",
+                    anomalies.join("; ")
+                )));
+            }
+            dbc_reader.read_exact(&mut byte).map_err(|_| {
+                DbfEncodingError::ParseError(format!(
+                    "DBC header unrecoverable in lenient mode: {}; file ends before the field descriptor terminator",
+                    anomalies.join("; ")
+                ))
+            })?;
+            header.push(byte[0]);
+        }
+        let header_size = header.len() + 10;
+        pre_header[8..10].copy_from_slice(&(header_size as u16).to_le_bytes());
+        header_size
+    };
 
     // Read the 4-byte CRC32 (we don't validate it, just skip it)
     let mut _crc32: [u8; 4] = Default::default();
@@ -432,7 +797,13 @@ pub fn dbc_to_dbf_reader<R: Read>(mut dbc_reader: R) -> Result<DbfReader<R>, Dbf
         compressed_content_reader,
     );
 
-    Ok(dbf_reader)
+    let report = DbcHeaderReport {
+        mode,
+        declared_header_size,
+        header_size,
+        anomalies,
+    };
+    Ok((dbf_reader, report))
 }
 
 /// Decompress a DBC file to a DBF file on disk
@@ -440,8 +811,18 @@ pub fn decompress_dbc_to_dbf<P: AsRef<Path>, Q: AsRef<Path>>(
     dbc_path: P,
     dbf_path: Q,
 ) -> Result<(), DbfEncodingError> {
+    decompress_dbc_to_dbf_with_mode(dbc_path, dbf_path, DbcParseMode::Strict).map(|_| ())
+}
+
+/// Decompress a DBC file to a DBF file on disk, checking its header under `mode`
+pub fn decompress_dbc_to_dbf_with_mode<P: AsRef<Path>, Q: AsRef<Path>>(
+    dbc_path: P,
+    dbf_path: Q,
+    mode: DbcParseMode,
+) -> Result<DbcHeaderReport, DbfEncodingError> {
     let dbc_file = File::open(dbc_path)?;
-    let mut dbf_reader = dbc_to_dbf_reader(dbc_file)?;
+    let file_len = dbc_file.metadata().ok().map(|metadata| metadata.len());
+    let (mut dbf_reader, report) = dbc_to_dbf_reader_with_mode(dbc_file, mode, file_len)?;
     
     let mut dbf_file = std::fs::OpenOptions::new()
         .write(true)
@@ -450,9 +831,237 @@ pub fn decompress_dbc_to_dbf<P: AsRef<Path>, Q: AsRef<Path>>(
         .open(dbf_path)?;
     
     std::io::copy(&mut dbf_reader, &mut dbf_file)?;
+    Ok(report)
+}
+
+/// Decompress only the header and first `max_records` records of a DBC file
+///
+/// Decompression stops once enough bytes have been produced, so peeking at
+/// the start of a large file doesn't pay for the whole file. The record count
+/// in the written header is left untouched; readers should stop after
+/// `max_records` records.
+pub fn decompress_dbc_head_to_dbf<P: AsRef<Path>, Q: AsRef<Path>>(
+    dbc_path: P,
+    dbf_path: Q,
+    max_records: usize,
+) -> Result<(), DbfEncodingError> {
+    let dbc_file = File::open(dbc_path)?;
+    let mut dbf_reader = dbc_to_dbf_reader(dbc_file)?;
+
+    // The first 32 bytes of the stream hold the DBF main header
+    let mut main_header = [0u8; 32];
+    dbf_reader
+        .read_exact(&mut main_header)
+        .map_err(|_| DbfEncodingError::ParseError("Missing or truncated DBF header".to_string()))?;
+    let header_size = u64::from(u16::from_le_bytes([main_header[8], main_header[9]]));
+    let record_size = u64::from(u16::from_le_bytes([main_header[10], main_header[11]]));
+    let limit = (header_size + record_size * max_records as u64).saturating_sub(32);
+
+    let mut dbf_file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(dbf_path)?;
+
+    std::io::Write::write_all(&mut dbf_file, &main_header)?;
+    std::io::copy(&mut dbf_reader.take(limit), &mut dbf_file)?;
     Ok(())
 }
 
+/// Copy the header and records `start..start + count` of a DBF file into a new DBF
+///
+/// Seeks straight to `header_size + start * record_size`, so earlier records
+/// are never read. The written header declares only the copied records.
+/// Returns the number of records copied (clamped to the records available).
+pub fn extract_dbf_record_window<P: AsRef<Path>, Q: AsRef<Path>>(
+    dbf_path: P,
+    out_path: Q,
+    start: usize,
+    count: usize,
+) -> Result<u64, DbfEncodingError> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let mut dbf_file = File::open(dbf_path)?;
+    let mut main_header = [0u8; 32];
+    dbf_file
+        .read_exact(&mut main_header)
+        .map_err(|_| DbfEncodingError::ParseError("Missing or truncated DBF header".to_string()))?;
+    let record_count = u64::from(u32::from_le_bytes([main_header[4], main_header[5], main_header[6], main_header[7]]));
+    let header_size = u64::from(u16::from_le_bytes([main_header[8], main_header[9]]));
+    let record_size = u64::from(u16::from_le_bytes([main_header[10], main_header[11]]));
+
+    let start = (start as u64).min(record_count);
+    let copied = (count as u64).min(record_count - start);
+
+    // Field descriptors sit between the main header and the first record
+    let mut descriptors = vec![0u8; header_size.saturating_sub(32) as usize];
+    dbf_file
+        .read_exact(&mut descriptors)
+        .map_err(|_| DbfEncodingError::ParseError("Truncated DBF field descriptors".to_string()))?;
+
+    let copied_u32 = u32::try_from(copied)
+        .map_err(|_| DbfEncodingError::ParseError("Record window too large".to_string()))?;
+    main_header[4..8].copy_from_slice(&copied_u32.to_le_bytes());
+
+    let mut out_file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(out_path)?;
+    out_file.write_all(&main_header)?;
+    out_file.write_all(&descriptors)?;
+
+    dbf_file.seek(SeekFrom::Start(header_size + start * record_size))?;
+    let written = std::io::copy(&mut (&mut dbf_file).take(copied * record_size), &mut out_file)?;
+    if written != copied * record_size {
+        return Err(DbfEncodingError::ParseError("DBF file is shorter than its header declares".to_string()));
+    }
+    out_file.write_all(&[0x1A])?;
+
+    Ok(copied)
+}
+
+/// Count the records flagged as deleted (`*`) in a DBF file
+pub fn count_deleted_dbf_records<P: AsRef<Path>>(dbf_path: P) -> Result<u64, DbfEncodingError> {
+    scan_deletion_flags(dbf_path.as_ref(), None)
+}
+
+/// Copy a DBF file with every record's deletion flag cleared
+///
+/// The dbase reader always drops deleted records; reading the copy keeps
+/// them. Returns the number of records that were flagged as deleted.
+pub fn undelete_dbf_records<P: AsRef<Path>, Q: AsRef<Path>>(
+    dbf_path: P,
+    out_path: Q,
+) -> Result<u64, DbfEncodingError> {
+    let out_file = File::create(out_path)?;
+    let mut writer = std::io::BufWriter::new(out_file);
+    let deleted = scan_deletion_flags(dbf_path.as_ref(), Some(&mut writer))?;
+    writer.into_inner().map_err(|e| DbfEncodingError::IoError(e.to_string()))?;
+    Ok(deleted)
+}
+
+/// Maps the records a dbase reader yields back to their positions in the file
+///
+/// The reader silently steps over records flagged as deleted, so the n-th
+/// record it yields is the n-th record of the file only when nothing before
+/// it was deleted. Deletion flags are read on demand, only as far as the
+/// latest lookup, so lookups must not go backwards.
+pub struct LiveRecordPositions {
+    reader: BufReader<File>,
+    record: Vec<u8>,
+    remaining: u32,
+    /// Live records passed so far
+    live: usize,
+    /// Position of the next record to read
+    next_position: usize,
+}
+
+impl LiveRecordPositions {
+    /// Start at the first record of `dbf_path`
+    pub fn open<P: AsRef<Path>>(dbf_path: P) -> Result<Self, DbfEncodingError> {
+        use std::io::{Seek, SeekFrom};
+
+        let mut reader = BufReader::new(File::open(dbf_path)?);
+        let mut main_header = [0u8; 32];
+        reader
+            .read_exact(&mut main_header)
+            .map_err(|_| DbfEncodingError::ParseError("Missing or truncated DBF header".to_string()))?;
+        let remaining = u32::from_le_bytes([main_header[4], main_header[5], main_header[6], main_header[7]]);
+        let header_size = u64::from(u16::from_le_bytes([main_header[8], main_header[9]]));
+        let record_size = usize::from(u16::from_le_bytes([main_header[10], main_header[11]]));
+        reader.seek(SeekFrom::Start(header_size))?;
+
+        Ok(Self {
+            reader,
+            record: vec![0u8; record_size.max(1)],
+            remaining,
+            live: 0,
+            next_position: 0,
+        })
+    }
+
+    /// Position in the file of the live record the reader yielded as `live_index`
+    pub fn position(&mut self, live_index: usize) -> Result<usize, DbfEncodingError> {
+        if live_index + 1 == self.live {
+            return Ok(self.next_position - 1);
+        }
+        if live_index < self.live {
+            return Err(DbfEncodingError::ParseError(format!(
+                "record {} was already passed",
+                live_index
+            )));
+        }
+
+        loop {
+            if self.remaining == 0 {
+                return Err(DbfEncodingError::ParseError("DBF file is shorter than its header declares".to_string()));
+            }
+            self.reader
+                .read_exact(&mut self.record)
+                .map_err(|_| DbfEncodingError::ParseError("DBF file is shorter than its header declares".to_string()))?;
+            self.remaining -= 1;
+            self.next_position += 1;
+            if self.record[0] != b'*' {
+                self.live += 1;
+                if self.live == live_index + 1 {
+                    return Ok(self.next_position - 1);
+                }
+            }
+        }
+    }
+
+    /// Raw bytes of the record found by the last lookup, deletion flag first
+    pub fn record(&self) -> &[u8] {
+        &self.record
+    }
+}
+
+/// Walk the records of a DBF file counting deletion flags, optionally copying it with the flags cleared
+fn scan_deletion_flags(dbf_path: &Path, mut out: Option<&mut dyn std::io::Write>) -> Result<u64, DbfEncodingError> {
+    use std::io::Write;
+
+    let mut reader = BufReader::new(File::open(dbf_path)?);
+    let mut main_header = [0u8; 32];
+    reader
+        .read_exact(&mut main_header)
+        .map_err(|_| DbfEncodingError::ParseError("Missing or truncated DBF header".to_string()))?;
+    let record_count = u32::from_le_bytes([main_header[4], main_header[5], main_header[6], main_header[7]]);
+    let header_size = usize::from(u16::from_le_bytes([main_header[8], main_header[9]]));
+    let record_size = usize::from(u16::from_le_bytes([main_header[10], main_header[11]]));
+
+    let mut descriptors = vec![0u8; header_size.saturating_sub(32)];
+    reader
+        .read_exact(&mut descriptors)
+        .map_err(|_| DbfEncodingError::ParseError("Truncated DBF field descriptors".to_string()))?;
+    if let Some(out) = out.as_mut() {
+        out.write_all(&main_header)?;
+        out.write_all(&descriptors)?;
+    }
+
+    let mut deleted = 0;
+    let mut record = vec![0u8; record_size.max(1)];
+    for _ in 0..record_count {
+        reader
+            .read_exact(&mut record)
+            .map_err(|_| DbfEncodingError::ParseError("DBF file is shorter than its header declares".to_string()))?;
+        if record[0] == b'*' {
+            deleted += 1;
+            record[0] = b' ';
+        }
+        if let Some(out) = out.as_mut() {
+            out.write_all(&record)?;
+        }
+    }
+
+    // Keep the end-of-file marker and anything else after the records
+    if let Some(out) = out {
+        std::io::copy(&mut reader, out)?;
+    }
+
+    Ok(deleted)
+}
+
 /// Asynchronously decompress a DBC file to a DBF file on disk
 pub async fn decompress_dbc_to_dbf_async<P: AsRef<Path>, Q: AsRef<Path>>(
     dbc_path: P,
@@ -650,6 +1259,136 @@ mod tests {
     // which have private fields and no public constructor. The function will be tested
     // indirectly through the file-based schema conversion functions when actual DBF files are available.
 
+    /// Build a minimal DBF header with the given (name, type, width, decimals) fields
+    fn build_dbf_header(fields: &[(&str, u8, u8, u8)]) -> Vec<u8> {
+        let header_size = 32 + fields.len() * 32 + 1;
+        let mut bytes = vec![0u8; 32];
+        bytes[0] = 0x03;
+        bytes[8..10].copy_from_slice(&(header_size as u16).to_le_bytes());
+        for (name, field_type, width, decimals) in fields {
+            let mut descriptor = [0u8; 32];
+            descriptor[..name.len()].copy_from_slice(name.as_bytes());
+            descriptor[11] = *field_type;
+            descriptor[16] = *width;
+            descriptor[17] = *decimals;
+            bytes.extend_from_slice(&descriptor);
+        }
+        bytes.push(0x0D);
+        bytes
+    }
+
+    #[test]
+    fn test_dbc_parse_mode_on_oversized_header() {
+        // DBC layout: DBF header, 4-byte CRC32, then the imploded records
+        let dbf_header = build_dbf_header(&[("UF_ZI", b'C', 6, 0)]);
+        let dbc = |declared: u16| {
+            let mut bytes = dbf_header.clone();
+            bytes[8..10].copy_from_slice(&declared.to_le_bytes());
+            bytes.extend_from_slice(&[0u8; 4]);
+            bytes.extend_from_slice(b"compressed");
+            bytes
+        };
+        let parse = |declared: u16, mode: DbcParseMode| {
+            let bytes = dbc(declared);
+            let len = bytes.len() as u64;
+            dbc_to_dbf_reader_with_mode(Cursor::new(bytes), mode, Some(len))
+        };
+
+        let (_, report) = parse(65, DbcParseMode::Strict).unwrap();
+        assert_eq!((report.declared_header_size, report.header_size), (65, 65));
+        assert!(report.anomalies.is_empty() && !report.recovered());
+
+        match parse(0xFFFF, DbcParseMode::Strict) {
+            Err(DbfEncodingError::ParseError(msg)) => {
+                assert!(msg.contains("strict mode") && msg.contains("runs past the end"), "{msg}");
+            }
+            Err(other) => panic!("expected a parse error, got {other}"),
+            Ok(_) => panic!("expected strict mode to reject the header"),
+        }
+
+        let (mut reader, report) = parse(0xFFFF, DbcParseMode::Lenient).unwrap();
+        assert_eq!(report.mode, DbcParseMode::Lenient);
+        assert_eq!((report.declared_header_size, report.header_size), (0xFFFF, 65));
+        assert!(report.recovered());
+        assert_eq!(report.anomalies.len(), 1);
+        // The DBF produced carries the recovered size and the untouched descriptors
+        let mut header = [0u8; 65];
+        reader.read_exact(&mut header).unwrap();
+        assert_eq!(u16::from_le_bytes([header[8], header[9]]), 65);
+        assert_eq!(&header[10..], &dbf_header[10..]);
+
+        // Too small to even hold the pre-header
+        assert!(parse(4, DbcParseMode::Strict).is_err());
+        assert_eq!(parse(4, DbcParseMode::Lenient).unwrap().1.header_size, 65);
+
+        // Declared within bounds but short of the terminator
+        assert!(parse(40, DbcParseMode::Strict).is_err());
+        assert_eq!(parse(40, DbcParseMode::Lenient).unwrap().1.header_size, 65);
+    }
+
+    #[test]
+    fn test_parse_dbf_field_descriptors() {
+        let header = build_dbf_header(&[("IDADE", b'N', 3, 0), ("VAL_TOT", b'N', 12, 2), ("UF_ZI", b'C', 6, 0)]);
+        let descriptors = parse_dbf_field_descriptors(Cursor::new(header)).unwrap();
+
+        assert_eq!(descriptors.len(), 3);
+        assert_eq!(descriptors[0].name, "IDADE");
+        assert_eq!(descriptors[0].field_type, 'N');
+        assert_eq!(descriptors[0].width, 3);
+        assert_eq!(descriptors[1].decimal_count, 2);
+        assert_eq!(descriptors[2].field_type, 'C');
+    }
+
+    #[test]
+    fn test_parse_dbf_field_descriptors_truncated() {
+        let mut header = build_dbf_header(&[("IDADE", b'N', 3, 0)]);
+        header.truncate(40);
+        assert!(parse_dbf_field_descriptors(Cursor::new(header)).is_err());
+    }
+
+    #[test]
+    fn test_encoding_from_language_driver() {
+        assert_eq!(DbaseEncoding::from_language_driver(0x02), Some(DbaseEncoding::Cp850));
+        assert_eq!(DbaseEncoding::from_language_driver(0x57), Some(DbaseEncoding::Latin1));
+        assert_eq!(DbaseEncoding::from_language_driver(0x00), None);
+        assert_eq!(DbaseEncoding::default(), DbaseEncoding::Latin1);
+    }
+
+    #[test]
+    fn test_dbf_codepage() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("header.dbf");
+        let mut header = build_dbf_header(&[("MUNIC", b'C', 20, 0)]);
+
+        header[29] = 0x02;
+        std::fs::write(&path, &header).unwrap();
+        assert_eq!(dbf_codepage(&path), Some(DbaseEncoding::Cp850));
+
+        header[29] = 0x00;
+        std::fs::write(&path, &header).unwrap();
+        assert_eq!(dbf_codepage(&path), None);
+
+        assert_eq!(dbf_codepage(temp_dir.path().join("missing.dbf")), None);
+    }
+
+    #[test]
+    fn test_encoding_decode_lossy() {
+        assert_eq!(DbaseEncoding::Latin1.decode_lossy(b"S\xe3o Paulo"), "São Paulo");
+        assert_eq!(DbaseEncoding::Cp850.decode_lossy(b"S\xc6o Paulo"), "São Paulo");
+        assert_eq!(DbaseEncoding::Utf8.decode_lossy("São Paulo".as_bytes()), "São Paulo");
+    }
+
+    #[test]
+    fn test_integer_type_for_width() {
+        assert_eq!(integer_type_for_width(3, true), DataType::Int16);
+        assert_eq!(integer_type_for_width(4, true), DataType::Int16);
+        assert_eq!(integer_type_for_width(5, true), DataType::Int32);
+        assert_eq!(integer_type_for_width(9, true), DataType::Int32);
+        assert_eq!(integer_type_for_width(10, true), DataType::Int64);
+        assert_eq!(integer_type_for_width(3, false), DataType::Int64);
+        assert_eq!(integer_type_for_width(20, true), DataType::Float64);
+    }
+
     #[test]
     fn test_arrow_schema_functions_exist() {
         // This test verifies that our functions compile and can be called
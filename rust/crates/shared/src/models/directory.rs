@@ -1,5 +1,9 @@
 use crate::models::file::File;
+use crate::models::ftp_path::FtpPath;
 use crate::models::async_utils::{async_cache, content_cache};
+use crate::models::date_utils::Period;
+use crate::models::regex_patterns::DataSusFileInfo;
+use crate::models::retry::{RetryPolicy, Retryable};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -7,6 +11,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use futures::future::join_all;
+use tracing::Instrument;
 
 /// Type alias for directory content
 pub type DirectoryContent = HashMap<String, DirectoryEntry>;
@@ -37,6 +42,24 @@ pub struct FlatDirectoryEntry {
 /// Type alias for flattened directory listing results
 pub type FlatDirectoryListing = Vec<FlatDirectoryEntry>;
 
+/// Differences between two listings of the same directory, entries sorted by name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListingDiff {
+    /// Entries only present in the new listing
+    pub added: Vec<DirectoryEntry>,
+    /// Entries only present in the old listing
+    pub removed: Vec<DirectoryEntry>,
+    /// Entries present in both whose size, modification time or kind changed (new version)
+    pub modified: Vec<DirectoryEntry>,
+}
+
+impl ListingDiff {
+    /// Whether the two listings were identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
 /// Trait for different file system providers
 #[async_trait]
 pub trait FileSystemProvider: Send + Sync {
@@ -89,11 +112,7 @@ pub trait FileSystemProvider: Send + Sync {
         
         // Process current level entries
         for (name, entry) in content {
-            let full_path = if path.ends_with('/') {
-                format!("{}{}", path, name)
-            } else {
-                format!("{}/{}", path, name)
-            };
+            let full_path = FtpPath::new(path).join(&name).to_string();
             
             let flat_entry = FlatDirectoryEntry {
                 path: full_path.clone(),
@@ -136,6 +155,23 @@ pub trait FileSystemProvider: Send + Sync {
         Ok(results)
     }
     
+    /// Sum the sizes of all files under a directory, walking subdirectories
+    async fn directory_size(&self, path: &str) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let mut total_size = 0u64;
+        let mut pending = vec![path.to_string()];
+        
+        while let Some(current) = pending.pop() {
+            for (name, entry) in self.list_directory(&current).await? {
+                match entry {
+                    DirectoryEntry::File(file) => total_size += file.size_bytes().unwrap_or(0),
+                    DirectoryEntry::Directory(_) => pending.push(FtpPath::new(&current).join(&name).to_string()),
+                }
+            }
+        }
+        
+        Ok(total_size)
+    }
+    
     /// Check if a path exists
     async fn exists(&self, path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
     
@@ -144,6 +180,14 @@ pub trait FileSystemProvider: Send + Sync {
     
     /// Get the name of the file system provider
     fn provider_name(&self) -> &'static str;
+
+    /// Key listings of `path` are cached under, or `None` to list afresh every time
+    ///
+    /// Providers whose listings depend on more than the path (server, filters)
+    /// must fold that into the key so differently configured providers don't share entries.
+    fn cache_key(&self, path: &str) -> Option<String> {
+        Some(content_cache::generate_provider_cache_key(self.provider_name(), path))
+    }
 }
 
 /// Local file system provider
@@ -208,6 +252,504 @@ impl FileSystemProvider for LocalFileSystemProvider {
     fn provider_name(&self) -> &'static str {
         "local"
     }
+
+    /// Local listings are cheap and can change at any moment, so they're never cached
+    fn cache_key(&self, _path: &str) -> Option<String> {
+        None
+    }
+}
+
+/// How FTP listings treat a `.DBF` file next to a `.DBC` with the same stem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DbfDbcPolicy {
+    /// Drop the `.DBF` when a matching `.DBC` exists (as in the Python version)
+    #[default]
+    PreferDbc,
+    /// Keep both files
+    KeepBoth,
+    /// Drop the `.DBC` when a matching `.DBF` exists
+    PreferDbf,
+}
+
+/// Which files FTP listings keep, by extension (directories are always kept)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtensionFilter {
+    /// Keep only files with one of these extensions
+    Allow(Vec<String>),
+    /// Drop files with any of these extensions
+    Block(Vec<String>),
+}
+
+impl ExtensionFilter {
+    /// Whether `file` passes the filter (case-insensitive, leading dot optional)
+    pub fn keeps(&self, file: &File) -> bool {
+        match self {
+            ExtensionFilter::Allow(extensions) => extensions.iter().any(|ext| file.has_extension(ext)),
+            ExtensionFilter::Block(extensions) => !extensions.iter().any(|ext| file.has_extension(ext)),
+        }
+    }
+}
+
+/// How FTP data connections are opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FtpMode {
+    /// `PASV`: the server opens a port and the client connects (works behind most NATs)
+    #[default]
+    Passive,
+    /// `EPSV`: passive mode with only a port in the reply, for IPv6 and servers behind NAT
+    ExtendedPassive,
+    /// `PORT`: the client listens and the server connects back
+    Active,
+}
+
+impl From<FtpMode> for suppaftp::Mode {
+    fn from(mode: FtpMode) -> Self {
+        match mode {
+            FtpMode::Passive => suppaftp::Mode::Passive,
+            FtpMode::ExtendedPassive => suppaftp::Mode::ExtendedPassive,
+            FtpMode::Active => suppaftp::Mode::Active,
+        }
+    }
+}
+
+/// Control port conventionally used by implicit FTPS servers
+pub const FTPS_IMPLICIT_PORT: u16 = 990;
+
+/// TLS negotiation on the FTP control connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FtpSecurity {
+    /// Unencrypted FTP (DATASUS)
+    #[default]
+    Plain,
+    /// Connect in plain text, then upgrade with `AUTH TLS` before logging in
+    Explicit,
+    /// TLS from the first byte, usually on [`FTPS_IMPLICIT_PORT`]
+    Implicit,
+}
+
+/// Whether a failed FTP operation is worth retrying
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FtpErrorKind {
+    /// Connection drops and 4xx replies (e.g. 421 too many users), usually gone on retry
+    Transient,
+    /// 5xx replies (e.g. 550 no such directory) and protocol errors
+    Permanent,
+}
+
+/// Refuse a passive data address whose port is outside `range`
+fn check_passive_port(address: std::net::SocketAddr, range: &std::ops::RangeInclusive<u16>) -> suppaftp::FtpResult<()> {
+    if range.contains(&address.port()) {
+        return Ok(());
+    }
+    Err(suppaftp::FtpError::ConnectionError(std::io::Error::new(
+        std::io::ErrorKind::ConnectionRefused,
+        format!(
+            "server offered passive data port {}, outside the allowed range {}-{}",
+            address.port(),
+            range.start(),
+            range.end()
+        ),
+    )))
+}
+
+/// Classify an `FtpError` into transient vs permanent
+pub fn classify_ftp_error(error: &suppaftp::FtpError) -> FtpErrorKind {
+    use suppaftp::FtpError;
+
+    match error {
+        FtpError::ConnectionError(_) => FtpErrorKind::Transient,
+        // An empty or cut-off reply: the control connection dropped mid-command
+        FtpError::BadResponse => FtpErrorKind::Transient,
+        FtpError::UnexpectedResponse(response) => {
+            if (400..500).contains(&response.status.code()) {
+                FtpErrorKind::Transient
+            } else {
+                FtpErrorKind::Permanent
+            }
+        }
+        _ => FtpErrorKind::Permanent,
+    }
+}
+
+/// Classify a boxed provider error; anything that is not an FTP or I/O error is permanent
+pub fn classify_provider_error(error: &(dyn std::error::Error + Send + Sync + 'static)) -> FtpErrorKind {
+    if let Some(ftp_error) = error.downcast_ref::<suppaftp::FtpError>() {
+        classify_ftp_error(ftp_error)
+    } else if error.downcast_ref::<std::io::Error>().is_some() {
+        FtpErrorKind::Transient
+    } else if error.downcast_ref::<FtpTimeout>().is_some() {
+        FtpErrorKind::Transient
+    } else if let Some(retry_error) = error.downcast_ref::<ListingRetryError>() {
+        classify_provider_error(retry_error.source.as_ref())
+    } else {
+        FtpErrorKind::Permanent
+    }
+}
+
+/// Default limit for a single FTP command
+pub const DEFAULT_FTP_OPERATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Most connections `FtpFileSystemProvider::exists_many` opens at once
+pub const EXISTS_MANY_MAX_CONNECTIONS: usize = 4;
+
+/// Default for [`set_global_ftp_connection_limit`]
+pub const DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT: usize = 16;
+
+/// Process-wide cap on open FTP connections, shared by every provider and downloader
+///
+/// Each connection made by `FtpFileSystemProvider::create_connection` holds a
+/// permit until it is dropped, so DATASUS never sees more connections from this
+/// process than the limit, however many downloaders are running.
+#[derive(Debug)]
+pub struct FtpConnectionLimiter {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    limit: std::sync::atomic::AtomicUsize,
+    /// Held while the permit count changes, so resizes don't interleave
+    resizing: tokio::sync::Mutex<()>,
+}
+
+static GLOBAL_FTP_CONNECTION_LIMITER: once_cell::sync::Lazy<FtpConnectionLimiter> =
+    once_cell::sync::Lazy::new(|| FtpConnectionLimiter::new(DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT));
+
+impl FtpConnectionLimiter {
+    /// A limiter allowing `limit` connections at once (at least 1)
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(limit)),
+            limit: std::sync::atomic::AtomicUsize::new(limit),
+            resizing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// The limiter every FTP connection goes through
+    pub fn global() -> &'static Self {
+        &GLOBAL_FTP_CONNECTION_LIMITER
+    }
+
+    /// Connections allowed at once
+    pub fn limit(&self) -> usize {
+        self.limit.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Change the limit; connections already open count against the new one
+    ///
+    /// Raising it frees slots at once. Lowering it takes back free slots,
+    /// waiting for open connections to close until there are few enough.
+    pub async fn set_limit(&self, limit: usize) {
+        let limit = limit.max(1);
+        let _resizing = self.resizing.lock().await;
+        let current = self.limit();
+        if limit > current {
+            self.semaphore.add_permits(limit - current);
+        } else if limit < current {
+            let surplus = u32::try_from(current - limit).unwrap_or(u32::MAX);
+            self.semaphore
+                .acquire_many(surplus)
+                .await
+                .expect("FTP connection semaphore is never closed")
+                .forget();
+        }
+        self.limit.store(limit, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Wait for a free connection slot, held until the permit is dropped
+    pub async fn acquire(&self) -> tokio::sync::OwnedSemaphorePermit {
+        self.semaphore.clone().acquire_owned().await.expect("FTP connection semaphore is never closed")
+    }
+}
+
+/// Cap open FTP connections across the whole process (default [`DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT`])
+///
+/// Lowering the limit waits until enough open connections have closed.
+pub async fn set_global_ftp_connection_limit(limit: usize) {
+    FtpConnectionLimiter::global().set_limit(limit).await;
+}
+
+/// Current process-wide FTP connection limit
+pub fn global_ftp_connection_limit() -> usize {
+    FtpConnectionLimiter::global().limit()
+}
+
+/// An open FTP connection holding a [`FtpConnectionLimiter`] permit
+///
+/// Derefs to the underlying stream; the permit is released when it is dropped.
+pub struct FtpConnection {
+    stream: suppaftp::AsyncRustlsFtpStream,
+    _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+impl std::ops::Deref for FtpConnection {
+    type Target = suppaftp::AsyncRustlsFtpStream;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stream
+    }
+}
+
+impl std::ops::DerefMut for FtpConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stream
+    }
+}
+
+/// An FTP operation did not complete within the provider's `operation_timeout`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FtpTimeout {
+    pub operation: String,
+    pub timeout: std::time::Duration,
+}
+
+impl fmt::Display for FtpTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FTP {} timed out after {:?}", self.operation, self.timeout)
+    }
+}
+
+impl std::error::Error for FtpTimeout {}
+
+/// Run one FTP operation, failing with `FtpTimeout` if it doesn't finish within `timeout`
+pub async fn with_ftp_timeout<T, E, F>(
+    operation: &str,
+    timeout: std::time::Duration,
+    future: F,
+) -> Result<T, Box<dyn std::error::Error + Send + Sync>>
+where
+    F: std::future::Future<Output = Result<T, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    match tokio::time::timeout(timeout, future).await {
+        Ok(result) => result.map_err(Into::into),
+        Err(_) => Err(Box::new(FtpTimeout {
+            operation: operation.to_string(),
+            timeout,
+        })),
+    }
+}
+
+/// Retry settings for FTP directory listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListingRetryConfig {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further retry
+    pub initial_backoff: std::time::Duration,
+}
+
+impl Default for ListingRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(500),
+        }
+    }
+}
+
+impl ListingRetryConfig {
+    /// The equivalent [`RetryPolicy`]: plain doubling, no cap and no jitter
+    pub fn policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.max_retries,
+            base_backoff: self.initial_backoff,
+            max_backoff: std::time::Duration::MAX,
+            jitter: 0.0,
+        }
+    }
+}
+
+/// Final listing error, with the number of attempts made
+#[derive(Debug)]
+pub struct ListingRetryError {
+    pub path: String,
+    pub attempts: u32,
+    pub source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl fmt::Display for ListingRetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "listing '{}' failed after {} attempt(s): {}", self.path, self.attempts, self.source)
+    }
+}
+
+impl std::error::Error for ListingRetryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Run a listing operation, retrying transient failures with exponential backoff
+pub async fn retry_listing<T, F, Fut>(
+    path: &str,
+    config: &ListingRetryConfig,
+    mut operation: F,
+) -> Result<T, ListingRetryError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, Box<dyn std::error::Error + Send + Sync>>>,
+{
+    let mut attempts = 0;
+    let classify = |error: &Box<dyn std::error::Error + Send + Sync>| {
+        let retryable = classify_provider_error(error.as_ref()) == FtpErrorKind::Transient;
+        if retryable {
+            log::debug!("Transient error listing {}: {}", path, error);
+        }
+        Retryable::from(retryable)
+    };
+
+    config
+        .policy()
+        .retry(classify, || {
+            attempts += 1;
+            operation()
+        })
+        .await
+        .map_err(|source| ListingRetryError {
+            path: path.to_string(),
+            attempts,
+            source,
+        })
+}
+
+/// Sorted, distinct periods available for `group` and `uf` in the directory at `path`
+///
+/// Files are matched by their DATASUS name (e.g. `RDSP2012.dbc`), so directories
+/// mixing several groups and UFs are fine; other files are ignored.
+pub async fn available_periods<P: FileSystemProvider + ?Sized>(
+    provider: &P,
+    path: &str,
+    group: &str,
+    uf: &str,
+) -> Result<Vec<Period>, Box<dyn std::error::Error + Send + Sync>> {
+    let content = provider.list_directory(path).await?;
+
+    let mut periods: Vec<Period> = content
+        .values()
+        .filter_map(|entry| match entry {
+            DirectoryEntry::File(file) => DataSusFileInfo::parse(&file.basename),
+            DirectoryEntry::Directory(_) => None,
+        })
+        .filter(|info| info.group_name.eq_ignore_ascii_case(group) && info.uf_code.eq_ignore_ascii_case(uf))
+        .map(|info| info.period())
+        .collect();
+
+    periods.sort();
+    periods.dedup();
+    Ok(periods)
+}
+
+/// Which UFs have a file for which periods, for one group in one directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvailabilityMatrix {
+    /// Group code, uppercased
+    pub group: String,
+    /// Every month from the earliest to the latest file found, in order
+    pub periods: Vec<Period>,
+    /// Periods with a file, per UF (UFs without any file are absent)
+    pub available: std::collections::BTreeMap<String, std::collections::BTreeSet<Period>>,
+}
+
+impl AvailabilityMatrix {
+    /// Whether `uf` has a file for `period`
+    pub fn is_available(&self, uf: &str, period: &Period) -> bool {
+        self.available
+            .get(&uf.to_ascii_uppercase())
+            .is_some_and(|periods| periods.contains(period))
+    }
+
+    /// `(uf, period)` cells without a file, by UF then period
+    pub fn missing(&self) -> Vec<(String, Period)> {
+        self.available
+            .iter()
+            .flat_map(|(uf, available)| {
+                self.periods
+                    .iter()
+                    .filter(|period| !available.contains(period))
+                    .map(|period| (uf.clone(), *period))
+            })
+            .collect()
+    }
+
+    /// One line per UF with `x` for an available period and `.` for a hole
+    ///
+    /// The header row lists the first and last period.
+    pub fn to_table(&self) -> String {
+        let mut table = match (self.periods.first(), self.periods.last()) {
+            (Some(first), Some(last)) => format!("{}  {} .. {}\n", self.group, first, last),
+            _ => format!("{}  (no files)\n", self.group),
+        };
+        for (uf, available) in &self.available {
+            let cells: String = self
+                .periods
+                .iter()
+                .map(|period| if available.contains(period) { 'x' } else { '.' })
+                .collect();
+            table.push_str(&format!("{:<4}{}\n", uf, cells));
+        }
+        table
+    }
+
+    /// The matrix as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Build the UF-by-period availability of `group` from one listing of `path`
+///
+/// Files are matched by their DATASUS name like [`available_periods`]; the
+/// period axis covers every month between the earliest and latest file, so
+/// months missing for all UFs show up as holes too.
+pub async fn availability_matrix<P: FileSystemProvider + ?Sized>(
+    provider: &P,
+    path: &str,
+    group: &str,
+) -> Result<AvailabilityMatrix, Box<dyn std::error::Error + Send + Sync>> {
+    let content = provider.list_directory(path).await?;
+
+    let mut available: std::collections::BTreeMap<String, std::collections::BTreeSet<Period>> = Default::default();
+    for entry in content.values() {
+        let DirectoryEntry::File(file) = entry else { continue };
+        let Some(info) = DataSusFileInfo::parse(&file.basename) else { continue };
+        if info.group_name.eq_ignore_ascii_case(group) {
+            available.entry(info.uf_code.to_ascii_uppercase()).or_default().insert(info.period());
+        }
+    }
+
+    let first = available.values().filter_map(|periods| periods.first()).min();
+    let last = available.values().filter_map(|periods| periods.last()).max();
+    let periods = match (first, last) {
+        // File periods are always monthly, so the granularities match
+        (Some(first), Some(last)) => Period::range(*first, *last)?.collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(AvailabilityMatrix {
+        group: group.to_ascii_uppercase(),
+        periods,
+        available,
+    })
+}
+
+/// TLS connector for FTPS, trusting the platform's root certificates
+fn tls_connector() -> suppaftp::AsyncRustlsConnector {
+    use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+    use tokio_rustls::rustls::crypto::aws_lc_rs;
+
+    let mut roots = RootCertStore::empty();
+    let native = rustls_native_certs::load_native_certs();
+    for error in &native.errors {
+        log::debug!("Skipping unreadable native certificate: {}", error);
+    }
+    let (added, ignored) = roots.add_parsable_certificates(native.certs);
+    log::debug!("Loaded {} native root certificates ({} ignored)", added, ignored);
+
+    // Pin the provider: both ring and aws-lc-rs are compiled in, so the implicit default is ambiguous
+    let config = ClientConfig::builder_with_provider(Arc::new(aws_lc_rs::default_provider()))
+        .with_safe_default_protocol_versions()
+        .expect("aws-lc-rs supports the default TLS versions")
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    suppaftp::AsyncRustlsConnector::from(futures_rustls::TlsConnector::from(Arc::new(config)))
 }
 
 /// FTP file system provider for DATASUS
@@ -219,6 +761,24 @@ pub struct FtpFileSystemProvider {
     pub base_path: String,
     /// FTP port (default 21)
     pub port: u16,
+    /// Which file to keep when both `.DBC` and `.DBF` exist for the same stem
+    pub dbf_dbc_policy: DbfDbcPolicy,
+    /// Retry behaviour for transient listing failures
+    pub listing_retry: ListingRetryConfig,
+    /// Limit for each FTP command (connect, login, cwd, list, ...) before it fails with `FtpTimeout`
+    pub operation_timeout: std::time::Duration,
+    /// Password sent with the anonymous login, conventionally a contact email (default empty)
+    pub anonymous_identity: String,
+    /// Optional extension allow/block list applied to listed files
+    pub extension_filter: Option<ExtensionFilter>,
+    /// Data connection mode for listings and downloads (default passive)
+    pub transfer_mode: FtpMode,
+    /// Data ports the server may offer in passive mode, e.g. what a firewall lets through
+    pub passive_port_range: Option<std::ops::RangeInclusive<u16>>,
+    /// TLS negotiation for the control connection (default plain)
+    pub security: FtpSecurity,
+    /// Limiter connections wait on, instead of the process-wide one
+    pub connection_limiter: Option<Arc<FtpConnectionLimiter>>,
 }
 
 impl FtpFileSystemProvider {
@@ -228,6 +788,15 @@ impl FtpFileSystemProvider {
             host: "ftp.datasus.gov.br".to_string(),
             base_path: "/dissemin/publicos".to_string(),
             port: 21,
+            dbf_dbc_policy: DbfDbcPolicy::default(),
+            listing_retry: ListingRetryConfig::default(),
+            operation_timeout: DEFAULT_FTP_OPERATION_TIMEOUT,
+            anonymous_identity: String::new(),
+            extension_filter: None,
+            transfer_mode: FtpMode::default(),
+            passive_port_range: None,
+            security: FtpSecurity::default(),
+            connection_limiter: None,
         }
     }
     
@@ -237,9 +806,75 @@ impl FtpFileSystemProvider {
             host,
             base_path,
             port: port.unwrap_or(21),
+            dbf_dbc_policy: DbfDbcPolicy::default(),
+            listing_retry: ListingRetryConfig::default(),
+            operation_timeout: DEFAULT_FTP_OPERATION_TIMEOUT,
+            anonymous_identity: String::new(),
+            extension_filter: None,
+            transfer_mode: FtpMode::default(),
+            passive_port_range: None,
+            security: FtpSecurity::default(),
+            connection_limiter: None,
         }
     }
     
+    /// Set the policy for listings containing both `.DBC` and `.DBF` for the same stem
+    pub fn with_dbf_dbc_policy(mut self, policy: DbfDbcPolicy) -> Self {
+        self.dbf_dbc_policy = policy;
+        self
+    }
+    
+    /// Set the per-operation FTP timeout
+    pub fn with_operation_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.operation_timeout = timeout;
+        self
+    }
+    
+    /// Set the data connection mode
+    pub fn with_transfer_mode(mut self, mode: FtpMode) -> Self {
+        self.transfer_mode = mode;
+        self
+    }
+    
+    /// Only open passive data connections on ports in `range`
+    ///
+    /// A `PASV`/`EPSV` reply outside it fails the transfer with a connection
+    /// error, which listings and downloads retry on a fresh connection.
+    pub fn with_passive_port_range(mut self, range: std::ops::RangeInclusive<u16>) -> Self {
+        self.passive_port_range = Some(range);
+        self
+    }
+    
+    /// Set how TLS is negotiated; `Implicit` servers usually need [`FTPS_IMPLICIT_PORT`] as the port
+    pub fn with_security(mut self, security: FtpSecurity) -> Self {
+        self.security = security;
+        self
+    }
+    
+    /// Restrict listed files by extension
+    pub fn with_extension_filter(mut self, filter: ExtensionFilter) -> Self {
+        self.extension_filter = Some(filter);
+        self
+    }
+    
+    /// Identify this client (e.g. with an email) in the anonymous login password
+    pub fn with_anonymous_identity(mut self, identity: impl Into<String>) -> Self {
+        self.anonymous_identity = identity.into();
+        self
+    }
+    
+    /// Take connection slots from `limiter` instead of the global [`FtpConnectionLimiter`]
+    pub fn with_connection_limiter(mut self, limiter: Arc<FtpConnectionLimiter>) -> Self {
+        self.connection_limiter = Some(limiter);
+        self
+    }
+    
+    /// Configure how transient listing failures are retried
+    pub fn with_listing_retry(mut self, config: ListingRetryConfig) -> Self {
+        self.listing_retry = config;
+        self
+    }
+    
     /// Parse FTP directory listing line
     /// Format: "MM-DD-YY HH:MMxm <DIR> name" or "MM-DD-YY HH:MMxm size name"
     pub fn parse_ftp_line(&self, line: &str, current_path: &str) -> Option<(String, DirectoryEntry)> {
@@ -262,11 +897,7 @@ impl FtpFileSystemProvider {
         if parts[2] == "<DIR>" {
             // Directory entry
             let name = parts[3..].join(" ");
-            let dir_path = if current_path.ends_with('/') {
-                format!("{}{}", current_path, name)
-            } else {
-                format!("{}/{}", current_path, name)
-            };
+            let dir_path = FtpPath::new(current_path).join(&name).to_string();
             
             // Create directory (this is a simplified version - we'll need to handle this properly)
             // For now, we'll create a basic directory structure
@@ -304,20 +935,263 @@ impl FtpFileSystemProvider {
         }
     }
     
-    /// Create FTP connection
-    async fn create_connection(&self) -> Result<suppaftp::AsyncRustlsFtpStream, Box<dyn std::error::Error + Send + Sync>> {
-        use suppaftp::{AsyncRustlsFtpStream, Mode};
+    /// Parse raw FTP listing lines and apply the provider's `DbfDbcPolicy`
+    pub fn parse_ftp_listing(&self, lines: &[String], current_path: &str) -> DirectoryContent {
+        let entries: Vec<(String, DirectoryEntry)> = lines
+            .iter()
+            .filter_map(|line| self.parse_ftp_line(line, current_path))
+            .filter(|(_, entry)| self.passes_extension_filter(entry))
+            .collect();
+        
+        self.apply_dbf_dbc_policy(entries, current_path)
+    }
+    
+    /// Parse one MLSD line: `type=file;size=4096;modify=20231201143000; RDSP2012.DBC`
+    /// 
+    /// Returns None for `cdir`/`pdir` entries and lines without a type fact.
+    pub fn parse_mlsd_line(&self, line: &str, current_path: &str) -> Option<(String, DirectoryEntry)> {
+        use crate::models::file_info::{FileInfo, FileSize};
+        use chrono::{DateTime, Utc, NaiveDateTime};
+        
+        // Facts end at the first space; the rest of the line is the name
+        let (facts, name) = line.trim_end_matches(['\r', '\n']).split_once(' ')?;
+        if name.is_empty() {
+            return None;
+        }
+        
+        let mut entry_type = None;
+        let mut size = None;
+        let mut modify = None;
+        for fact in facts.split(';').filter(|fact| !fact.is_empty()) {
+            let (key, value) = fact.split_once('=')?;
+            match key.to_ascii_lowercase().as_str() {
+                "type" => entry_type = Some(value.to_ascii_lowercase()),
+                "size" => size = value.parse::<u64>().ok(),
+                "modify" => {
+                    // YYYYMMDDHHMMSS with optional fractional seconds, always UTC
+                    let seconds = value.split('.').next().unwrap_or(value);
+                    modify = NaiveDateTime::parse_from_str(seconds, "%Y%m%d%H%M%S")
+                        .ok()
+                        .map(|naive| DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc));
+                }
+                _ => {}
+            }
+        }
+        
+        let name = name.to_string();
+        match entry_type?.as_str() {
+            "dir" => {
+                let directory = Directory {
+                    path: FtpPath::new(current_path).join(&name).to_string(),
+                    name: name.clone(),
+                    loaded: false,
+                    provider_type: "ftp".to_string(),
+                };
+                Some((name, DirectoryEntry::Directory(directory)))
+            }
+            "file" => {
+                let extension = std::path::Path::new(&name)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| format!(".{}", ext))
+                    .unwrap_or_default();
+                
+                let file_info = FileInfo::new(
+                    FileSize::from_bytes(size.unwrap_or(0)),
+                    extension,
+                    modify.unwrap_or_default(),
+                );
+                let file = File::new(current_path, &name, file_info);
+                Some((name, DirectoryEntry::File(file)))
+            }
+            _ => None,
+        }
+    }
+    
+    /// Parse MLSD listing lines and apply the provider's `DbfDbcPolicy`
+    pub fn parse_mlsd_listing(&self, lines: &[String], current_path: &str) -> DirectoryContent {
+        let entries: Vec<(String, DirectoryEntry)> = lines
+            .iter()
+            .filter_map(|line| self.parse_mlsd_line(line, current_path))
+            .filter(|(_, entry)| self.passes_extension_filter(entry))
+            .collect();
         
+        self.apply_dbf_dbc_policy(entries, current_path)
+    }
+    
+    /// Check a parsed entry against the provider's `ExtensionFilter`
+    fn passes_extension_filter(&self, entry: &DirectoryEntry) -> bool {
+        match (entry, &self.extension_filter) {
+            (DirectoryEntry::File(file), Some(filter)) => filter.keeps(file),
+            _ => true,
+        }
+    }
+    
+    /// Drop DBC/DBF duplicates according to the provider's `DbfDbcPolicy`
+    fn apply_dbf_dbc_policy(&self, entries: Vec<(String, DirectoryEntry)>, current_path: &str) -> DirectoryContent {
+        let upper_names: std::collections::HashSet<String> = entries
+            .iter()
+            .map(|(name, _)| name.to_uppercase())
+            .collect();
+        
+        let mut content = DirectoryContent::new();
+        for (name, entry) in entries {
+            let upper = name.to_uppercase();
+            let counterpart = if let Some(stem) = upper.strip_suffix(".DBF") {
+                Some(format!("{}.DBC", stem))
+            } else {
+                upper.strip_suffix(".DBC").map(|stem| format!("{}.DBF", stem))
+            };
+            let has_counterpart = counterpart.is_some_and(|other| upper_names.contains(&other));
+            
+            if has_counterpart {
+                let is_dbf = upper.ends_with(".DBF");
+                let skip = match self.dbf_dbc_policy {
+                    DbfDbcPolicy::PreferDbc => is_dbf,
+                    DbfDbcPolicy::PreferDbf => !is_dbf,
+                    DbfDbcPolicy::KeepBoth => false,
+                };
+                if skip {
+                    log::debug!("Skipping {} in {}: both DBC and DBF exist ({:?})", name, current_path, self.dbf_dbc_policy);
+                    continue;
+                }
+            }
+            content.insert(name, entry);
+        }
+        
+        content
+    }
+    
+    /// List a directory over an already open connection (bypasses the cache)
+    async fn list_directory_with_stream(
+        &self,
+        ftp_stream: &mut suppaftp::AsyncRustlsFtpStream,
+        path: &str,
+    ) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+        let full_path = self.full_path(path);
+        
+        let timeout = self.operation_timeout;
+        with_ftp_timeout("cwd", timeout, ftp_stream.cwd(full_path.as_str())).await?;
+        
+        // Prefer machine-readable MLSD when the server advertises it
+        if self.supports_mlsd(ftp_stream).await {
+            match with_ftp_timeout("mlsd", timeout, ftp_stream.mlsd(None)).await {
+                Ok(lines) => return Ok(self.parse_mlsd_listing(&lines, path)),
+                Err(e) => log::debug!("MLSD failed for {}, falling back to LIST: {}", full_path, e),
+            }
+        }
+        
+        let lines = with_ftp_timeout("list", timeout, ftp_stream.list(None)).await?;
+        Ok(self.parse_ftp_listing(&lines, path))
+    }
+    
+    /// Probe the server's FEAT reply for MLST/MLSD support
+    async fn supports_mlsd(&self, ftp_stream: &mut suppaftp::AsyncRustlsFtpStream) -> bool {
+        match with_ftp_timeout("feat", self.operation_timeout, ftp_stream.feat()).await {
+            Ok(features) => features.keys().any(|feature| feature.eq_ignore_ascii_case("MLST")),
+            Err(_) => false,
+        }
+    }
+    
+    /// Check which of `paths` exist, files or directories, keyed by the queried path
+    ///
+    /// Paths are shared out over at most `EXISTS_MANY_MAX_CONNECTIONS` connections,
+    /// each checking its share in turn, instead of one connection per path as
+    /// with `exists`. Paths whose connection can't be opened are reported missing.
+    pub async fn exists_many(&self, paths: &[String]) -> HashMap<String, bool> {
+        let workers = paths.len().min(EXISTS_MANY_MAX_CONNECTIONS);
+        let shares: Vec<Vec<&String>> = (0..workers)
+            .map(|worker| paths.iter().skip(worker).step_by(workers).collect())
+            .collect();
+
+        let results = join_all(shares.into_iter().map(|share| async move {
+            let mut found = Vec::with_capacity(share.len());
+            match self.create_connection().await {
+                Ok(mut ftp_stream) => {
+                    for path in share {
+                        let exists = self.exists_with_stream(&mut ftp_stream, path).await;
+                        found.push((path.clone(), exists));
+                    }
+                    let _ = ftp_stream.quit().await;
+                }
+                Err(e) => {
+                    log::debug!("exists_many: connection failed, reporting {} path(s) missing: {}", share.len(), e);
+                    found.extend(share.into_iter().map(|path| (path.clone(), false)));
+                }
+            }
+            found
+        }))
+        .await;
+
+        results.into_iter().flatten().collect()
+    }
+    
+    /// Check a file (`SIZE`) or directory (`CWD`) over an already open connection
+    async fn exists_with_stream(&self, ftp_stream: &mut suppaftp::AsyncRustlsFtpStream, path: &str) -> bool {
+        let full_path = self.full_path(path);
+        
+        if with_ftp_timeout("size", self.operation_timeout, ftp_stream.size(full_path.as_str())).await.is_ok() {
+            return true;
+        }
+        with_ftp_timeout("cwd", self.operation_timeout, ftp_stream.cwd(full_path.as_str())).await.is_ok()
+    }
+    
+    /// Server path of `path`, which is relative to `base_path` with or without a leading slash
+    pub fn full_path(&self, path: &str) -> FtpPath {
+        FtpPath::new(&self.base_path).join(path)
+    }
+
+    /// Create FTP connection, negotiating TLS as configured by `security`
+    ///
+    /// Waits for a slot from the provider's [`FtpConnectionLimiter`] first
+    /// (the global one unless set with `with_connection_limiter`).
+    pub(crate) async fn create_connection(&self) -> Result<FtpConnection, Box<dyn std::error::Error + Send + Sync>> {
+        use suppaftp::AsyncRustlsFtpStream;
+        
+        let limiter = self.connection_limiter.as_deref().unwrap_or(FtpConnectionLimiter::global());
+        let permit = limiter.acquire().await;
+
         // Connect to FTP server
-        let mut ftp_stream = AsyncRustlsFtpStream::connect(&format!("{}:{}", self.host, self.port)).await?;
+        let address = format!("{}:{}", self.host, self.port);
+        let mut ftp_stream = match self.security {
+            FtpSecurity::Plain => {
+                with_ftp_timeout("connect", self.operation_timeout, AsyncRustlsFtpStream::connect(&address)).await?
+            }
+            FtpSecurity::Explicit => {
+                let plain = with_ftp_timeout("connect", self.operation_timeout, AsyncRustlsFtpStream::connect(&address)).await?;
+                // Sends AUTH TLS, then PBSZ/PROT so data connections are encrypted too
+                with_ftp_timeout("auth tls", self.operation_timeout, plain.into_secure(tls_connector(), &self.host)).await?
+            }
+            FtpSecurity::Implicit => {
+                with_ftp_timeout(
+                    "connect",
+                    self.operation_timeout,
+                    AsyncRustlsFtpStream::connect_secure_implicit(&address, tls_connector(), &self.host),
+                ).await?
+            }
+        };
         
         // Login as anonymous (DATASUS is public)
-        ftp_stream.login("anonymous", "").await?;
-        
-        // Set passive mode (not async)
-        ftp_stream.set_mode(Mode::Passive);
+        with_ftp_timeout("login", self.operation_timeout, ftp_stream.login("anonymous", self.anonymous_identity.as_str())).await?;
+        
+        // Set data connection mode (not async)
+        ftp_stream.set_mode(self.transfer_mode.into());
+        if let Some(range) = self.passive_port_range.clone() {
+            ftp_stream = ftp_stream.passive_stream_builder(move |address| {
+                let range = range.clone();
+                Box::pin(async move {
+                    check_passive_port(address, &range)?;
+                    async_std::net::TcpStream::connect(address)
+                        .await
+                        .map_err(suppaftp::FtpError::ConnectionError)
+                })
+            });
+        }
         
-        Ok(ftp_stream)
+        Ok(FtpConnection {
+            stream: ftp_stream,
+            _permit: permit,
+        })
     }
     
     /// List multiple directories in parallel with timing information
@@ -356,9 +1230,7 @@ impl FtpFileSystemProvider {
         let filtered: Vec<_> = all_entries.into_iter()
             .filter(|entry| {
                 match &entry.entry {
-                    DirectoryEntry::File(file) => {
-                        file.basename.to_lowercase().ends_with(&format!(".{}", extension.to_lowercase()))
-                    }
+                    DirectoryEntry::File(file) => file.has_extension(extension),
                     DirectoryEntry::Directory(_) => false, // Don't include directories in extension filter
                 }
             })
@@ -394,68 +1266,42 @@ impl FtpFileSystemProvider {
         
         Ok((file_count, dir_count, total_size))
     }
-}
 
-#[async_trait]
-impl FileSystemProvider for FtpFileSystemProvider {
-    async fn list_directory(&self, path: &str) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
-        // Generate cache key
-        let cache_key = content_cache::generate_ftp_cache_key(&self.host, path);
-        
+    /// Content cache key for listings of `path`; listings differ per server, DBC/DBF policy and extension filter
+    fn listing_cache_key(&self, path: &str) -> String {
+        let server = format!("{}:{}", self.host, self.port);
+        let mut cache_key = content_cache::generate_ftp_cache_key(&server, &self.full_path(path).to_string());
+        if self.dbf_dbc_policy != DbfDbcPolicy::PreferDbc {
+            cache_key = format!("{}#{:?}", cache_key, self.dbf_dbc_policy);
+        }
+        if let Some(filter) = &self.extension_filter {
+            cache_key = format!("{}#{:?}", cache_key, filter);
+        }
+        cache_key
+    }
+
+    /// Listing behind [`FileSystemProvider::list_directory`], served from the content cache when possible
+    async fn list_directory_cached(&self, path: &str) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+        let cache_key = self.listing_cache_key(path);
+
         // Check cache first
         if let Some(cached_content) = content_cache::get_cached_content(&cache_key).await {
             // Deserialize cached content
             if let Ok(content) = serde_json::from_str::<DirectoryContent>(&cached_content) {
-                return Ok(content);
-            }
-        }
-        
-        // Cache miss - fetch from FTP server
-        let mut content = DirectoryContent::new();
-        let full_path = if path.starts_with('/') {
-            format!("{}{}", self.base_path, path)
-        } else {
-            format!("{}/{}", self.base_path, path)
-        };
-        
-        // Create connection
-        let mut ftp_stream = self.create_connection().await?;
-        
-        // Change to target directory
-        ftp_stream.cwd(&full_path).await?;
-        
-        // Get directory listing
-        let lines = ftp_stream.list(None).await?;
-        
-        // Parse each line
-        for line in lines {
-            if let Some((name, entry)) = self.parse_ftp_line(&line, path) {
-                // Filter out .DBF files if .DBC exists (as in Python version)
-                if name.to_uppercase().ends_with(".DBF") {
-                    let dbc_name = name.to_uppercase().replace(".DBF", ".DBC");
-                    if content.contains_key(&dbc_name) {
-                        continue; // Skip .DBF file
-                    }
-                }
-                content.insert(name, entry);
+                tracing::Span::current().record("cached", true);
+                return Ok(content);
             }
         }
         
-        // Remove .DBF files if corresponding .DBC exists (post-processing)
-        let to_remove: Vec<String> = content.keys()
-            .filter(|name| {
-                name.to_uppercase().ends_with(".DBF") && 
-                content.contains_key(&name.to_uppercase().replace(".DBF", ".DBC"))
-            })
-            .cloned()
-            .collect();
-        
-        for name in to_remove {
-            content.remove(&name);
-        }
-        
-        // Close connection
-        let _ = ftp_stream.quit().await;
+        tracing::Span::current().record("cached", false);
+
+        // Cache miss - fetch from FTP server, retrying transient failures
+        let content = retry_listing(path, &self.listing_retry, || async {
+            let mut ftp_stream = self.create_connection().await?;
+            let result = self.list_directory_with_stream(&mut ftp_stream, path).await;
+            let _ = ftp_stream.quit().await;
+            result
+        }).await?;
         
         // Cache the result with default TTL (5 minutes)
         if let Ok(serialized) = serde_json::to_string(&content) {
@@ -468,17 +1314,38 @@ impl FileSystemProvider for FtpFileSystemProvider {
         
         Ok(content)
     }
+}
+
+#[async_trait]
+impl FileSystemProvider for FtpFileSystemProvider {
+    async fn list_directory(&self, path: &str) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+        let span = tracing::info_span!(
+            "list_directory",
+            host = %self.host,
+            path,
+            cached = tracing::field::Empty,
+            entries = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        );
+        let start = std::time::Instant::now();
+        let result = self.list_directory_cached(path).instrument(span.clone()).await;
+        span.record("duration_ms", start.elapsed().as_millis() as u64);
+        match &result {
+            Ok(content) => {
+                span.record("entries", content.len());
+                tracing::debug!(parent: &span, "listed directory");
+            }
+            Err(e) => tracing::warn!(parent: &span, error = %e, "directory listing failed"),
+        }
+        result
+    }
     
     async fn exists(&self, path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
-        let full_path = if path.starts_with('/') {
-            format!("{}{}", self.base_path, path)
-        } else {
-            format!("{}/{}", self.base_path, path)
-        };
+        let full_path = self.full_path(path);
         
         match self.create_connection().await {
             Ok(mut ftp_stream) => {
-                match ftp_stream.cwd(&full_path).await {
+                match with_ftp_timeout("cwd", self.operation_timeout, ftp_stream.cwd(full_path.as_str())).await {
                     Ok(_) => {
                         let _ = ftp_stream.quit().await;
                         Ok(true)
@@ -512,9 +1379,39 @@ impl FileSystemProvider for FtpFileSystemProvider {
         join_all(futures).await
     }
     
+    /// Walk the tree over a single connection instead of one per directory
+    async fn directory_size(&self, path: &str) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ftp_stream = self.create_connection().await?;
+        let mut total_size = 0u64;
+        let mut pending = vec![path.to_string()];
+        
+        while let Some(current) = pending.pop() {
+            let content = match self.list_directory_with_stream(&mut ftp_stream, &current).await {
+                Ok(content) => content,
+                Err(e) => {
+                    let _ = ftp_stream.quit().await;
+                    return Err(e);
+                }
+            };
+            for (name, entry) in content {
+                match entry {
+                    DirectoryEntry::File(file) => total_size += file.size_bytes().unwrap_or(0),
+                    DirectoryEntry::Directory(_) => pending.push(FtpPath::new(&current).join(&name).to_string()),
+                }
+            }
+        }
+        
+        let _ = ftp_stream.quit().await;
+        Ok(total_size)
+    }
+    
     fn provider_name(&self) -> &'static str {
         "ftp"
     }
+
+    fn cache_key(&self, path: &str) -> Option<String> {
+        Some(self.listing_cache_key(path))
+    }
 }
 
 /// S3 file system provider (placeholder for now)
@@ -648,7 +1545,7 @@ impl Directory {
         &self, 
         provider: Arc<dyn FileSystemProvider>
     ) -> Result<Vec<DirectoryEntry>, Box<dyn std::error::Error + Send + Sync>> {
-        let content_map = provider.list_directory(&self.path).await?;
+        let content_map = self.load_with_provider(provider).await?;
         Ok(content_map.into_values().collect())
     }
     
@@ -658,18 +1555,52 @@ impl Directory {
     }
     
     /// Loads the content of the directory with a specific provider
+    /// 
+    /// Listings are cached under [`FileSystemProvider::cache_key`] for
+    /// `DEFAULT_FTP_TTL_SECONDS`; use `reload_with_provider` to bypass the cache.
     pub async fn load_with_provider(
         &self, 
         provider: Arc<dyn FileSystemProvider>
     ) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
-        provider.list_directory(&self.path).await
+        let Some(cache_key) = provider.cache_key(&self.path) else {
+            return provider.list_directory(&self.path).await;
+        };
+        
+        if let Some(cached_content) = content_cache::get_cached_content(&cache_key).await {
+            if let Ok(content) = serde_json::from_str::<DirectoryContent>(&cached_content) {
+                return Ok(content);
+            }
+        }
+        
+        let content = provider.list_directory(&self.path).await?;
+        
+        if let Ok(serialized) = serde_json::to_string(&content) {
+            content_cache::cache_content(
+                cache_key,
+                serialized,
+                content_cache::DEFAULT_FTP_TTL_SECONDS,
+            ).await;
+        }
+        
+        Ok(content)
     }
     
     /// Reloads the content of the directory (clears cache and loads again)
     pub async fn reload(&self) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
-        // Remove from cache
+        self.reload_with_provider(Arc::new(LocalFileSystemProvider)).await
+    }
+    
+    /// Reloads the content of the directory with a specific provider
+    pub async fn reload_with_provider(
+        &self, 
+        provider: Arc<dyn FileSystemProvider>
+    ) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+        // Remove from cache; providers that cache their own listings use the same key
         async_cache::remove_cached_directory_async(&self.path).await;
-        self.load().await
+        if let Some(cache_key) = provider.cache_key(&self.path) {
+            content_cache::remove_cached_content(&cache_key).await;
+        }
+        self.load_with_provider(provider).await
     }
     
     /// Get the parent directory
@@ -802,6 +1733,51 @@ pub mod directory_utils {
         })
     }
     
+    /// Get directory size using a specific provider
+    /// 
+    /// Delegates to `FileSystemProvider::directory_size`, so FTP providers
+    /// reuse one connection for the whole walk.
+    pub async fn get_directory_size_with_provider(
+        dir: &Directory,
+        provider: Arc<dyn FileSystemProvider>,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        provider.directory_size(&dir.path).await
+    }
+    
+    /// Compare two listings of the same directory (e.g. from consecutive crawls)
+    /// 
+    /// Files count as modified when their size or modification time differs;
+    /// an entry that switched between file and directory is also reported as modified.
+    pub fn diff_listings(old: &DirectoryContent, new: &DirectoryContent) -> ListingDiff {
+        let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        let mut diff = ListingDiff::default();
+        for name in names {
+            match (old.get(name), new.get(name)) {
+                (None, Some(entry)) => diff.added.push(entry.clone()),
+                (Some(entry), None) => diff.removed.push(entry.clone()),
+                (Some(old_entry), Some(new_entry)) => {
+                    let changed = match (old_entry, new_entry) {
+                        (DirectoryEntry::File(a), DirectoryEntry::File(b)) => {
+                            a.size_bytes() != b.size_bytes()
+                                || a.modification_date() != b.modification_date()
+                        }
+                        (DirectoryEntry::Directory(_), DirectoryEntry::Directory(_)) => false,
+                        _ => true,
+                    };
+                    if changed {
+                        diff.modified.push(new_entry.clone());
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        diff
+    }
+    
     /// Get directory size (sum of all file sizes)
     pub fn get_directory_size(
         dir: Directory
@@ -830,6 +1806,7 @@ pub mod directory_utils {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::mock_ftp::{MockFtpServer, MockReply};
     use tempfile::TempDir;
     use tokio::fs;
 
@@ -1170,4 +2147,681 @@ mod tests {
         // Note: Actual FTP operations would require network access
         // In a real scenario, we'd test with a mock FTP server
     }
+
+    fn dbc_dbf_listing() -> Vec<String> {
+        vec![
+            "12-01-23 02:30PM              4096 RDSP2012.DBC".to_string(),
+            "12-01-23 02:30PM             16384 RDSP2012.DBF".to_string(),
+            "12-01-23 02:30PM              2048 RDRJ2012.DBF".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_listing_policy_prefer_dbc() {
+        let provider = FtpFileSystemProvider::new_datasus();
+        assert_eq!(provider.dbf_dbc_policy, DbfDbcPolicy::PreferDbc);
+
+        let content = provider.parse_ftp_listing(&dbc_dbf_listing(), "/SIHSUS/200801_/Dados");
+        assert!(content.contains_key("RDSP2012.DBC"));
+        assert!(!content.contains_key("RDSP2012.DBF"));
+        assert!(content.contains_key("RDRJ2012.DBF")); // no DBC counterpart
+    }
+
+    #[test]
+    fn test_listing_policy_keep_both() {
+        let provider = FtpFileSystemProvider::new_datasus().with_dbf_dbc_policy(DbfDbcPolicy::KeepBoth);
+
+        let content = provider.parse_ftp_listing(&dbc_dbf_listing(), "/SIHSUS/200801_/Dados");
+        assert_eq!(content.len(), 3);
+        assert!(content.contains_key("RDSP2012.DBC"));
+        assert!(content.contains_key("RDSP2012.DBF"));
+    }
+
+    #[test]
+    fn test_listing_policy_prefer_dbf() {
+        let provider = FtpFileSystemProvider::new_datasus().with_dbf_dbc_policy(DbfDbcPolicy::PreferDbf);
+
+        let content = provider.parse_ftp_listing(&dbc_dbf_listing(), "/SIHSUS/200801_/Dados");
+        assert!(!content.contains_key("RDSP2012.DBC"));
+        assert!(content.contains_key("RDSP2012.DBF"));
+        assert!(content.contains_key("RDRJ2012.DBF"));
+    }
+
+    #[test]
+    fn test_listing_policy_is_case_insensitive() {
+        let provider = FtpFileSystemProvider::new_datasus();
+        let lines = vec![
+            "12-01-23 02:30PM              4096 rdsp2012.dbc".to_string(),
+            "12-01-23 02:30PM             16384 RDSP2012.DBF".to_string(),
+        ];
+
+        let content = provider.parse_ftp_listing(&lines, "/");
+        assert_eq!(content.len(), 1);
+        assert!(content.contains_key("rdsp2012.dbc"));
+    }
+
+    #[test]
+    fn test_listing_extension_filter_allow_dbc() {
+        let provider = FtpFileSystemProvider::new_datasus()
+            .with_extension_filter(ExtensionFilter::Allow(vec!["dbc".to_string()]));
+        let lines = vec![
+            "12-01-23 02:30PM              4096 RDSP2012.DBC".to_string(),
+            "12-01-23 02:30PM              1024 rdrj2012.dbc".to_string(),
+            "12-01-23 02:30PM              2048 RDMG2012.DBF".to_string(),
+            "12-01-23 02:30PM               512 LEIAME.TXT".to_string(),
+            "12-01-23 02:30PM       <DIR>          Dados".to_string(),
+        ];
+
+        let content = provider.parse_ftp_listing(&lines, "/SIHSUS/200801_");
+        assert_eq!(content.len(), 3);
+        assert!(content.contains_key("RDSP2012.DBC"));
+        assert!(content.contains_key("rdrj2012.dbc"));
+        assert!(matches!(content.get("Dados"), Some(DirectoryEntry::Directory(_))));
+    }
+
+    #[test]
+    fn test_listing_extension_filter_block() {
+        let provider = FtpFileSystemProvider::new_datasus()
+            .with_extension_filter(ExtensionFilter::Block(vec![".txt".to_string()]));
+        let lines = vec![
+            "12-01-23 02:30PM              4096 RDSP2012.DBC".to_string(),
+            "12-01-23 02:30PM               512 LEIAME.TXT".to_string(),
+        ];
+
+        let content = provider.parse_ftp_listing(&lines, "/");
+        assert_eq!(content.len(), 1);
+        assert!(content.contains_key("RDSP2012.DBC"));
+    }
+
+    #[tokio::test]
+    async fn test_files_with_extension_mixed_case() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["RDSP2012.DBC", "rdrj2012.dbc", "PASP2012.Dbc", "notes.txt"] {
+            fs::write(temp_dir.path().join(name), b"data").await.unwrap();
+        }
+
+        let dir = Directory::new(temp_dir.path().to_string_lossy().to_string()).await.unwrap();
+        let mut names: Vec<String> = dir
+            .files_with_extension("dbc")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|f| f.basename)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["PASP2012.Dbc", "RDSP2012.DBC", "rdrj2012.dbc"]);
+
+        assert_eq!(dir.files_with_extension(".DBC").await.unwrap().len(), 3);
+    }
+
+    /// Provider that counts how often it is asked to list a directory
+    struct CountingProvider {
+        name: &'static str,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl FileSystemProvider for CountingProvider {
+        async fn list_directory(&self, path: &str) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+            use crate::models::file_info::{FileInfo, FileSize};
+
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let mut content = DirectoryContent::new();
+            let info = FileInfo::new(FileSize::from_bytes(10), ".dbc".to_string(), chrono::Utc::now());
+            content.insert(
+                format!("{}.dbc", self.name),
+                DirectoryEntry::File(File::new(path, &format!("{}.dbc", self.name), info)),
+            );
+            Ok(content)
+        }
+
+        async fn exists(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        async fn is_directory(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        fn provider_name(&self) -> &'static str {
+            self.name
+        }
+    }
+
+    /// Provider serving a fixed file list for any path
+    struct StaticListingProvider {
+        names: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl FileSystemProvider for StaticListingProvider {
+        async fn list_directory(&self, path: &str) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+            use crate::models::file_info::{FileInfo, FileSize};
+
+            let mut content = DirectoryContent::new();
+            for name in &self.names {
+                let info = FileInfo::new(FileSize::from_bytes(10), ".dbc".to_string(), chrono::Utc::now());
+                content.insert(name.to_string(), DirectoryEntry::File(File::new(path, name, info)));
+            }
+            Ok(content)
+        }
+
+        async fn exists(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        async fn is_directory(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "static"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_available_periods_filters_group_and_uf() {
+        let provider = StaticListingProvider {
+            names: vec![
+                "RDSP2102.dbc",
+                "RDSP2012.DBC",
+                "RDSP2101.dbc",
+                "RDSP2101.dbf", // same period as the .dbc, and not a DATASUS .dbc name
+                "RDRJ2101.dbc", // other UF
+                "SPSP2103.dbc", // other group
+                "ERSP2104.dbc", // other group
+                "LEIAME.TXT",
+            ],
+        };
+
+        let periods = available_periods(&provider, "/SIHSUS/200801_/Dados", "rd", "SP").await.unwrap();
+        assert_eq!(periods, vec![
+            Period::YearMonth(2020, 12),
+            Period::YearMonth(2021, 1),
+            Period::YearMonth(2021, 2),
+        ]);
+
+        let none = available_periods(&provider, "/SIHSUS/200801_/Dados", "RD", "AC").await.unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_availability_matrix_shows_holes() {
+        let provider = StaticListingProvider {
+            names: vec![
+                "RDSP2011.dbc",
+                "RDSP2012.dbc",
+                "RDSP2101.dbc",
+                "RDRJ2011.dbc",
+                "RDRJ2101.DBC", // RJ is missing 2020-12
+                "ERSP2102.dbc", // other group
+                "LEIAME.TXT",
+            ],
+        };
+
+        let matrix = availability_matrix(&provider, "/SIHSUS/200801_/Dados", "rd").await.unwrap();
+        assert_eq!(matrix.periods, vec![
+            Period::YearMonth(2020, 11),
+            Period::YearMonth(2020, 12),
+            Period::YearMonth(2021, 1),
+        ]);
+        assert!(matrix.is_available("sp", &Period::YearMonth(2020, 12)));
+        assert!(!matrix.is_available("RJ", &Period::YearMonth(2020, 12)));
+        assert!(!matrix.is_available("AC", &Period::YearMonth(2020, 12)));
+        assert_eq!(matrix.missing(), vec![("RJ".to_string(), Period::YearMonth(2020, 12))]);
+        assert_eq!(matrix.to_table(), "RD  2020-11 .. 2021-01\nRJ  x.x\nSP  xxx\n");
+
+        let json: serde_json::Value = serde_json::from_str(&matrix.to_json().unwrap()).unwrap();
+        assert_eq!(json["available"]["RJ"], serde_json::json!(["2020-11", "2021-01"]));
+    }
+
+    #[tokio::test]
+    async fn test_content_is_cached_per_provider() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let path = "/counting/content_cache_test".to_string();
+        let calls_a = Arc::new(AtomicUsize::new(0));
+        let calls_b = Arc::new(AtomicUsize::new(0));
+        let provider_a: Arc<dyn FileSystemProvider> = Arc::new(CountingProvider { name: "counting-a", calls: calls_a.clone() });
+        let provider_b: Arc<dyn FileSystemProvider> = Arc::new(CountingProvider { name: "counting-b", calls: calls_b.clone() });
+
+        let dir = Directory::new_with_provider(path, provider_a.clone()).await.unwrap();
+        dir.reload_with_provider(provider_a.clone()).await.unwrap(); // start from a clean cache
+        dir.reload_with_provider(provider_b.clone()).await.unwrap();
+        calls_a.store(0, Ordering::SeqCst);
+        calls_b.store(0, Ordering::SeqCst);
+
+        // Second call is served from the cache
+        let first = dir.content_with_provider(provider_a.clone()).await.unwrap();
+        let second = dir.content_with_provider(provider_a.clone()).await.unwrap();
+        assert_eq!(calls_a.load(Ordering::SeqCst), 0);
+        assert_eq!(first.len(), second.len());
+
+        // Same path through another provider does not collide
+        let other = dir.load_with_provider(provider_b.clone()).await.unwrap();
+        assert!(other.contains_key("counting-b.dbc"));
+        assert_eq!(calls_b.load(Ordering::SeqCst), 0);
+
+        // Reload bypasses and refreshes the cache
+        dir.reload_with_provider(provider_a.clone()).await.unwrap();
+        assert_eq!(calls_a.load(Ordering::SeqCst), 1);
+        dir.content_with_provider(provider_a).await.unwrap();
+        assert_eq!(calls_a.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_content_cache_separates_ftp_filters() {
+        // Lists one DBC and one DBF in every directory
+        let server = MockFtpServer::start(|command, _| match command {
+            "LIST" => Some(MockReply::data(
+                "12-01-23 02:30PM              1024 RDSP2301.dbc\r\n12-01-23 02:30PM               512 PASP2301.dbf\r\n",
+            )),
+            _ => None,
+        })
+        .await;
+        let ftp = |filter: ExtensionFilter| -> Arc<dyn FileSystemProvider> {
+            Arc::new(
+                FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+                    .with_operation_timeout(std::time::Duration::from_secs(5))
+                    .with_extension_filter(filter),
+            )
+        };
+        let dbc_only = ftp(ExtensionFilter::Allow(vec!["dbc".to_string()]));
+        let dbf_only = ftp(ExtensionFilter::Allow(vec!["dbf".to_string()]));
+        assert_ne!(dbc_only.cache_key("/SIHSUS"), dbf_only.cache_key("/SIHSUS"));
+        assert_eq!(LocalFileSystemProvider.cache_key("/SIHSUS"), None);
+
+        // Same server and path, but each provider gets its own filtered listing
+        let dir = Directory::new_with_provider("/SIHSUS".to_string(), dbc_only.clone()).await.unwrap();
+        let dbc = dir.load_with_provider(dbc_only.clone()).await.unwrap();
+        let dbf = dir.load_with_provider(dbf_only.clone()).await.unwrap();
+        assert!(dbc.len() == 1 && dbc.contains_key("RDSP2301.dbc"));
+        assert!(dbf.len() == 1 && dbf.contains_key("PASP2301.dbf"));
+
+        // Cached listings stay off the wire until a reload, which goes back to the server
+        let lists = || server.commands().iter().filter(|line| line.starts_with("LIST")).count();
+        assert_eq!(lists(), 2);
+        dir.load_with_provider(dbc_only.clone()).await.unwrap();
+        assert_eq!(lists(), 2);
+        dir.reload_with_provider(dbc_only).await.unwrap();
+        assert_eq!(lists(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_directory_size_with_provider() {
+        use directory_utils::*;
+
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.dbc"), vec![0u8; 100]).await.unwrap();
+        let level1 = temp_dir.path().join("level1");
+        let level2 = level1.join("level2");
+        fs::create_dir_all(&level2).await.unwrap();
+        fs::write(level1.join("b.dbc"), vec![0u8; 250]).await.unwrap();
+        fs::write(level2.join("c.dbc"), vec![0u8; 25]).await.unwrap();
+        fs::write(level2.join("d.dbf"), vec![0u8; 1]).await.unwrap();
+
+        let dir = Directory::new(temp_dir.path().to_string_lossy().to_string()).await.unwrap();
+        let total = get_directory_size_with_provider(&dir, Arc::new(LocalFileSystemProvider)).await.unwrap();
+        assert_eq!(total, 376);
+
+        // Matches the existing recursive implementation
+        assert_eq!(get_directory_size(dir).await.unwrap(), total);
+    }
+
+    #[test]
+    fn test_diff_listings() {
+        use crate::models::file_info::{FileInfo, FileSize};
+        use chrono::{TimeZone, Utc};
+        use directory_utils::diff_listings;
+
+        let crawl_time = Utc.with_ymd_and_hms(2024, 1, 10, 8, 0, 0).unwrap();
+        let file_entry = |name: &str, size: u64, modify| {
+            let info = FileInfo::new(FileSize::from_bytes(size), ".dbc".to_string(), modify);
+            (name.to_string(), DirectoryEntry::File(File::new("/SIHSUS/200801_/Dados", name, info)))
+        };
+
+        let old: DirectoryContent = [
+            file_entry("RDAC2401.dbc", 100, crawl_time),
+            file_entry("RDAL2401.dbc", 200, crawl_time),
+            file_entry("RDAM2401.dbc", 300, crawl_time),
+            file_entry("RDAP2401.dbc", 400, crawl_time),
+        ].into_iter().collect();
+
+        let new: DirectoryContent = [
+            file_entry("RDAC2401.dbc", 100, crawl_time),
+            file_entry("RDAL2401.dbc", 250, crawl_time),
+            file_entry("RDAM2401.dbc", 300, crawl_time + chrono::Duration::days(1)),
+            file_entry("RDAC2402.dbc", 120, crawl_time),
+        ].into_iter().collect();
+
+        let diff = diff_listings(&old, &new);
+        let names = |entries: &[DirectoryEntry]| -> Vec<String> {
+            entries.iter().map(|entry| match entry {
+                DirectoryEntry::File(file) => file.basename.clone(),
+                DirectoryEntry::Directory(dir) => dir.name.clone(),
+            }).collect()
+        };
+
+        assert_eq!(names(&diff.added), vec!["RDAC2402.dbc"]);
+        assert_eq!(names(&diff.removed), vec!["RDAP2401.dbc"]);
+        assert_eq!(names(&diff.modified), vec!["RDAL2401.dbc", "RDAM2401.dbc"]);
+        assert!(!diff.is_empty());
+        assert!(diff_listings(&old, &old).is_empty());
+
+        let json = serde_json::to_string(&diff).unwrap();
+        assert!(json.contains("RDAC2402.dbc"));
+    }
+
+    #[test]
+    fn test_parse_mlsd_listing() {
+        let provider = FtpFileSystemProvider::new_datasus();
+        let lines: Vec<String> = [
+            "type=cdir;modify=20231201143000; .",
+            "type=pdir;modify=20231201143000; ..",
+            "type=dir;modify=20231115090000; Antigos",
+            "type=file;size=4096;modify=20231201143000; RDSP2012.DBC",
+            "type=file;size=16384;modify=20231201143000.123; RDSP2012.DBF",
+            "Type=File;Size=2048;Modify=20231201143000;UNIX.mode=0644; RDRJ 2012.dbc",
+        ].iter().map(|line| line.to_string()).collect();
+
+        let content = provider.parse_mlsd_listing(&lines, "/SIHSUS/200801_/Dados");
+        assert_eq!(content.len(), 3); // cdir/pdir skipped, DBF dropped by PreferDbc
+
+        match content.get("Antigos") {
+            Some(DirectoryEntry::Directory(dir)) => assert_eq!(dir.path, "/SIHSUS/200801_/Dados/Antigos"),
+            other => panic!("expected directory, got {:?}", other),
+        }
+        match content.get("RDSP2012.DBC") {
+            Some(DirectoryEntry::File(file)) => {
+                assert_eq!(file.size_bytes(), Some(4096));
+                assert_eq!(file.modification_date().format("%Y-%m-%d %H:%M:%S").to_string(), "2023-12-01 14:30:00");
+            }
+            other => panic!("expected file, got {:?}", other),
+        }
+        // Names may contain spaces and facts are case-insensitive
+        assert!(matches!(content.get("RDRJ 2012.dbc"), Some(DirectoryEntry::File(_))));
+
+        assert!(provider.parse_mlsd_line("no facts here", "/").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retry_listing_recovers_from_transient_errors() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct FlakyProvider {
+            calls: AtomicUsize,
+        }
+
+        #[async_trait]
+        impl FileSystemProvider for FlakyProvider {
+            async fn list_directory(&self, _path: &str) -> Result<DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+                if self.calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    let reset = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset by peer");
+                    return Err(Box::new(suppaftp::FtpError::ConnectionError(reset)));
+                }
+                Ok(DirectoryContent::new())
+            }
+
+            async fn exists(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                Ok(true)
+            }
+
+            async fn is_directory(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+                Ok(true)
+            }
+        }
+
+        let provider = FlakyProvider { calls: AtomicUsize::new(0) };
+        let config = ListingRetryConfig {
+            max_retries: 3,
+            initial_backoff: std::time::Duration::ZERO,
+        };
+
+        let content = retry_listing("/SIM/CID10", &config, || provider.list_directory("/SIM/CID10")).await;
+        assert!(content.unwrap().is_empty());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+
+        // Not enough retries: the final error reports every attempt
+        let provider = FlakyProvider { calls: AtomicUsize::new(0) };
+        let config = ListingRetryConfig { max_retries: 1, ..config };
+        let error = retry_listing("/SIM/CID10", &config, || provider.list_directory("/SIM/CID10")).await.unwrap_err();
+        assert_eq!(error.attempts, 2);
+        assert_eq!(classify_provider_error(error.source.as_ref()), FtpErrorKind::Transient);
+    }
+
+    #[tokio::test]
+    async fn test_retry_listing_does_not_retry_permanent_errors() {
+        use suppaftp::{FtpError, Status, types::Response};
+
+        let not_found = FtpError::UnexpectedResponse(Response::new(Status::FileUnavailable, b"550 No such directory".to_vec()));
+        assert_eq!(classify_ftp_error(&not_found), FtpErrorKind::Permanent);
+        assert_eq!(classify_ftp_error(&FtpError::BadResponse), FtpErrorKind::Transient);
+
+        let mut attempts = 0;
+        let config = ListingRetryConfig {
+            max_retries: 3,
+            initial_backoff: std::time::Duration::ZERO,
+        };
+        let error = retry_listing("/MISSING", &config, || {
+            attempts += 1;
+            async {
+                let not_found = FtpError::UnexpectedResponse(Response::new(Status::FileUnavailable, Vec::new()));
+                Err::<DirectoryContent, _>(Box::new(not_found) as Box<dyn std::error::Error + Send + Sync>)
+            }
+        }).await.unwrap_err();
+
+        assert_eq!(attempts, 1);
+        assert_eq!(error.attempts, 1);
+        assert!(error.to_string().contains("/MISSING"));
+    }
+
+    #[tokio::test]
+    async fn test_connection_times_out_on_silent_server() {
+        // Accepts TCP connections but never sends the FTP greeting
+        let server = MockFtpServer::silent().await;
+
+        let timeout = std::time::Duration::from_millis(200);
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+            .with_operation_timeout(timeout);
+
+        let error = provider.create_connection().await.err().unwrap();
+        let ftp_timeout = error.downcast_ref::<FtpTimeout>().expect("expected FtpTimeout");
+        assert_eq!(ftp_timeout.operation, "connect");
+        assert_eq!(ftp_timeout.timeout, timeout);
+        assert_eq!(classify_provider_error(error.as_ref()), FtpErrorKind::Transient);
+    }
+
+    #[tokio::test]
+    async fn test_login_sends_anonymous_identity() {
+        let server = MockFtpServer::start(|_, _| None).await;
+
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5))
+            .with_anonymous_identity("analyst@example.org");
+        assert_eq!(FtpFileSystemProvider::new_datasus().anonymous_identity, "");
+
+        let _stream = provider.create_connection().await.unwrap();
+        assert!(server.commands().contains(&"PASS analyst@example.org".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_security_selects_negotiation_sequence() {
+        // Greets like a plain FTP server and reports the first command, refusing AUTH TLS
+        async fn first_command(security: FtpSecurity) -> String {
+            let server = MockFtpServer::start(|command, _| match command {
+                "AUTH" => Some(MockReply::line("504 not supported")),
+                _ => None,
+            })
+            .await;
+
+            let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+                .with_operation_timeout(std::time::Duration::from_millis(500))
+                .with_security(security);
+            let _ = provider.create_connection().await;
+            server.commands().into_iter().next().unwrap_or_default()
+        }
+
+        assert_eq!(FtpFileSystemProvider::new_datasus().security, FtpSecurity::Plain);
+        assert!(first_command(FtpSecurity::Plain).await.starts_with("USER"));
+        assert_eq!(first_command(FtpSecurity::Explicit).await, "AUTH TLS");
+
+        // Implicit FTPS starts the TLS handshake before any FTP greeting
+        let server = MockFtpServer::silent().await;
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_millis(500))
+            .with_security(FtpSecurity::Implicit);
+        assert!(provider.create_connection().await.is_err());
+        assert_eq!(server.raw_bytes().first(), Some(&0x16)); // TLS handshake record
+    }
+
+    #[tokio::test]
+    async fn test_exists_many_shares_connections() {
+        // Answers SIZE for one file and CWD for one directory, 550 for anything else
+        let server = MockFtpServer::start(|command, arg| match (command, arg) {
+            ("SIZE", "/pub/SIHSUS/Dados/RDSP2012.dbc") => Some(MockReply::line("213 4096")),
+            ("CWD", "/pub/SIHSUS") => None,
+            ("SIZE" | "CWD", _) => Some(MockReply::line("550 not found")),
+            _ => None,
+        })
+        .await;
+
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let mut paths = vec![
+            "/SIHSUS/Dados/RDSP2012.dbc".to_string(),
+            "/SIHSUS".to_string(),
+        ];
+        paths.extend((0..8).map(|i| format!("/SIHSUS/Dados/MISSING{}.dbc", i)));
+
+        let found = provider.exists_many(&paths).await;
+        assert_eq!(found.len(), paths.len());
+        assert!(found["/SIHSUS/Dados/RDSP2012.dbc"]);
+        assert!(found["/SIHSUS"]);
+        assert_eq!(found.values().filter(|exists| **exists).count(), 2);
+        assert!(server.connections() <= EXISTS_MANY_MAX_CONNECTIONS);
+
+        assert!(provider.exists_many(&[]).await.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_connection_limiter_caps_concurrency() {
+        let server = MockFtpServer::start(|_, _| None).await;
+        let limiter = Arc::new(FtpConnectionLimiter::new(2));
+
+        // Separate providers, as if from separate downloaders, sharing one limiter
+        let connections = (0..6).map(|_| async {
+            let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+                .with_operation_timeout(std::time::Duration::from_secs(5))
+                .with_connection_limiter(limiter.clone());
+            let mut ftp_stream = provider.create_connection().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            ftp_stream.quit().await.unwrap();
+        });
+        join_all(connections).await;
+
+        assert_eq!(server.peak_sessions(), 2);
+        assert_eq!(server.open_sessions(), 0);
+        assert_eq!(FtpConnectionLimiter::new(0).limit(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_global_connection_limit_is_resizable() {
+        // Never below the default, so FTP tests running alongside keep their slots
+        set_global_ftp_connection_limit(DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT + 4).await;
+        assert_eq!(global_ftp_connection_limit(), DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT + 4);
+        set_global_ftp_connection_limit(DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT).await;
+        assert_eq!(global_ftp_connection_limit(), DEFAULT_GLOBAL_FTP_CONNECTION_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_connection_limit_resize_counts_held_permits() {
+        use std::time::Duration;
+
+        let limiter = Arc::new(FtpConnectionLimiter::new(2));
+        let held = limiter.acquire().await;
+
+        // Growing keeps the held permit counted: two more slots, not three
+        limiter.set_limit(3).await;
+        assert_eq!(limiter.limit(), 3);
+        let more = (limiter.acquire().await, limiter.acquire().await);
+        assert!(tokio::time::timeout(Duration::from_millis(50), limiter.acquire()).await.is_err());
+
+        // Shrinking to 1 waits for two permits to come back
+        let shrink = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.set_limit(1).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!shrink.is_finished());
+        drop(more);
+        shrink.await.unwrap();
+        assert_eq!(limiter.limit(), 1);
+
+        // The permit held throughout fills the single slot until it's dropped
+        assert!(tokio::time::timeout(Duration::from_millis(50), limiter.acquire()).await.is_err());
+        drop(held);
+        assert!(tokio::time::timeout(Duration::from_millis(50), limiter.acquire()).await.is_ok());
+    }
+
+    /// Log in, then report the first data-connection command a LIST triggers
+    async fn data_setup_command(mode: FtpMode) -> String {
+        // Refuse the data connection so the client gives up
+        let server = MockFtpServer::start(|command, _| match command {
+            "USER" | "PASS" | "TYPE" => None,
+            _ => Some(MockReply::line("500 refused")),
+        })
+        .await;
+
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5))
+            .with_transfer_mode(mode);
+        let mut ftp_stream = provider.create_connection().await.unwrap();
+        assert!(ftp_stream.list(None).await.is_err());
+        server
+            .commands()
+            .iter()
+            .map(|line| line.split(' ').next().unwrap_or_default().to_string())
+            .find(|command| !["USER", "PASS", "TYPE"].contains(&command.as_str()))
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_transfer_mode_is_applied_to_stream() {
+        assert_eq!(FtpFileSystemProvider::new_datasus().transfer_mode, FtpMode::Passive);
+        assert_eq!(data_setup_command(FtpMode::Passive).await, "PASV");
+        assert_eq!(data_setup_command(FtpMode::ExtendedPassive).await, "EPSV");
+        assert_eq!(data_setup_command(FtpMode::Active).await, "PORT");
+    }
+
+    #[tokio::test]
+    async fn test_passive_port_outside_range_is_refused() {
+        let server = MockFtpServer::start(|command, _| match command {
+            "LIST" => Some(MockReply::data("-rw-r--r-- 1 ftp ftp 10 Jan 01 2024 a.dbc\r\n")),
+            _ => None,
+        })
+        .await;
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+
+        // The mock's data ports are ephemeral, so never in the well-known range
+        let mut ftp_stream = provider.clone().with_passive_port_range(1..=1023).create_connection().await.unwrap();
+        let error = ftp_stream.list(None).await.unwrap_err();
+        assert!(error.to_string().contains("outside the allowed range 1-1023"), "{}", error);
+        assert_eq!(classify_ftp_error(&error), FtpErrorKind::Transient);
+
+        let mut ftp_stream = provider.with_passive_port_range(1024..=65535).create_connection().await.unwrap();
+        assert_eq!(ftp_stream.list(None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_with_ftp_timeout_passes_results_through() {
+        let timeout = std::time::Duration::from_secs(1);
+        let value = with_ftp_timeout("noop", timeout, async { Ok::<_, std::io::Error>(7) }).await.unwrap();
+        assert_eq!(value, 7);
+
+        let error = with_ftp_timeout("never", std::time::Duration::from_millis(10), std::future::pending::<Result<(), std::io::Error>>())
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "FTP never timed out after 10ms");
+    }
 }
@@ -1,18 +1,75 @@
 use crate::models::file::File;
-use crate::models::directory::FtpFileSystemProvider;
-use crate::models::async_utils::async_path_utils::{path_exists_async, ensure_dir_async, get_file_size_async, cache_path_async};
-use indicatif::{ProgressBar, ProgressStyle, MultiProgress, HumanDuration};
+use crate::models::regex_patterns::DataSusFileInfo;
+use crate::models::date_utils::Period;
+use crate::models::directory::{
+    classify_provider_error, DirectoryEntry, FileSystemProvider, FtpErrorKind, FtpFileSystemProvider, FtpTimeout, with_ftp_timeout,
+};
+use crate::models::geo_utils::get_state_info;
+use crate::models::ftp_path::FtpPath;
+use crate::models::retry::{RetryPolicy, Retryable};
+use crate::models::async_utils::async_path_utils::{ensure_dir_async, get_file_size_async, cache_path_async};
+use crate::models::polars_utils::dbase_pl::verify_dbase_file;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle, MultiProgress, HumanDuration};
 use console::{Style, Term};
 
+use std::collections::VecDeque;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicU64, Ordering};
-use rayon::prelude::*;
+use futures::future::join_all;
 use tokio::fs::File as TokioFile;
 use tokio::io::AsyncWriteExt;
 use futures::io::AsyncReadExt;
 use serde::{Deserialize, Serialize};
 use anyhow::{anyhow, Result};
+use tracing::Instrument;
+
+/// How downloaded files are laid out under the output directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LocalLayout {
+    /// Mirror the FTP directory structure (honours `preserve_structure`)
+    #[default]
+    MirrorFtp,
+    /// `{subsystem}/{group}/{uf}/{year}/` derived from the parsed DATASUS filename
+    SubsystemPartitioned,
+    /// Every file directly in the output directory
+    Flat,
+}
+
+/// When an existing local file is replaced by a new download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverwritePolicy {
+    /// Keep the local file
+    #[default]
+    Never,
+    /// Always download again
+    Always,
+    /// Download when the server's modification time is later than the local file's
+    IfNewer,
+    /// Download when the server's size differs from the local file's
+    IfDifferentSize,
+}
+
+impl From<bool> for OverwritePolicy {
+    fn from(overwrite: bool) -> Self {
+        if overwrite { OverwritePolicy::Always } else { OverwritePolicy::Never }
+    }
+}
+
+/// Read `overwrite` as either a policy or the boolean older configs used
+fn deserialize_overwrite<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<OverwritePolicy, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Overwrite {
+        Flag(bool),
+        Policy(OverwritePolicy),
+    }
+
+    Ok(match Overwrite::deserialize(deserializer)? {
+        Overwrite::Flag(flag) => flag.into(),
+        Overwrite::Policy(policy) => policy,
+    })
+}
 
 /// Download configuration for customizing download behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,10 +82,29 @@ pub struct DownloadConfig {
     pub max_concurrent: usize,
     /// Buffer size for streaming downloads (in bytes)
     pub buffer_size: usize,
-    /// Whether to overwrite existing files
-    pub overwrite: bool,
+    /// What to do when the local file already exists
+    ///
+    /// Also accepts the boolean of older configs (`true` = `Always`, `false` = `Never`).
+    #[serde(deserialize_with = "deserialize_overwrite")]
+    pub overwrite: OverwritePolicy,
+    /// Local directory layout for downloaded files
+    #[serde(default)]
+    pub layout: LocalLayout,
+    /// Retries after a failed transfer in `download_files` (0 disables retrying)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Parse each downloaded DBC/DBF and fail the download if it's unreadable
+    #[serde(default)]
+    pub verify_readable: bool,
+}
+
+fn default_max_retries() -> u32 {
+    2
 }
 
+/// Delay before the first download retry, doubled for each further retry
+const DOWNLOAD_RETRY_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(500);
+
 impl Default for DownloadConfig {
     fn default() -> Self {
         Self {
@@ -36,7 +112,10 @@ impl Default for DownloadConfig {
             preserve_structure: true,
             max_concurrent: 4,
             buffer_size: 8192,
-            overwrite: false,
+            overwrite: OverwritePolicy::Never,
+            layout: LocalLayout::default(),
+            max_retries: default_max_retries(),
+            verify_readable: false,
         }
     }
 }
@@ -56,11 +135,64 @@ pub struct DownloadResult {
     pub error: Option<String>,
     /// Download duration in milliseconds
     pub duration_ms: u64,
+    /// Records in the file, when checked with `verify_readable`
+    #[serde(default)]
+    pub row_count: Option<u64>,
+    /// Why an existing local file was kept or replaced (None when there was none)
+    #[serde(default)]
+    pub overwrite_reason: Option<String>,
+}
+
+impl DownloadResult {
+    /// Average transfer rate in bytes per second (0 when no time was measured)
+    pub fn bytes_per_sec(&self) -> f64 {
+        if self.duration_ms == 0 {
+            0.0
+        } else {
+            self.size_bytes as f64 * 1000.0 / self.duration_ms as f64
+        }
+    }
 }
 
 /// Progress callback type for monitoring downloads
 pub type ProgressCallback = Arc<dyn Fn(u64, u64, &str) + Send + Sync>;
 
+/// Progress event emitted by [`FtpDownloader::download_files`]
+///
+/// `index` is the position of the file in the slice passed to `download_files`.
+/// Every file ends with exactly one `FileFinished`; files skipped because they
+/// already exist locally get no `FileStarted` or `Progress`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DownloadEvent {
+    /// The batch is about to start
+    Started { files: usize, total_bytes: u64 },
+    /// A file transfer is starting
+    FileStarted { index: usize, file: String, size_bytes: u64 },
+    /// A chunk of a file was received
+    Progress { index: usize, downloaded: u64, total: u64, overall_downloaded: u64 },
+    /// A file finished, successfully or not
+    FileFinished { index: usize, file: String, result: DownloadResult },
+    /// Every file in the batch has finished; `downloaded_bytes` excludes skipped files
+    BatchFinished { files: usize, total_bytes: u64, downloaded_bytes: u64, duration_ms: u64 },
+}
+
+/// Handler receiving [`DownloadEvent`]s, called from the download worker threads
+pub type DownloadEventHandler = Arc<dyn Fn(&DownloadEvent) + Send + Sync>;
+
+/// Creates the indicatif target the console progress display draws to
+pub type ProgressTargetFactory = Arc<dyn Fn() -> ProgressDrawTarget + Send + Sync>;
+
+/// Create an event handler that forwards every event into an `mpsc` channel
+pub fn download_event_channel() -> (DownloadEventHandler, std::sync::mpsc::Receiver<DownloadEvent>) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    let handler: DownloadEventHandler = Arc::new(move |event: &DownloadEvent| {
+        // A dropped receiver just means nobody is listening anymore
+        let _ = tx.lock().unwrap().send(event.clone());
+    });
+    (handler, rx)
+}
+
 /// FTP file downloader with progress tracking
 #[derive(Clone)]
 pub struct FtpDownloader {
@@ -70,6 +202,12 @@ pub struct FtpDownloader {
     config: DownloadConfig,
     /// Optional progress callback
     progress_callback: Option<ProgressCallback>,
+    /// Optional event handler replacing the console display in `download_files`
+    event_handler: Option<DownloadEventHandler>,
+    /// Never create progress bars or write to the terminal
+    silent: bool,
+    /// Where progress bars are drawn, stderr unless overridden
+    progress_target: ProgressTargetFactory,
 }
 
 impl FtpDownloader {
@@ -79,6 +217,9 @@ impl FtpDownloader {
             provider: FtpFileSystemProvider::new_datasus(),
             config: DownloadConfig::default(),
             progress_callback: None,
+            event_handler: None,
+            silent: false,
+            progress_target: Arc::new(ProgressDrawTarget::stderr),
         }
     }
 
@@ -90,13 +231,19 @@ impl FtpDownloader {
             preserve_structure: true,
             max_concurrent: 4,
             buffer_size: 8192,
-            overwrite: false,
+            overwrite: OverwritePolicy::Never,
+            layout: LocalLayout::default(),
+            max_retries: default_max_retries(),
+            verify_readable: false,
         };
         
         Ok(Self {
             provider: FtpFileSystemProvider::new_datasus(),
             config,
             progress_callback: None,
+            event_handler: None,
+            silent: false,
+            progress_target: Arc::new(ProgressDrawTarget::stderr),
         })
     }
 
@@ -106,15 +253,42 @@ impl FtpDownloader {
             provider,
             config,
             progress_callback: None,
+            event_handler: None,
+            silent: false,
+            progress_target: Arc::new(ProgressDrawTarget::stderr),
         }
     }
 
+    /// Create a downloader that never touches the terminal, for embedding in servers
+    ///
+    /// No progress bars are created and nothing is written to stdout or stderr;
+    /// callers get only the returned `DownloadResult`s, plus any progress callback
+    /// or event handler they set.
+    pub fn new_silent(provider: FtpFileSystemProvider, config: DownloadConfig) -> Self {
+        Self {
+            silent: true,
+            ..Self::new(provider, config)
+        }
+    }
+
+    /// Draw the console progress display to targets made by `factory` instead of stderr
+    pub fn with_progress_target(mut self, factory: ProgressTargetFactory) -> Self {
+        self.progress_target = factory;
+        self
+    }
+
     /// Set a progress callback for monitoring download progress
     pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
         self.progress_callback = Some(callback);
         self
     }
 
+    /// Send `download_files` events to `handler` instead of the console display
+    pub fn with_event_handler(mut self, handler: DownloadEventHandler) -> Self {
+        self.event_handler = Some(handler);
+        self
+    }
+
     /// Update the download configuration
     pub fn with_config(mut self, config: DownloadConfig) -> Self {
         self.config = config;
@@ -123,22 +297,47 @@ impl FtpDownloader {
 
     /// Download a single file with progress bar
     pub async fn download_file(&self, file: &File) -> Result<DownloadResult> {
+        let span = tracing::info_span!(
+            "download_file",
+            path = %file.path,
+            size = file.size_bytes().unwrap_or(0),
+            success = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        );
+        let result = self.download_file_impl(file).instrument(span.clone()).await;
+        match &result {
+            Ok(download) => {
+                span.record("success", download.success);
+                span.record("duration_ms", download.duration_ms);
+                tracing::info!(parent: &span, local_path = %download.local_path, error = download.error.as_deref(), "download finished");
+            }
+            Err(e) => tracing::warn!(parent: &span, error = %e, "download failed"),
+        }
+        result
+    }
+
+    async fn download_file_impl(&self, file: &File) -> Result<DownloadResult> {
         let start_time = std::time::Instant::now();
 
         // Determine local path
         let local_path = self.get_local_path(file)?;
         
-        // Check if file exists and should not be overwritten
-        if path_exists_async(&local_path).await && !self.config.overwrite {
-            return Ok(DownloadResult {
-                ftp_path: file.path.clone(),
-                local_path: local_path.to_string_lossy().to_string(),
-                size_bytes: file.size_bytes().unwrap_or(0),
-                success: false,
-                error: Some("File exists and overwrite is disabled".to_string()),
-                duration_ms: start_time.elapsed().as_millis() as u64,
-            });
-        }
+        // Keep an existing local file unless the overwrite policy says otherwise
+        let overwrite_reason = match self.overwrite_decision(file, &local_path).await {
+            Some((false, reason)) => {
+                return Ok(DownloadResult {
+                    ftp_path: file.path.clone(),
+                    local_path: local_path.to_string_lossy().to_string(),
+                    size_bytes: file.size_bytes().unwrap_or(0),
+                    success: false,
+                    error: Some(reason.clone()),
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    row_count: None,
+                    overwrite_reason: Some(reason),
+                });
+            }
+            decision => decision.map(|(_, reason)| reason),
+        };
 
         // Create parent directories if needed
         if let Some(parent) = local_path.parent() {
@@ -148,21 +347,32 @@ impl FtpDownloader {
         // Get file size for progress tracking
         let total_size = file.size_bytes().unwrap_or(0);
 
-        // Create progress bar with beautiful styling
-        let pb = ProgressBar::new(total_size);
-        pb.set_style(
-            ProgressStyle::with_template("{msg}\n{spinner:.yellow} [{elapsed_precise}] [{wide_bar:.magenta}] {bytes:>8.blue}/{total_bytes:<8.blue} ({bytes_per_sec:>10.blue}, {eta:>4.blue})")
-                .map_err(|e| anyhow!("Failed to set progress bar template: {}", e))?
-                .progress_chars("█▉▊▋▌▍▎▏ ")
-                .tick_strings(&[
-                    "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"
-                ])
-        );
-        pb.set_message(format!("Downloading {}", file.basename));
-        pb.enable_steady_tick(std::time::Duration::from_millis(100));
+        // Perform the download, with a progress bar unless silent
+        let (pb, result) = if self.silent {
+            let no_events: DownloadEventHandler = Arc::new(|_: &DownloadEvent| {});
+            let overall_progress = Arc::new(AtomicU64::new(0));
+            let counted = AtomicU64::new(0);
+            let result = self
+                .download_file_with_events(file, &local_path, 0, &overall_progress, &counted, &no_events, false)
+                .await;
+            (None, result)
+        } else {
+            // Create progress bar with beautiful styling
+            let pb = ProgressBar::with_draw_target(Some(total_size), (self.progress_target)());
+            pb.set_style(
+                ProgressStyle::with_template("{msg}\n{spinner:.yellow} [{elapsed_precise}] [{wide_bar:.magenta}] {bytes:>8.blue}/{total_bytes:<8.blue} ({bytes_per_sec:>10.blue}, {eta:>4.blue})")
+                    .map_err(|e| anyhow!("Failed to set progress bar template: {}", e))?
+                    .progress_chars("█▉▊▋▌▍▎▏ ")
+                    .tick_strings(&[
+                        "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"
+                    ])
+            );
+            pb.set_message(format!("Downloading {}", file.basename));
+            pb.enable_steady_tick(std::time::Duration::from_millis(100));
 
-        // Perform the download
-        let result = self.download_file_with_progress(file, &local_path, &pb).await;
+            let result = self.download_file_with_progress(file, &local_path, &pb).await;
+            (Some(pb), result)
+        };
 
         let duration = start_time.elapsed();
         
@@ -172,23 +382,30 @@ impl FtpDownloader {
                 let actual_size = get_file_size_async(&local_path).await.unwrap_or(0);
                 let verification_ok = actual_size == bytes_downloaded;
                 
-                pb.finish_with_message(format!("✓ Downloaded {} ({} bytes){}", 
-                    file.basename, 
-                    bytes_downloaded,
-                    if verification_ok { "" } else { " - Size mismatch!" }
-                ));
+                if let Some(pb) = &pb {
+                    pb.finish_with_message(format!("✓ Downloaded {} ({} bytes){}", 
+                        file.basename, 
+                        bytes_downloaded,
+                        if verification_ok { "" } else { " - Size mismatch!" }
+                    ));
+                }
                 
-                Ok(DownloadResult {
+                let result = DownloadResult {
                     ftp_path: file.path.clone(),
                     local_path: local_path.to_string_lossy().to_string(),
                     size_bytes: bytes_downloaded,
                     success: verification_ok,
                     error: if verification_ok { None } else { Some(format!("Size mismatch: expected {}, got {}", bytes_downloaded, actual_size)) },
                     duration_ms: duration.as_millis() as u64,
-                })
+                    row_count: None,
+                    overwrite_reason,
+                };
+                Ok(self.verify_download(result).await)
             }
             Err(e) => {
-                pb.finish_with_message(format!("✗ Failed to download {}", file.basename));
+                if let Some(pb) = &pb {
+                    pb.finish_with_message(format!("✗ Failed to download {}", file.basename));
+                }
                 
                 Ok(DownloadResult {
                     ftp_path: file.path.clone(),
@@ -197,294 +414,271 @@ impl FtpDownloader {
                     success: false,
                     error: Some(e.to_string()),
                     duration_ms: duration.as_millis() as u64,
+                    row_count: None,
+                    overwrite_reason,
                 })
             }
         }
     }
 
-    /// Download multiple files concurrently with beautiful progress bars
+    /// Download multiple files concurrently, reporting progress as [`DownloadEvent`]s
+    ///
+    /// `max_concurrent` workers pull files from a shared queue, so a large file
+    /// only occupies one worker while the others keep draining small ones. Failed
+    /// transfers are retried up to `max_retries` times with exponential backoff.
+    ///
+    /// Events go to the handler set with [`FtpDownloader::with_event_handler`];
+    /// without one, they drive the default indicatif console display (or are
+    /// dropped for a [`FtpDownloader::new_silent`] downloader).
     pub async fn download_files(&self, files: Vec<&File>) -> Result<Vec<DownloadResult>> {
         let start_time = std::time::Instant::now();
         
         // Calculate total size for overall progress
         let total_size: u64 = files.iter().map(|f| f.size_bytes().unwrap_or(0)).sum();
         let overall_progress = Arc::new(AtomicU64::new(0));
-        
-        // Create beautiful colored styles
-        let green_bold = Style::new().green().bold();
-        let yellow = Style::new().yellow();
-        let blue_bold = Style::new().blue().bold();
-        let blue = Style::new().blue();
-        
-        // Create a single MultiProgress instance to manage all progress bars
-        let mp = MultiProgress::new();
-        
-        // Show startup message
-        mp.println(format!("    {} Downloading {} files ({:.1} MB total)", 
-            blue_bold.apply_to("Starting"), 
-            files.len(),
-            total_size as f64 / (1024.0 * 1024.0)
-        )).unwrap();
-        
-        // Determine appropriate template based on terminal width
-        let term_width = Term::stdout().size().1;
-        
-        // Create OVERALL progress bar FIRST (at the top) with beautiful styling
-        let overall_template = if term_width > 100 {
-            "{prefix:>12.yellow.bold} [{wide_bar:.cyan}] {bytes:>10.blue}/{total_bytes:<10.blue} ({bytes_per_sec:>12.blue}) {msg}"
-        } else {
-            "{prefix:>12.yellow.bold} [{wide_bar:.cyan}] {bytes.blue}/{total_bytes.blue} {msg}"
-        };
-        
-        let overall_pb = mp.add(ProgressBar::new(total_size));
-        overall_pb.set_style(
-            ProgressStyle::with_template(&overall_template)
-                .map_err(|e| anyhow!("Failed to set overall progress bar template: {}", e))?
-                .progress_chars("█▉▊▋▌▍▎▏ ")
-        );
-        overall_pb.set_prefix("Downloading");
-        overall_pb.set_message("files...");
-        
-        // Pre-create all individual progress bars with beautiful styling (yellow spinners, yellow file names, blue bars)
-        let mut progress_bars = Vec::new();
-        
-        let file_template = if term_width > 100 {
-            "{spinner:.yellow} {msg:<18.yellow} [{wide_bar:.magenta}] {bytes:>8.blue}/{total_bytes:<8.blue} ({bytes_per_sec:>10.blue}, {eta:>4.blue})"
-        } else {
-            "{spinner:.yellow} {msg:<12.yellow} [{wide_bar:.magenta}] {bytes.blue}/{total_bytes.blue} ({eta.blue})"
-        };
-        
-        for file in &files {
-            let pb = mp.add(ProgressBar::new(file.size_bytes().unwrap_or(0)));
-            pb.set_style(
-                ProgressStyle::with_template(&file_template)
-                    .map_err(|e| anyhow!("Failed to set progress bar template: {}", e))?
-                    .progress_chars("█▉▊▋▌▍▎▏ ")
-                    .tick_strings(&[
-                        "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"
-                    ])
-            );
-            pb.set_message(format!("{}", file.basename));
-            pb.enable_steady_tick(std::time::Duration::from_millis(100));
-            progress_bars.push(pb);
-        }
 
-        // Convert async method to sync for rayon
-        let rt = tokio::runtime::Handle::current();
-        let downloader_ref = Arc::new(self.clone());
-        let mp_clone = mp.clone();
-        
-        // Use rayon to download files in parallel with pre-assigned progress bars
-        let results: Result<Vec<DownloadResult>> = files
-            .par_iter()
-            .zip(progress_bars.par_iter())
-            .map(|(file, pb)| {
-                let overall_pb = overall_pb.clone();
-                let overall_progress = overall_progress.clone();
-                let downloader = downloader_ref.clone();
-                let file = (*file).clone();
-                let mp_clone = mp_clone.clone();
-                let yellow = yellow.clone();
-                let blue = blue.clone();
-
-                // Use tokio block_in_place to run async code in rayon thread
-                let result = tokio::task::block_in_place(|| {
-                    rt.block_on(async {
-                        let start_time = std::time::Instant::now();
-                        let local_path = downloader.get_local_path(&file)?;
-                        
-                        // Check if file exists and should not be overwritten
-                        if local_path.exists() && !downloader.config.overwrite {
-                            return Ok(DownloadResult {
-                                ftp_path: file.path.clone(),
-                                local_path: local_path.to_string_lossy().to_string(),
-                                size_bytes: file.size_bytes().unwrap_or(0),
-                                success: false,
-                                error: Some("File exists and overwrite is disabled".to_string()),
-                                duration_ms: start_time.elapsed().as_millis() as u64,
-                            });
-                        }
-
-                        // Create parent directories if needed
-                        if let Some(parent) = local_path.parent() {
-                            tokio::fs::create_dir_all(parent).await?;
-                        }
+        let emit: DownloadEventHandler = match &self.event_handler {
+            Some(handler) => handler.clone(),
+            None if self.silent => Arc::new(|_: &DownloadEvent| {}),
+            None => ConsoleRenderer::new(&files, total_size, (self.progress_target)())?.into_handler(),
+        };
 
-                        // Show download start message
-                        let size_mb = file.size_bytes().unwrap_or(0) as f64 / (1024.0 * 1024.0);
-                        mp_clone.println(format!(
-                            "    {:>12} {} ({:.1} MB)",
-                            yellow.apply_to("Downloading"),
-                            file.basename,
-                            size_mb
-                        )).unwrap();
-
-                        // Download with dual progress tracking (individual + overall)
-                        let result = downloader.download_file_with_dual_progress(&file, &local_path, pb, &overall_progress, &overall_pb).await;
-                        let duration = start_time.elapsed();
-
-                        match result {
-                            Ok(bytes_downloaded) => {
-                                let mb_downloaded = bytes_downloaded as f64 / (1024.0 * 1024.0);
-                                let speed = mb_downloaded / duration.as_secs_f64();
-                                
-                                pb.finish_and_clear();
-                                
-                                mp_clone.println(format!(
-                                    "    {:>12} {} {}",
-                                    Style::new().green().bold().apply_to("✓ Finished"),
-                                    file.basename,
-                                    blue.apply_to(format!("({:.1} MB in {} @ {:.1} MB/s)", mb_downloaded, HumanDuration(duration), speed))
-                                )).unwrap();
-                                
-                                Ok(DownloadResult {
-                                    ftp_path: file.path.clone(),
-                                    local_path: local_path.to_string_lossy().to_string(),
-                                    size_bytes: bytes_downloaded,
-                                    success: true,
-                                    error: None,
-                                    duration_ms: duration.as_millis() as u64,
-                                })
-                            }
-                            Err(e) => {
-                                pb.finish_and_clear();
-                                
-                                mp_clone.println(format!(
-                                    "    {:>12} {} ({})",
-                                    Style::new().red().bold().apply_to("✗ Failed"),
-                                    file.basename,
-                                    e
-                                )).unwrap();
-                                
-                                Ok(DownloadResult {
-                                    ftp_path: file.path.clone(),
-                                    local_path: local_path.to_string_lossy().to_string(),
-                                    size_bytes: 0,
-                                    success: false,
-                                    error: Some(e.to_string()),
-                                    duration_ms: duration.as_millis() as u64,
-                                })
-                            }
-                        }
-                    })
-                });
+        emit(&DownloadEvent::Started {
+            files: files.len(),
+            total_bytes: total_size,
+        });
+
+        // Shared queue of (index in `files`, file), drained by the workers
+        let queue: Mutex<VecDeque<(usize, &File)>> = Mutex::new(files.iter().copied().enumerate().collect());
+        let workers = self.config.max_concurrent.clamp(1, files.len().max(1));
+
+        let worker_results = join_all((0..workers).map(|_| async {
+            let mut finished = Vec::new();
+            loop {
+                let next = queue.lock().unwrap().pop_front();
+                let Some((index, file)) = next else { break };
+                let result = self.download_queued_file(index, file, &overall_progress, &emit).await;
+                let failed = result.is_err();
+                finished.push((index, result));
+                if failed {
+                    break;
+                }
+            }
+            finished
+        }))
+        .await;
 
-                result
-            })
-            .collect();
+        let mut finished: Vec<(usize, Result<DownloadResult>)> = worker_results.into_iter().flatten().collect();
+        finished.sort_by_key(|(index, _)| *index);
+        let results: Result<Vec<DownloadResult>> = finished.into_iter().map(|(_, result)| result).collect();
 
-        // Finish overall progress bar with beautiful completion message
-        let total_duration = start_time.elapsed();
-        let total_mb = total_size as f64 / (1024.0 * 1024.0);
-        let avg_speed = total_mb / total_duration.as_secs_f64();
-        
-        overall_pb.finish_and_clear();
-        
-        mp.println(format!(
-            "    {:>12} {} files {}",
-            green_bold.apply_to("✅ Completed"),
-            files.len(),
-            blue.apply_to(format!("({:.1} MB) in {} @ {:.1} MB/s average", total_mb, HumanDuration(total_duration), avg_speed))
-        )).unwrap();
+        emit(&DownloadEvent::BatchFinished {
+            files: files.len(),
+            total_bytes: total_size,
+            downloaded_bytes: overall_progress.load(Ordering::SeqCst),
+            duration_ms: start_time.elapsed().as_millis() as u64,
+        });
 
         results
     }
 
-    /// Internal method to download a file with dual progress tracking (individual + overall)
-    async fn download_file_with_dual_progress(
+    /// Whether to replace an existing local copy of `file` under the overwrite policy, and why
+    ///
+    /// `None` when there is no local copy, so the file is simply downloaded.
+    async fn overwrite_decision(&self, file: &File, local_path: &Path) -> Option<(bool, String)> {
+        let metadata = tokio::fs::metadata(local_path).await.ok()?;
+        Some(match self.config.overwrite {
+            OverwritePolicy::Never => (false, "File exists and overwrite is disabled".to_string()),
+            OverwritePolicy::Always => (true, "Overwriting existing file".to_string()),
+            OverwritePolicy::IfNewer => {
+                let local_modified = metadata.modified().ok().map(chrono::DateTime::<chrono::Utc>::from);
+                match local_modified {
+                    Some(local) if file.modification_date() <= local => {
+                        (false, "Local copy is as recent as the server file".to_string())
+                    }
+                    _ => (true, "Server file is newer than the local copy".to_string()),
+                }
+            }
+            OverwritePolicy::IfDifferentSize => match file.size_bytes() {
+                Some(size) if size == metadata.len() => (false, "Local copy has the same size".to_string()),
+                Some(size) => (true, format!("Size differs: server {} bytes, local {} bytes", size, metadata.len())),
+                None => (true, "Server size unknown".to_string()),
+            },
+        })
+    }
+
+    /// Download one file taken from the `download_files` queue, retrying failed transfers
+    async fn download_queued_file(
         &self,
+        index: usize,
         file: &File,
-        local_path: &Path,
-        pb: &ProgressBar,
         overall_progress: &Arc<AtomicU64>,
-        overall_pb: &ProgressBar,
-    ) -> Result<u64> {
-        use suppaftp::{AsyncRustlsFtpStream, Mode, FtpError};
+        emit: &DownloadEventHandler,
+    ) -> Result<DownloadResult> {
+        let start_time = std::time::Instant::now();
+        let local_path = self.get_local_path(file)?;
+        
+        // Keep an existing local file unless the overwrite policy says otherwise
+        let overwrite_reason = match self.overwrite_decision(file, &local_path).await {
+            Some((false, reason)) => {
+                let result = DownloadResult {
+                    ftp_path: file.path.clone(),
+                    local_path: local_path.to_string_lossy().to_string(),
+                    size_bytes: file.size_bytes().unwrap_or(0),
+                    success: false,
+                    error: Some(reason.clone()),
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    row_count: None,
+                    overwrite_reason: Some(reason),
+                };
+                emit(&DownloadEvent::FileFinished {
+                    index,
+                    file: file.basename.clone(),
+                    result: result.clone(),
+                });
+                return Ok(result);
+            }
+            decision => decision.map(|(_, reason)| reason),
+        };
 
-        // Create FTP connection
-        let mut ftp_stream = AsyncRustlsFtpStream::connect(&format!("{}:{}", self.provider.host, self.provider.port)).await?;
-        ftp_stream.login("anonymous", "").await?;
-        ftp_stream.set_mode(Mode::Passive);
+        // Create parent directories if needed
+        if let Some(parent) = local_path.parent() {
+            ensure_dir_async(parent).await?;
+        }
 
-        // Navigate to the file's directory
-        let ftp_dir = if let Some(parent) = std::path::Path::new(&file.path).parent() {
-            parent.to_string_lossy().to_string()
-        } else {
-            "/".to_string()
+        emit(&DownloadEvent::FileStarted {
+            index,
+            file: file.basename.clone(),
+            size_bytes: file.size_bytes().unwrap_or(0),
+        });
+
+        // Download, reporting per-file and overall progress
+        let mut attempts = 0;
+        let counted = AtomicU64::new(0);
+        let retry_policy = RetryPolicy {
+            max_retries: self.config.max_retries,
+            base_backoff: DOWNLOAD_RETRY_INITIAL_BACKOFF,
+            ..RetryPolicy::default()
         };
+        let result = retry_policy
+            .retry(
+                |e: &anyhow::Error| {
+                    if classify_download_error(e) == FtpErrorKind::Permanent {
+                        return Retryable::No;
+                    }
+                    log::debug!("Download of {} failed, retrying: {}", file.basename, e);
+                    Retryable::Yes
+                },
+                || {
+                    attempts += 1;
+                    // Retries continue from what the failed attempt already wrote
+                    let resume = attempts > 1;
+                    self.download_file_with_events(file, &local_path, index, overall_progress, &counted, emit, resume)
+                },
+            )
+            .await;
+        let duration = start_time.elapsed();
+        if result.is_err() {
+            // Un-count the partial transfer so a failed file doesn't inflate overall progress
+            overall_progress.fetch_sub(counted.load(Ordering::SeqCst), Ordering::SeqCst);
+        }
 
-        let full_ftp_path = if ftp_dir.starts_with('/') {
-            format!("{}{}", self.provider.base_path, ftp_dir)
-        } else {
-            format!("{}/{}", self.provider.base_path, ftp_dir)
+        let result = match result {
+            Ok(bytes_downloaded) => DownloadResult {
+                ftp_path: file.path.clone(),
+                local_path: local_path.to_string_lossy().to_string(),
+                size_bytes: bytes_downloaded,
+                success: true,
+                error: None,
+                duration_ms: duration.as_millis() as u64,
+                row_count: None,
+                overwrite_reason,
+            },
+            Err(e) => DownloadResult {
+                ftp_path: file.path.clone(),
+                local_path: local_path.to_string_lossy().to_string(),
+                size_bytes: 0,
+                success: false,
+                error: Some(format!("{} (after {} attempt(s))", e, attempts)),
+                duration_ms: duration.as_millis() as u64,
+                row_count: None,
+                overwrite_reason,
+            },
         };
+        let result = self.verify_download(result).await;
 
-        ftp_stream.cwd(&full_ftp_path).await?;
-
-        // Open file for writing
-        let mut local_file = TokioFile::create(local_path).await?;
-
-        // Clone for use in the closure
-        let pb_clone = pb.clone();
-        let overall_progress_clone = overall_progress.clone();
-        let overall_pb_clone = overall_pb.clone();
-        let callback = self.progress_callback.clone();
-        let file_basename = file.basename.clone();
-        let expected_size = file.size_bytes().unwrap_or(0);
+        emit(&DownloadEvent::FileFinished {
+            index,
+            file: file.basename.clone(),
+            result: result.clone(),
+        });
 
-        // Use the retr method with a closure for dual progress tracking
-        let file_data = ftp_stream
-            .retr(&file.basename, move |mut data_stream| {
-                let pb_clone = pb_clone.clone();
-                let overall_progress_clone = overall_progress_clone.clone();
-                let overall_pb_clone = overall_pb_clone.clone();
-                let callback = callback.clone();
-                let file_basename = file_basename.clone();
-                
-                Box::pin(async move {
-                    let mut file_buffer = Vec::new();
-                    let mut total_downloaded = 0u64;
-                    let mut chunk_buffer = vec![0u8; 8192]; // Use reasonable buffer size
-
-                    loop {
-                        match data_stream.read(&mut chunk_buffer).await {
-                            Ok(0) => break, // EOF
-                            Ok(n) => {
-                                // Append this chunk to our file buffer
-                                file_buffer.extend_from_slice(&chunk_buffer[..n]);
-                                total_downloaded += n as u64;
-                                
-                                // Update individual progress bar
-                                pb_clone.set_position(total_downloaded);
-                                
-                                // Update overall progress
-                                let current_overall = overall_progress_clone.fetch_add(n as u64, Ordering::SeqCst) + n as u64;
-                                overall_pb_clone.set_position(current_overall);
-
-                                // Call progress callback if provided
-                                if let Some(ref callback) = callback {
-                                    callback(total_downloaded, expected_size, &file_basename);
-                                }
-                            }
-                            Err(e) => return Err(FtpError::ConnectionError(e)),
-                        }
-                    }
+        Ok(result)
+    }
 
-                    // Return all data and the stream
-                    Ok((file_buffer, data_stream))
-                })
-            })
-            .await?;
+    /// With `verify_readable`, parse a successful download and record its row count
+    ///
+    /// Only `.dbc`/`.dbf` files are checked; an unreadable one marks the result failed.
+    async fn verify_download(&self, mut result: DownloadResult) -> DownloadResult {
+        let is_dbase = Path::new(&result.local_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("dbc") || ext.eq_ignore_ascii_case("dbf"));
+        if !self.config.verify_readable || !result.success || !is_dbase {
+            return result;
+        }
 
-        // Write all data to file
-        if !file_data.is_empty() {
-            local_file.write_all(&file_data).await?;
+        let local_path = result.local_path.clone();
+        match tokio::task::spawn_blocking(move || verify_dbase_file(&local_path)).await {
+            Ok(Ok(row_count)) => result.row_count = Some(row_count),
+            Ok(Err(e)) => {
+                result.success = false;
+                result.error = Some(format!("Downloaded file is unreadable: {}", e));
+            }
+            Err(e) => {
+                result.success = false;
+                result.error = Some(format!("Verification task failed: {}", e));
+            }
         }
+        result
+    }
 
-        // Close FTP connection
-        let _ = ftp_stream.quit().await;
+    /// Internal method to download a file, emitting [`DownloadEvent::Progress`] per chunk
+    ///
+    /// `counted` holds the bytes of this file already added to `overall_progress`
+    /// by earlier attempts; it is kept in step as the file grows or restarts.
+    /// With `resume`, a partial file left by an earlier attempt is continued.
+    #[allow(clippy::too_many_arguments)]
+    async fn download_file_with_events(
+        &self,
+        file: &File,
+        local_path: &Path,
+        index: usize,
+        overall_progress: &Arc<AtomicU64>,
+        counted: &AtomicU64,
+        emit: &DownloadEventHandler,
+        resume: bool,
+    ) -> Result<u64> {
+        let expected_size = file.size_bytes().unwrap_or(0);
+        self.transfer_file(file, local_path, resume, |downloaded| {
+            // A restarted transfer shrinks the file, so this can also take bytes back off
+            let previous = counted.swap(downloaded, Ordering::SeqCst);
+            overall_progress.fetch_add(downloaded, Ordering::SeqCst);
+            let current_overall = overall_progress.fetch_sub(previous, Ordering::SeqCst) - previous;
+
+            emit(&DownloadEvent::Progress {
+                index,
+                downloaded,
+                total: expected_size,
+                overall_downloaded: current_overall,
+            });
 
-        Ok(file_data.len() as u64)
+            // Call progress callback if provided
+            if let Some(ref callback) = self.progress_callback {
+                callback(downloaded, expected_size, &file.basename);
+            }
+        })
+        .await
     }
 
     /// Internal method to download a file with progress tracking
@@ -494,104 +688,107 @@ impl FtpDownloader {
         local_path: &Path,
         pb: &ProgressBar,
     ) -> Result<u64> {
-        use suppaftp::{AsyncRustlsFtpStream, Mode, FtpError};
+        let expected_size = file.size_bytes().unwrap_or(0);
+        self.transfer_file(file, local_path, false, |downloaded| {
+            pb.set_position(downloaded);
 
-        // Create FTP connection
-        let mut ftp_stream = AsyncRustlsFtpStream::connect(&format!("{}:{}", self.provider.host, self.provider.port)).await?;
-        ftp_stream.login("anonymous", "").await?;
-        ftp_stream.set_mode(Mode::Passive);
+            // Call progress callback if provided
+            if let Some(ref callback) = self.progress_callback {
+                callback(downloaded, expected_size, &file.basename);
+            }
+        })
+        .await
+    }
 
-        // Navigate to the file's directory
-        let ftp_dir = if let Some(parent) = std::path::Path::new(&file.path).parent() {
-            parent.to_string_lossy().to_string()
-        } else {
-            "/".to_string()
-        };
+    /// Fetch `file` into `local_path`, writing each chunk as it arrives
+    ///
+    /// With `resume`, whatever an earlier attempt left in `local_path` is kept and
+    /// the transfer restarts from its length with `REST`; a server that refuses
+    /// `REST` gets a full transfer instead. `on_chunk` is called with the bytes on
+    /// disk after every chunk. Returns the final size of the local file.
+    async fn transfer_file(
+        &self,
+        file: &File,
+        local_path: &Path,
+        resume: bool,
+        mut on_chunk: impl FnMut(u64) + Send,
+    ) -> Result<u64> {
+        use suppaftp::FtpError;
 
-        let full_ftp_path = if ftp_dir.starts_with('/') {
-            format!("{}{}", self.provider.base_path, ftp_dir)
+        // Create FTP connection (TLS, login and data mode as configured on the provider)
+        let timeout = self.provider.operation_timeout;
+        let mut ftp_stream = self.provider.create_connection().await.map_err(|e| anyhow!(e))?;
+
+        // Navigate to the file's directory
+        let full_ftp_path = self.provider.full_path(&file.path).parent();
+        with_ftp_timeout("cwd", timeout, ftp_stream.cwd(full_ftp_path.as_str())).await.map_err(|e| anyhow!(e))?;
+
+        // Pick up after the bytes an earlier attempt already wrote
+        let mut offset = 0;
+        if resume {
+            let partial = tokio::fs::metadata(local_path).await.map(|metadata| metadata.len()).unwrap_or(0);
+            if partial > 0 {
+                match with_ftp_timeout("rest", timeout, ftp_stream.resume_transfer(partial as usize)).await {
+                    Ok(()) => offset = partial,
+                    Err(e) => log::debug!("Server refused to resume {} at byte {}, restarting: {}", file.basename, partial, e),
+                }
+            }
+        }
+        let mut local_file = if offset > 0 {
+            tokio::fs::OpenOptions::new().append(true).open(local_path).await?
         } else {
-            format!("{}/{}", self.provider.base_path, ftp_dir)
+            TokioFile::create(local_path).await?
         };
 
-        ftp_stream.cwd(&full_ftp_path).await?;
-
-        // Create local file
-        let mut local_file = TokioFile::create(local_path).await?;
-
-        // Progress tracking variables
-        let pb_clone = pb.clone();
-        let callback = self.progress_callback.clone();
-        let file_basename = file.basename.clone();
-        let expected_size = file.size_bytes().unwrap_or(0);
-
-        // Use the retr method with a closure for progress tracking
-        let file_data = ftp_stream
-            .retr(&file.basename, move |mut data_stream| {
-                let pb_clone = pb_clone.clone();
-                let callback = callback.clone();
-                let file_basename = file_basename.clone();
-                
-                Box::pin(async move {
-                    let mut file_buffer = Vec::new();
-                    let mut total_downloaded = 0u64;
-                    let mut chunk_buffer = vec![0u8; 8192]; // Use reasonable buffer size
-
-                    loop {
-                        match data_stream.read(&mut chunk_buffer).await {
-                            Ok(0) => break, // EOF
-                            Ok(n) => {
-                                // Append this chunk to our file buffer
-                                file_buffer.extend_from_slice(&chunk_buffer[..n]);
-                                total_downloaded += n as u64;
-                                
-                                // Update progress bar
-                                pb_clone.set_position(total_downloaded);
-
-                                // Call progress callback if provided
-                                if let Some(ref callback) = callback {
-                                    callback(total_downloaded, expected_size, &file_basename);
-                                }
-                            }
-                            Err(e) => return Err(FtpError::ConnectionError(e)),
-                        }
-                    }
-
-                    // Return all data and the stream
-                    Ok((file_buffer, data_stream))
-                })
-            })
-            .await?;
+        let mut data_stream = ftp_stream.retr_as_stream(&file.basename).await.map_err(|e| retr_error(e, timeout))?;
+        let mut downloaded = offset;
+        let mut chunk_buffer = vec![0u8; 8192];
+        loop {
+            // A stalled data connection counts as a timeout, however long the whole transfer takes
+            let read = tokio::time::timeout(timeout, data_stream.read(&mut chunk_buffer))
+                .await
+                .unwrap_or_else(|_| Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "no data received")));
+            let n = read.map_err(|e| retr_error(FtpError::ConnectionError(e), timeout))?;
+            if n == 0 {
+                break;
+            }
 
-        // Write all data to file
-        if !file_data.is_empty() {
-            local_file.write_all(&file_data).await?;
+            // Written before it is counted, so a failed attempt leaves exactly what was reported
+            local_file.write_all(&chunk_buffer[..n]).await?;
+            downloaded += n as u64;
+            on_chunk(downloaded);
         }
+        local_file.flush().await?;
+        ftp_stream.finalize_retr_stream(data_stream).await.map_err(|e| retr_error(e, timeout))?;
 
         // Close FTP connection
         let _ = ftp_stream.quit().await;
 
-        Ok(file_data.len() as u64)
+        Ok(downloaded)
     }
 
     /// Get the local path for a file based on the configuration
     fn get_local_path(&self, file: &File) -> Result<std::path::PathBuf> {
         let mut local_path = std::path::PathBuf::from(&self.config.output_dir);
 
-        if self.config.preserve_structure {
-            // Remove the base path from the FTP path to get relative path
-            let relative_path = if file.path.starts_with(&self.provider.base_path) {
-                file.path.strip_prefix(&self.provider.base_path)
-                    .unwrap_or(&file.path)
-                    .trim_start_matches('/')
-            } else {
-                file.path.trim_start_matches('/')
-            };
-
-            // If there's a directory structure, add it
-            if let Some(parent) = std::path::Path::new(relative_path).parent() {
-                if !parent.as_os_str().is_empty() {
-                    local_path.push(parent);
+        // Remove the base path from the FTP path to get relative path
+        let ftp_path = FtpPath::new(&file.path);
+        let relative_path = ftp_path
+            .strip_prefix(&FtpPath::new(&self.provider.base_path))
+            .unwrap_or_else(|| ftp_path.as_str().trim_start_matches('/'));
+
+        match self.config.layout {
+            LocalLayout::Flat => {}
+            LocalLayout::SubsystemPartitioned => {
+                match Self::partitioned_dir(relative_path, &file.basename) {
+                    Some(dir) => local_path.push(dir),
+                    // Unparseable filenames keep their FTP location
+                    None => Self::push_ftp_parent(&mut local_path, relative_path),
+                }
+            }
+            LocalLayout::MirrorFtp => {
+                if self.config.preserve_structure {
+                    Self::push_ftp_parent(&mut local_path, relative_path);
                 }
             }
         }
@@ -600,6 +797,30 @@ impl FtpDownloader {
         Ok(local_path)
     }
 
+    /// Append the FTP parent directory of a relative path
+    fn push_ftp_parent(local_path: &mut std::path::PathBuf, relative_path: &str) {
+        if let Some(parent) = std::path::Path::new(relative_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                local_path.push(parent);
+            }
+        }
+    }
+
+    /// Build `{subsystem}/{group}/{uf}/{year}` for a DATASUS file, if its name parses
+    fn partitioned_dir(relative_path: &str, basename: &str) -> Option<std::path::PathBuf> {
+        let info = DataSusFileInfo::parse(basename)?;
+        let subsystem = relative_path
+            .split('/')
+            .next()
+            .filter(|component| !component.is_empty() && *component != basename)?;
+
+        let mut dir = std::path::PathBuf::from(subsystem);
+        dir.push(&info.group_name);
+        dir.push(&info.uf_code);
+        dir.push(info.full_year().to_string());
+        Some(dir)
+    }
+
     /// Create a silent progress callback (indicatif handles visual progress)
     pub fn create_console_progress_callback() -> ProgressCallback {
         Arc::new(|_downloaded: u64, _total: u64, _filename: &str| {
@@ -608,6 +829,161 @@ impl FtpDownloader {
     }
 }
 
+/// Turn a stalled RETR into `FtpTimeout`, passing other FTP errors through
+fn retr_error(error: suppaftp::FtpError, timeout: std::time::Duration) -> anyhow::Error {
+    match &error {
+        suppaftp::FtpError::ConnectionError(io_error) if io_error.kind() == std::io::ErrorKind::TimedOut => {
+            anyhow::Error::new(FtpTimeout {
+                operation: "retr".to_string(),
+                timeout,
+            })
+        }
+        _ => anyhow::Error::new(error),
+    }
+}
+
+/// Classify a failed download attempt with the provider's FTP error rules
+///
+/// Provider errors are wrapped boxed, so anyhow can only downcast those to the box itself.
+fn classify_download_error(error: &anyhow::Error) -> FtpErrorKind {
+    match error.downcast_ref::<Box<dyn std::error::Error + Send + Sync>>() {
+        Some(boxed) => classify_provider_error(boxed.as_ref()),
+        None => classify_provider_error(error.as_ref()),
+    }
+}
+
+/// Default [`DownloadEvent`] consumer rendering indicatif progress bars
+struct ConsoleRenderer {
+    mp: MultiProgress,
+    overall_pb: ProgressBar,
+    file_pbs: Vec<ProgressBar>,
+}
+
+impl ConsoleRenderer {
+    /// Pre-create the overall bar and one bar per file, all drawn to `target`
+    fn new(files: &[&File], total_size: u64, target: ProgressDrawTarget) -> Result<Self> {
+        // Create a single MultiProgress instance to manage all progress bars
+        let mp = MultiProgress::with_draw_target(target);
+        
+        // Determine appropriate template based on terminal width
+        let term_width = Term::stdout().size().1;
+        
+        // Create OVERALL progress bar FIRST (at the top) with beautiful styling
+        let overall_template = if term_width > 100 {
+            "{prefix:>12.yellow.bold} [{wide_bar:.cyan}] {bytes:>10.blue}/{total_bytes:<10.blue} ({bytes_per_sec:>12.blue}) {msg}"
+        } else {
+            "{prefix:>12.yellow.bold} [{wide_bar:.cyan}] {bytes.blue}/{total_bytes.blue} {msg}"
+        };
+        
+        let overall_pb = mp.add(ProgressBar::new(total_size));
+        overall_pb.set_style(
+            ProgressStyle::with_template(overall_template)
+                .map_err(|e| anyhow!("Failed to set overall progress bar template: {}", e))?
+                .progress_chars("█▉▊▋▌▍▎▏ ")
+        );
+        overall_pb.set_prefix("Downloading");
+        overall_pb.set_message("files...");
+        
+        // Pre-create all individual progress bars with beautiful styling (yellow spinners, yellow file names, blue bars)
+        let mut file_pbs = Vec::new();
+        
+        let file_template = if term_width > 100 {
+            "{spinner:.yellow} {msg:<18.yellow} [{wide_bar:.magenta}] {bytes:>8.blue}/{total_bytes:<8.blue} ({bytes_per_sec:>10.blue}, {eta:>4.blue})"
+        } else {
+            "{spinner:.yellow} {msg:<12.yellow} [{wide_bar:.magenta}] {bytes.blue}/{total_bytes.blue} ({eta.blue})"
+        };
+        
+        for file in files {
+            let pb = mp.add(ProgressBar::new(file.size_bytes().unwrap_or(0)));
+            pb.set_style(
+                ProgressStyle::with_template(file_template)
+                    .map_err(|e| anyhow!("Failed to set progress bar template: {}", e))?
+                    .progress_chars("█▉▊▋▌▍▎▏ ")
+                    .tick_strings(&[
+                        "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"
+                    ])
+            );
+            pb.set_message(file.basename.to_string());
+            pb.enable_steady_tick(std::time::Duration::from_millis(100));
+            file_pbs.push(pb);
+        }
+
+        Ok(Self { mp, overall_pb, file_pbs })
+    }
+
+    fn into_handler(self) -> DownloadEventHandler {
+        Arc::new(move |event: &DownloadEvent| self.handle(event))
+    }
+
+    fn handle(&self, event: &DownloadEvent) {
+        let blue = Style::new().blue();
+
+        // Console output is best effort; a closed terminal must not abort downloads
+        match event {
+            DownloadEvent::Started { files, total_bytes } => {
+                let _ = self.mp.println(format!("    {} Downloading {} files ({:.1} MB total)", 
+                    Style::new().blue().bold().apply_to("Starting"), 
+                    files,
+                    *total_bytes as f64 / (1024.0 * 1024.0)
+                ));
+            }
+            DownloadEvent::FileStarted { file, size_bytes, .. } => {
+                let _ = self.mp.println(format!(
+                    "    {:>12} {} ({:.1} MB)",
+                    Style::new().yellow().apply_to("Downloading"),
+                    file,
+                    *size_bytes as f64 / (1024.0 * 1024.0)
+                ));
+            }
+            DownloadEvent::Progress { index, downloaded, overall_downloaded, .. } => {
+                if let Some(pb) = self.file_pbs.get(*index) {
+                    pb.set_position(*downloaded);
+                }
+                self.overall_pb.set_position(*overall_downloaded);
+            }
+            DownloadEvent::FileFinished { index, file, result } => {
+                if let Some(pb) = self.file_pbs.get(*index) {
+                    pb.finish_and_clear();
+                }
+
+                if result.success {
+                    let duration = std::time::Duration::from_millis(result.duration_ms);
+                    let mb_downloaded = result.size_bytes as f64 / (1024.0 * 1024.0);
+                    let speed = result.bytes_per_sec() / (1024.0 * 1024.0);
+
+                    let _ = self.mp.println(format!(
+                        "    {:>12} {} {}",
+                        Style::new().green().bold().apply_to("✓ Finished"),
+                        file,
+                        blue.apply_to(format!("({:.1} MB in {} @ {:.1} MB/s)", mb_downloaded, HumanDuration(duration), speed))
+                    ));
+                } else {
+                    let _ = self.mp.println(format!(
+                        "    {:>12} {} ({})",
+                        Style::new().red().bold().apply_to("✗ Failed"),
+                        file,
+                        result.error.as_deref().unwrap_or("unknown error")
+                    ));
+                }
+            }
+            DownloadEvent::BatchFinished { files, total_bytes, downloaded_bytes, duration_ms } => {
+                let total_duration = std::time::Duration::from_millis(*duration_ms);
+                let total_mb = *total_bytes as f64 / (1024.0 * 1024.0);
+                let avg_speed = *downloaded_bytes as f64 / (1024.0 * 1024.0) / total_duration.as_secs_f64();
+
+                self.overall_pb.finish_and_clear();
+
+                let _ = self.mp.println(format!(
+                    "    {:>12} {} files {}",
+                    Style::new().green().bold().apply_to("✅ Completed"),
+                    files,
+                    blue.apply_to(format!("({:.1} MB) in {} @ {:.1} MB/s average", total_mb, HumanDuration(total_duration), avg_speed))
+                ));
+            }
+        }
+    }
+}
+
 /// Convenience functions for common download scenarios
 
 /// Download a single DATASUS file to the default downloads directory
@@ -631,11 +1007,106 @@ pub async fn download_datasus_files_with_config(
     downloader.download_files(files).await
 }
 
+/// Download the `group` files of every UF in `ufs` for each month in `periods`
+///
+/// File names follow the DATASUS `<group><UF><YY><MM>.dbc` template and are
+/// looked up in a single listing of `path` made with `provider`. Files the
+/// listing lacks get a failed [`DownloadResult`] saying so; the rest are
+/// downloaded concurrently by `downloader`. Results are ordered by UF, then
+/// period. Unknown UFs and malformed group codes are rejected up front.
+///
+/// # Examples
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// use shared::models::date_utils::Period;
+/// use shared::models::directory::FtpFileSystemProvider;
+/// use shared::models::download::{download_selection, FtpDownloader};
+///
+/// let provider = FtpFileSystemProvider::new_datasus();
+/// let downloader = FtpDownloader::new_datasus();
+/// // SIH RD for SP, 2018 through 2020
+/// let results = download_selection(
+///     &provider, "/SIHSUS/200801_/Dados", "RD", &["SP"], Period::Year(2018)..Period::Year(2021), &downloader,
+/// ).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn download_selection<P: FileSystemProvider + ?Sized>(
+    provider: &P,
+    path: &str,
+    group: &str,
+    ufs: &[&str],
+    periods: std::ops::Range<Period>,
+    downloader: &FtpDownloader,
+) -> Result<Vec<DownloadResult>> {
+    if group.is_empty() || !group.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(anyhow!("Invalid group code '{}'", group));
+    }
+    let group = group.to_ascii_uppercase();
+    let ufs = ufs
+        .iter()
+        .map(|uf| get_state_info(uf).map(|state| state.uf))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // A whole year stands for its January, and the range's end is exclusive
+    let first_month = |period: Period| Period::YearMonth(period.year(), period.month().unwrap_or(1));
+    let end = first_month(periods.end);
+    let months: Vec<Period> = Period::range(first_month(periods.start), end)?
+        .filter(|month| *month != end)
+        .collect();
+    let wanted: Vec<String> = ufs
+        .iter()
+        .flat_map(|uf| {
+            let group = &group;
+            months.iter().map(move |month| {
+                let year = (month.year() % 100) as u8;
+                DataSusFileInfo::generate_filename(group, uf, year, month.month().unwrap_or(1) as u8)
+            })
+        })
+        .collect();
+
+    let listing = provider.list_directory(path).await.map_err(|e| anyhow!(e))?;
+    let listed: std::collections::HashMap<String, &File> = listing
+        .values()
+        .filter_map(|entry| match entry {
+            DirectoryEntry::File(file) => Some((file.basename.to_ascii_uppercase(), file)),
+            DirectoryEntry::Directory(_) => None,
+        })
+        .collect();
+
+    let mut results: Vec<Option<DownloadResult>> = vec![None; wanted.len()];
+    let mut found: Vec<(usize, &File)> = Vec::new();
+    for (slot, name) in wanted.iter().enumerate() {
+        match listed.get(&name.to_ascii_uppercase()) {
+            Some(file) => found.push((slot, *file)),
+            None => {
+                results[slot] = Some(DownloadResult {
+                    ftp_path: FtpPath::new(path).join(name).to_string(),
+                    local_path: String::new(),
+                    size_bytes: 0,
+                    success: false,
+                    error: Some("Not found on the server".to_string()),
+                    duration_ms: 0,
+                    row_count: None,
+                    overwrite_reason: None,
+                });
+            }
+        }
+    }
+
+    let downloaded = downloader.download_files(found.iter().map(|(_, file)| *file).collect()).await?;
+    for ((slot, _), result) in found.iter().zip(downloaded) {
+        results[*slot] = Some(result);
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::file_info::{FileInfo, FileSize};
     use chrono::Utc;
+    use crate::models::mock_ftp::{MockFtpServer, MockReply};
 
     fn create_test_file() -> File {
         let info = FileInfo::new(
@@ -653,7 +1124,8 @@ mod tests {
         assert!(config.preserve_structure);
         assert_eq!(config.max_concurrent, 4);
         assert_eq!(config.buffer_size, 8192);
-        assert!(!config.overwrite);
+        assert_eq!(config.overwrite, OverwritePolicy::Never);
+        assert_eq!(config.layout, LocalLayout::MirrorFtp);
     }
 
     #[test]
@@ -690,6 +1162,54 @@ mod tests {
         assert_eq!(local_path.file_name().unwrap().to_str().unwrap(), "test_file.txt");
     }
 
+    #[test]
+    fn test_local_path_subsystem_partitioned() {
+        let mut config = DownloadConfig::default();
+        config.layout = LocalLayout::SubsystemPartitioned;
+
+        let downloader = FtpDownloader::new_datasus().with_config(config);
+        let info = FileInfo::new(FileSize::from_bytes(1024), ".dbc".to_string(), Utc::now());
+        let file = File::new("/dissemin/publicos/SIHSUS/200801_/Dados", "RDSP2012.dbc", info);
+
+        let local_path = downloader.get_local_path(&file).unwrap();
+        let expected = std::path::Path::new("./downloads")
+            .join("SIHSUS")
+            .join("RD")
+            .join("SP")
+            .join("2020")
+            .join("RDSP2012.dbc");
+        assert_eq!(local_path, expected);
+    }
+
+    #[test]
+    fn test_local_path_partitioned_falls_back_to_mirror() {
+        let mut config = DownloadConfig::default();
+        config.layout = LocalLayout::SubsystemPartitioned;
+
+        let downloader = FtpDownloader::new_datasus().with_config(config);
+        let info = FileInfo::new(FileSize::from_bytes(1024), ".pdf".to_string(), Utc::now());
+        let file = File::new("/dissemin/publicos/SIHSUS/200801_/Doc", "Leiame.pdf", info);
+
+        let local_path = downloader.get_local_path(&file).unwrap();
+        let expected = std::path::Path::new("./downloads")
+            .join("SIHSUS/200801_/Doc")
+            .join("Leiame.pdf");
+        assert_eq!(local_path, expected);
+    }
+
+    #[test]
+    fn test_local_path_flat_layout() {
+        let mut config = DownloadConfig::default();
+        config.layout = LocalLayout::Flat;
+
+        let downloader = FtpDownloader::new_datasus().with_config(config);
+        let info = FileInfo::new(FileSize::from_bytes(1024), ".dbc".to_string(), Utc::now());
+        let file = File::new("/dissemin/publicos/SIHSUS/200801_/Dados", "RDSP2012.dbc", info);
+
+        let local_path = downloader.get_local_path(&file).unwrap();
+        assert_eq!(local_path, std::path::Path::new("./downloads").join("RDSP2012.dbc"));
+    }
+
     #[test]
     fn test_progress_callback_creation() {
         let callback = FtpDownloader::create_console_progress_callback();
@@ -700,6 +1220,503 @@ mod tests {
         callback(100, 0, "unknown_size.txt"); // Test with unknown total
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_files_emits_events() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DownloadConfig {
+            output_dir: temp_dir.path().to_string_lossy().to_string(),
+            preserve_structure: false,
+            ..DownloadConfig::default()
+        };
+
+        // An existing local file is skipped, so no FTP connection is made
+        let file = create_test_file();
+        std::fs::write(temp_dir.path().join("test_file.txt"), b"existing").unwrap();
+
+        let (handler, events) = download_event_channel();
+        let downloader = FtpDownloader::new_datasus()
+            .with_config(config)
+            .with_event_handler(handler);
+
+        let results = downloader.download_files(vec![&file]).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].success);
+
+        let events: Vec<DownloadEvent> = events.try_iter().collect();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], DownloadEvent::Started { files: 1, total_bytes: 1024 }));
+        match &events[1] {
+            DownloadEvent::FileFinished { index, file, result } => {
+                assert_eq!(*index, 0);
+                assert_eq!(file, "test_file.txt");
+                assert_eq!(result.error.as_deref(), Some("File exists and overwrite is disabled"));
+            }
+            other => panic!("expected FileFinished, got {:?}", other),
+        }
+        assert!(matches!(events[2], DownloadEvent::BatchFinished { files: 1, total_bytes: 1024, .. }));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_files_queue_retries_failed_transfer() {
+        use std::sync::atomic::AtomicUsize;
+
+        fn contents(name: &str, size: usize) -> Vec<u8> {
+            name.bytes().cycle().take(size).collect()
+        }
+
+        // The first RETR of FLAKY.dbc sends half the data, then fails with 451
+        let sizes: Vec<(&'static str, usize)> = vec![
+            ("BIG.dbc", 200_000),
+            ("FLAKY.dbc", 3_000),
+            ("SMALL1.dbc", 1_000),
+            ("SMALL2.dbc", 2_000),
+        ];
+        let flaky_attempts = Arc::new(AtomicUsize::new(0));
+        let server_sizes = sizes.clone();
+        let server_attempts = flaky_attempts.clone();
+        let server = MockFtpServer::start(move |command, arg| {
+            if command != "RETR" {
+                return None;
+            }
+            let size = server_sizes.iter().find(|(name, _)| *name == arg).map(|(_, size)| *size).unwrap();
+            let mut body = contents(arg, size);
+            if arg == "FLAKY.dbc" && server_attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                body.truncate(size / 2);
+                return Some(MockReply::Data { body, done: "451 transfer aborted".to_string() });
+            }
+            Some(MockReply::data(body))
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DownloadConfig {
+            output_dir: temp_dir.path().to_string_lossy().to_string(),
+            layout: LocalLayout::Flat,
+            max_concurrent: 2,
+            overwrite: OverwritePolicy::Always,
+            max_retries: 1,
+            ..DownloadConfig::default()
+        };
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let (handler, events) = download_event_channel();
+        let downloader = FtpDownloader::new_silent(provider, config).with_event_handler(handler);
+
+        let files: Vec<File> = sizes
+            .iter()
+            .map(|(name, size)| {
+                let info = FileInfo::new(FileSize::from_bytes(*size as u64), ".dbc".to_string(), Utc::now());
+                File::new("/data", name, info)
+            })
+            .collect();
+        let results = downloader.download_files(files.iter().collect()).await.unwrap();
+
+        assert_eq!(results.len(), sizes.len());
+        for (result, (name, size)) in results.iter().zip(&sizes) {
+            assert!(result.success, "{} failed: {:?}", name, result.error);
+            assert_eq!(result.size_bytes, *size as u64);
+            assert_eq!(std::fs::read(temp_dir.path().join(name)).unwrap(), contents(name, *size));
+        }
+        assert_eq!(flaky_attempts.load(Ordering::SeqCst), 2);
+
+        // The aborted half of FLAKY.dbc must not be counted twice
+        let total: u64 = sizes.iter().map(|(_, size)| *size as u64).sum();
+        let finished = events.try_iter().last().unwrap();
+        assert!(matches!(
+            finished,
+            DownloadEvent::BatchFinished { files: 4, total_bytes, downloaded_bytes, .. }
+                if total_bytes == total && downloaded_bytes == total
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_does_not_retry_missing_file() {
+        let server = MockFtpServer::start(|command, _| match command {
+            "RETR" => Some(MockReply::line("550 no such file")),
+            _ => None,
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DownloadConfig {
+            output_dir: temp_dir.path().to_string_lossy().to_string(),
+            layout: LocalLayout::Flat,
+            overwrite: OverwritePolicy::Always,
+            max_retries: 3,
+            ..DownloadConfig::default()
+        };
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let downloader = FtpDownloader::new_silent(provider, config);
+        let info = FileInfo::new(FileSize::from_bytes(1024), ".dbc".to_string(), Utc::now());
+        let file = File::new("/data", "GONE.dbc", info);
+
+        let results = downloader.download_files(vec![&file]).await.unwrap();
+        assert!(!results[0].success);
+        assert!(results[0].error.as_deref().unwrap().ends_with("(after 1 attempt(s))"));
+        let retrs = server.commands().iter().filter(|line| line.starts_with("RETR")).count();
+        assert_eq!(retrs, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_resumes_after_dropped_connection() {
+        use std::sync::atomic::AtomicUsize;
+
+        let body: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+        // The first RETR of each server hangs up after 4000 bytes
+        async fn flaky_server(body: &[u8], refuse_rest: bool) -> MockFtpServer {
+            let retrs = Arc::new(AtomicUsize::new(0));
+            let body = body.to_vec();
+            MockFtpServer::start(move |command, _| match command {
+                "REST" if refuse_rest => Some(MockReply::line("502 not implemented")),
+                "RETR" if retrs.fetch_add(1, Ordering::SeqCst) == 0 => Some(MockReply::Abort { body: body[..4_000].to_vec() }),
+                "RETR" => Some(MockReply::data(body.clone())),
+                _ => None,
+            })
+            .await
+        }
+
+        async fn download(server: &MockFtpServer, output_dir: &Path) -> (DownloadResult, u64) {
+            let config = DownloadConfig {
+                output_dir: output_dir.to_string_lossy().to_string(),
+                layout: LocalLayout::Flat,
+                overwrite: OverwritePolicy::Always,
+                max_retries: 1,
+                ..DownloadConfig::default()
+            };
+            let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+                .with_operation_timeout(std::time::Duration::from_secs(5));
+            let (handler, events) = download_event_channel();
+            let downloader = FtpDownloader::new_silent(provider, config).with_event_handler(handler);
+            let info = FileInfo::new(FileSize::from_bytes(10_000), ".dbc".to_string(), Utc::now());
+            let file = File::new("/data", "RDSP2301.dbc", info);
+
+            let mut results = downloader.download_files(vec![&file]).await.unwrap();
+            let downloaded_bytes = match events.try_iter().last().unwrap() {
+                DownloadEvent::BatchFinished { downloaded_bytes, .. } => downloaded_bytes,
+                other => panic!("expected BatchFinished, got {:?}", other),
+            };
+            (results.remove(0), downloaded_bytes)
+        }
+
+        // The retry asks for the rest of the file only
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server = flaky_server(&body, false).await;
+        let (result, downloaded_bytes) = download(&server, temp_dir.path()).await;
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.size_bytes, 10_000);
+        assert_eq!(downloaded_bytes, 10_000);
+        assert_eq!(std::fs::read(temp_dir.path().join("RDSP2301.dbc")).unwrap(), body);
+        assert!(server.commands().contains(&"REST 4000".to_string()));
+
+        // Without REST the retry starts over and replaces the partial file
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server = flaky_server(&body, true).await;
+        let (result, downloaded_bytes) = download(&server, temp_dir.path()).await;
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(downloaded_bytes, 10_000);
+        assert_eq!(std::fs::read(temp_dir.path().join("RDSP2301.dbc")).unwrap(), body);
+        assert_eq!(server.commands().iter().filter(|line| line.starts_with("RETR")).count(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_verify_readable_flags_truncated_download() {
+        use crate::models::polars_utils::dbase_pl::fixtures;
+
+        let fixture_dir = tempfile::tempdir().unwrap();
+        let fixture_path = fixture_dir.path().join("GOOD.dbf");
+        fixtures::write_numbered_dbf(&fixture_path, 40);
+        let good = std::fs::read(&fixture_path).unwrap();
+        // Header still declares 40 records, and the listed size matches what is sent
+        let truncated = good[..good.len() - 100].to_vec();
+        let bodies: Vec<(&'static str, Vec<u8>)> = vec![("GOOD.dbf", good), ("CUT.dbf", truncated)];
+
+        let server_bodies = bodies.clone();
+        let server = MockFtpServer::start(move |command, arg| match command {
+            "RETR" => server_bodies.iter().find(|(name, _)| *name == arg).map(|(_, body)| MockReply::data(body.clone())),
+            _ => None,
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DownloadConfig {
+            output_dir: temp_dir.path().to_string_lossy().to_string(),
+            layout: LocalLayout::Flat,
+            overwrite: OverwritePolicy::Always,
+            max_retries: 0,
+            verify_readable: true,
+            ..DownloadConfig::default()
+        };
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let downloader = FtpDownloader::new_silent(provider, config);
+
+        let files: Vec<File> = bodies
+            .iter()
+            .map(|(name, body)| {
+                let info = FileInfo::new(FileSize::from_bytes(body.len() as u64), ".dbf".to_string(), Utc::now());
+                File::new("/data", name, info)
+            })
+            .collect();
+        let results = downloader.download_files(files.iter().collect()).await.unwrap();
+
+        assert!(results[0].success, "{:?}", results[0].error);
+        assert_eq!(results[0].row_count, Some(40));
+
+        // The byte size matches, but the records don't fit the file
+        assert!(!results[1].success);
+        assert_eq!(results[1].size_bytes, bodies[1].1.len() as u64);
+        assert_eq!(results[1].row_count, None);
+        assert!(results[1].error.as_deref().unwrap().starts_with("Downloaded file is unreadable"));
+
+        let single = downloader.download_file(&files[1]).await.unwrap();
+        assert!(!single.success);
+    }
+
+    /// Terminal stand-in keeping everything drawn to it
+    #[derive(Debug, Default, Clone)]
+    struct RecordingTerm {
+        written: Arc<Mutex<String>>,
+    }
+
+    impl indicatif::TermLike for RecordingTerm {
+        fn width(&self) -> u16 {
+            120
+        }
+
+        fn move_cursor_up(&self, _n: usize) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn move_cursor_down(&self, _n: usize) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn move_cursor_right(&self, _n: usize) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn move_cursor_left(&self, _n: usize) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn write_line(&self, s: &str) -> std::io::Result<()> {
+            let mut written = self.written.lock().unwrap();
+            written.push_str(s);
+            written.push('\n');
+            Ok(())
+        }
+
+        fn write_str(&self, s: &str) -> std::io::Result<()> {
+            self.written.lock().unwrap().push_str(s);
+            Ok(())
+        }
+
+        fn clear_line(&self) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn flush(&self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_silent_downloader_draws_no_progress() {
+        use std::sync::atomic::AtomicUsize;
+
+        // Serves every RETR with the file name as its contents
+        let server = MockFtpServer::start(|command, arg| match command {
+            "RETR" => Some(MockReply::data(arg.as_bytes())),
+            _ => None,
+        })
+        .await;
+
+        // Every progress target the downloader asks for draws into `term`
+        let term = RecordingTerm::default();
+        let targets = Arc::new(AtomicUsize::new(0));
+        let progress_target: ProgressTargetFactory = {
+            let (term, targets) = (term.clone(), targets.clone());
+            Arc::new(move || {
+                targets.fetch_add(1, Ordering::SeqCst);
+                ProgressDrawTarget::term_like(Box::new(term.clone()))
+            })
+        };
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DownloadConfig {
+            output_dir: temp_dir.path().to_string_lossy().to_string(),
+            layout: LocalLayout::Flat,
+            overwrite: OverwritePolicy::Always,
+            max_retries: 0,
+            ..DownloadConfig::default()
+        };
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let info = FileInfo::new(FileSize::from_bytes(12), ".dbc".to_string(), Utc::now());
+        let file = File::new("/data", "RDSP2301.dbc", info);
+
+        let silent = FtpDownloader::new_silent(provider.clone(), config.clone()).with_progress_target(progress_target.clone());
+        let results = silent.download_files(vec![&file]).await.unwrap();
+        let single = silent.download_file(&file).await.unwrap();
+        assert!(results[0].success, "{:?}", results[0].error);
+        assert!(single.success, "{:?}", single.error);
+        assert_eq!(targets.load(Ordering::SeqCst), 0);
+        assert!(term.written.lock().unwrap().is_empty());
+
+        // The same downloads without `new_silent` do reach the sink
+        let console = FtpDownloader::new(provider, config).with_progress_target(progress_target);
+        console.download_files(vec![&file]).await.unwrap();
+        console.download_file(&file).await.unwrap();
+        assert_eq!(targets.load(Ordering::SeqCst), 2);
+        assert!(term.written.lock().unwrap().contains("RDSP2301.dbc"));
+    }
+
+    /// Provider listing a fixed set of files in any directory
+    struct ListingProvider {
+        names: Vec<&'static str>,
+    }
+
+    #[async_trait::async_trait]
+    impl FileSystemProvider for ListingProvider {
+        async fn list_directory(
+            &self,
+            path: &str,
+        ) -> Result<crate::models::directory::DirectoryContent, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(self
+                .names
+                .iter()
+                .map(|name| {
+                    let info = FileInfo::new(FileSize::from_bytes(name.len() as u64), ".dbc".to_string(), Utc::now());
+                    (name.to_string(), DirectoryEntry::File(File::new(path, name, info)))
+                })
+                .collect())
+        }
+
+        async fn exists(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        async fn is_directory(&self, _path: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "listing"
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_selection_skips_unlisted_files() {
+        // Serves every RETR with the file name as its contents
+        let server = MockFtpServer::start(|command, arg| match command {
+            "RETR" => Some(MockReply::data(arg.as_bytes())),
+            _ => None,
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DownloadConfig {
+            output_dir: temp_dir.path().to_string_lossy().to_string(),
+            layout: LocalLayout::Flat,
+            overwrite: OverwritePolicy::Always,
+            max_retries: 0,
+            ..DownloadConfig::default()
+        };
+        let ftp = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let downloader = FtpDownloader::new_silent(ftp, config);
+        let listing = ListingProvider {
+            names: vec!["RDSP1811.dbc", "RDRJ1901.DBC", "RDSP1901.dbc", "PASP1812.dbc"],
+        };
+
+        let results = download_selection(
+            &listing, "/data", "rd", &["SP", "rj"], Period::YearMonth(2018, 11)..Period::YearMonth(2019, 2), &downloader,
+        )
+        .await
+        .unwrap();
+
+        let outcome: Vec<(&str, bool)> = results
+            .iter()
+            .map(|result| (result.ftp_path.rsplit('/').next().unwrap(), result.success))
+            .collect();
+        assert_eq!(
+            outcome,
+            [
+                ("RDSP1811.dbc", true),
+                ("RDSP1812.dbc", false),
+                ("RDSP1901.dbc", true),
+                ("RDRJ1811.dbc", false),
+                ("RDRJ1812.dbc", false),
+                ("RDRJ1901.DBC", true),
+            ]
+        );
+        assert_eq!(results[1].error.as_deref(), Some("Not found on the server"));
+        assert_eq!(std::fs::read(temp_dir.path().join("RDRJ1901.DBC")).unwrap(), b"RDRJ1901.DBC");
+
+        let invalid = download_selection(&listing, "/data", "RD", &["XX"], Period::Year(2018)..Period::Year(2019), &downloader).await;
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_policies_on_existing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let remote = |size: u64, modify: chrono::DateTime<Utc>| {
+            File::new("/data", "RDAC2001.dbc", FileInfo::new(FileSize::from_bytes(size), ".dbc".to_string(), modify))
+        };
+        let downloader_with = |overwrite: OverwritePolicy| {
+            FtpDownloader::new_silent(FtpFileSystemProvider::new_datasus(), DownloadConfig {
+                output_dir: temp_dir.path().to_string_lossy().to_string(),
+                layout: LocalLayout::Flat,
+                overwrite,
+                ..DownloadConfig::default()
+            })
+        };
+
+        let older = Utc::now() - chrono::Duration::days(30);
+        let newer = Utc::now() + chrono::Duration::days(30);
+
+        // Nothing on disk yet: every policy downloads
+        let local_path = temp_dir.path().join("RDAC2001.dbc");
+        assert!(downloader_with(OverwritePolicy::Never).overwrite_decision(&remote(4, older), &local_path).await.is_none());
+
+        std::fs::write(&local_path, b"data").unwrap();
+
+        let result = downloader_with(OverwritePolicy::Never).download_file(&remote(4, older)).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("File exists and overwrite is disabled"));
+        assert_eq!(result.overwrite_reason, result.error);
+
+        let decide = |overwrite: OverwritePolicy, file: File| {
+            let downloader = downloader_with(overwrite);
+            let local_path = local_path.clone();
+            async move { downloader.overwrite_decision(&file, &local_path).await.unwrap().0 }
+        };
+        assert!(decide(OverwritePolicy::Always, remote(4, older)).await);
+        assert!(!decide(OverwritePolicy::IfNewer, remote(4, older)).await);
+        assert!(decide(OverwritePolicy::IfNewer, remote(4, newer)).await);
+        assert!(!decide(OverwritePolicy::IfDifferentSize, remote(4, newer)).await);
+        assert!(decide(OverwritePolicy::IfDifferentSize, remote(5, older)).await);
+
+        let result = downloader_with(OverwritePolicy::IfNewer).download_file(&remote(4, older)).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.overwrite_reason.as_deref(), Some("Local copy is as recent as the server file"));
+        assert_eq!(std::fs::read(&local_path).unwrap(), b"data");
+    }
+
+    #[test]
+    fn test_overwrite_accepts_legacy_bool() {
+        let mut value = serde_json::to_value(DownloadConfig::default()).unwrap();
+        value["overwrite"] = serde_json::json!(true);
+        let config: DownloadConfig = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(config.overwrite, OverwritePolicy::Always);
+
+        value["overwrite"] = serde_json::json!("IfNewer");
+        let config: DownloadConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.overwrite, OverwritePolicy::IfNewer);
+    }
+
     #[tokio::test]
     async fn test_datasus_with_cache_constructor() {
         let downloader = FtpDownloader::new_datasus_with_cache().await;
@@ -712,7 +1729,7 @@ mod tests {
         assert!(downloader.config.preserve_structure);
         assert_eq!(downloader.config.max_concurrent, 4);
         assert_eq!(downloader.config.buffer_size, 8192);
-        assert!(!downloader.config.overwrite);
+        assert_eq!(downloader.config.overwrite, OverwritePolicy::Never);
     }
 
     #[tokio::test]
@@ -773,7 +1790,10 @@ mod tests {
             preserve_structure: false,
             max_concurrent: 1,
             buffer_size: 4096,
-            overwrite: true,
+            overwrite: OverwritePolicy::Always,
+            layout: LocalLayout::MirrorFtp,
+            max_retries: 0,
+            verify_readable: false,
         };
 
         let downloader = FtpDownloader::new_datasus().with_config(config);
@@ -866,7 +1886,10 @@ mod tests {
             preserve_structure: false,
             max_concurrent: 1,
             buffer_size: 16384, // Larger buffer for big file
-            overwrite: true,
+            overwrite: OverwritePolicy::Always,
+            layout: LocalLayout::MirrorFtp,
+            max_retries: 0,
+            verify_readable: false,
         };
 
         let downloader = FtpDownloader::new_datasus()
@@ -988,7 +2011,10 @@ mod tests {
             preserve_structure: false,
             max_concurrent: 2, // Download 2 files at once
             buffer_size: 16384, // Larger buffer for big files
-            overwrite: true,
+            overwrite: OverwritePolicy::Always,
+            layout: LocalLayout::MirrorFtp,
+            max_retries: 0,
+            verify_readable: false,
         };
 
         let downloader = FtpDownloader::new_datasus().with_config(config);
@@ -1,8 +1,10 @@
+use crate::models::file_extension::{FileExtension, find_extension};
 use crate::models::file_info::{FileInfo, format_bytes_human};
+use crate::models::ftp_path::FtpPath;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::{fmt, hash};
 
 /// FTP File representation with improved type safety.
@@ -55,24 +57,14 @@ impl File {
         };
 
         let basename = name.to_string();
-        let full_path = if path.ends_with('/') {
-            format!("{}{}", path, basename)
-        } else {
-            format!("{}/{}", path, basename)
-        };
-
-        let parent_path = {
-            let path_buf = PathBuf::from(&full_path);
-            path_buf.parent()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| path.to_string())
-        };
+        let full_path = FtpPath::new(path).join(&basename);
+        let parent_path = full_path.parent().to_string();
 
         Self {
             name: file_name,
             extension,
             basename,
-            path: full_path,
+            path: full_path.to_string(),
             parent_path,
             info,
         }
@@ -131,6 +123,11 @@ impl File {
         self.extension.eq_ignore_ascii_case(&ext_with_dot)
     }
 
+    /// Look up registry metadata (MIME type, category) for this file's extension
+    pub fn extension_info(&self) -> Option<&'static FileExtension> {
+        find_extension(&self.extension)
+    }
+
     /// Check if this is a large file (> 100MB by default)
     pub fn is_large(&self, threshold_bytes: Option<u64>) -> bool {
         self.info.is_large_file(threshold_bytes)
@@ -182,6 +179,12 @@ mod tests {
         assert_eq!(file.extension, ".txt");
         assert_eq!(file.basename, "test.txt");
         assert_eq!(file.path, "/home/user/test.txt");
+        assert_eq!(file.parent_path, "/home/user");
+
+        // Stray slashes in the directory don't leak into the path
+        let file = File::new("//SIHSUS/Dados/", "RDSP2301.dbc", file.info.clone());
+        assert_eq!(file.path, "/SIHSUS/Dados/RDSP2301.dbc");
+        assert_eq!(file.parent_path, "/SIHSUS/Dados");
     }
 
     #[test]
@@ -252,4 +255,30 @@ mod tests {
         assert!(file.has_extension("TXT"));
         assert!(!file.has_extension("csv"));
     }
+
+    #[test]
+    fn test_has_extension_mixed_case() {
+        for name in ["RDSP2012.dbc", "RDSP2012.DBC", "RDSP2012.Dbc"] {
+            let info = FileInfo::new(FileSize::from_bytes(1024), ".dbc".to_string(), Utc::now());
+            let file = File::new("/SIHSUS/200801_/Dados", name, info);
+
+            assert!(file.has_extension("dbc"), "{} should match dbc", name);
+            assert!(file.has_extension(".DBC"), "{} should match .DBC", name);
+            assert!(file.has_extension("Dbc"), "{} should match Dbc", name);
+            assert!(!file.has_extension("dbf"), "{} should not match dbf", name);
+        }
+    }
+
+    #[test]
+    fn test_extension_info() {
+        use crate::models::file_extension::ExtensionCategory;
+
+        let info = FileInfo::new(FileSize::from_bytes(1024), ".DBC".to_string(), Utc::now());
+        let file = File::new("/SIHSUS/200801_/Dados", "RDSP2012.DBC", info);
+        assert_eq!(file.extension_info().unwrap().category, ExtensionCategory::Compressed);
+
+        let info = FileInfo::new(FileSize::from_bytes(10), String::new(), Utc::now());
+        let file = File::new("/", "README", info);
+        assert!(file.extension_info().is_none());
+    }
 }
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use once_cell::sync::Lazy;
+
+/// Broad category of a file found on the DATASUS servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtensionCategory {
+    /// Compressed data (e.g. DBC, ZIP)
+    Compressed,
+    /// Tabular data (e.g. DBF, CSV)
+    Tabular,
+    /// Documentation and auxiliary files (e.g. PDF, TabWin definitions)
+    Documentation,
+    /// Anything else (executables, installers)
+    Other,
+}
+
+/// Metadata about a known file extension.
+/// 
+/// # Fields
+/// * `extension` - Lowercase extension without the leading dot
+/// * `mime_type` - MIME type used when serving or uploading the file
+/// * `description` - Human-readable description
+/// * `category` - Broad file category
+/// 
+/// # Example
+/// ```rust
+/// use shared::models::file_extension::{find_extension, ExtensionCategory};
+/// 
+/// let dbc = find_extension(".DBC").unwrap();
+/// assert_eq!(dbc.extension, "dbc");
+/// assert_eq!(dbc.category, ExtensionCategory::Compressed);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileExtension {
+    pub extension: &'static str,
+    pub mime_type: &'static str,
+    pub description: &'static str,
+    pub category: ExtensionCategory,
+}
+
+impl FileExtension {
+    pub const fn new(
+        extension: &'static str,
+        mime_type: &'static str,
+        description: &'static str,
+        category: ExtensionCategory,
+    ) -> Self {
+        Self {
+            extension,
+            mime_type,
+            description,
+            category,
+        }
+    }
+}
+
+/// Mapping of lowercase extensions to their metadata.
+pub static FILE_EXTENSIONS: Lazy<HashMap<&'static str, FileExtension>> = Lazy::new(|| {
+    use ExtensionCategory::*;
+
+    let mut extensions = HashMap::new();
+    extensions.insert("dbc", FileExtension::new("dbc", "application/octet-stream", "Compressed dBase file (DATASUS)", Compressed));
+    extensions.insert("dbf", FileExtension::new("dbf", "application/x-dbf", "dBase table", Tabular));
+    extensions.insert("csv", FileExtension::new("csv", "text/csv", "Comma-separated values", Tabular));
+    extensions.insert("xls", FileExtension::new("xls", "application/vnd.ms-excel", "Excel spreadsheet", Tabular));
+    extensions.insert("xlsx", FileExtension::new("xlsx", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "Excel spreadsheet", Tabular));
+    extensions.insert("zip", FileExtension::new("zip", "application/zip", "ZIP archive", Compressed));
+    extensions.insert("rar", FileExtension::new("rar", "application/vnd.rar", "RAR archive", Compressed));
+    extensions.insert("pdf", FileExtension::new("pdf", "application/pdf", "PDF document", Documentation));
+    extensions.insert("doc", FileExtension::new("doc", "application/msword", "Word document", Documentation));
+    extensions.insert("txt", FileExtension::new("txt", "text/plain", "Plain text", Documentation));
+    extensions.insert("cnv", FileExtension::new("cnv", "text/plain", "TabWin conversion table", Documentation));
+    extensions.insert("def", FileExtension::new("def", "text/plain", "TabWin definition file", Documentation));
+    extensions.insert("exe", FileExtension::new("exe", "application/vnd.microsoft.portable-executable", "Windows executable", Other));
+    extensions
+});
+
+/// Get extension metadata, ignoring case and an optional leading dot.
+/// 
+/// # Example
+/// ```rust
+/// use shared::models::file_extension::find_extension;
+/// 
+/// assert_eq!(find_extension("dbf").unwrap().mime_type, "application/x-dbf");
+/// assert!(find_extension("unknown").is_none());
+/// ```
+pub fn find_extension(ext: &str) -> Option<&'static FileExtension> {
+    let normalized = ext.trim_start_matches('.').to_lowercase();
+    FILE_EXTENSIONS.get(normalized.as_str())
+}
+
+/// Whether the extension holds DATASUS microdata (`dbc` or `dbf`).
+pub fn is_datasus_data(ext: &str) -> bool {
+    matches!(
+        find_extension(ext).map(|e| e.extension),
+        Some("dbc") | Some("dbf")
+    )
+}
+
+/// Get all known extensions in a category.
+pub fn extensions_by_category(category: ExtensionCategory) -> Vec<&'static FileExtension> {
+    let mut extensions: Vec<&'static FileExtension> = FILE_EXTENSIONS
+        .values()
+        .filter(|e| e.category == category)
+        .collect();
+    extensions.sort_by_key(|e| e.extension);
+    extensions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dbc_is_compressed_datasus_data() {
+        let dbc = find_extension("dbc").unwrap();
+        assert_eq!(dbc.category, ExtensionCategory::Compressed);
+        assert!(is_datasus_data("dbc"));
+    }
+
+    #[test]
+    fn test_find_extension_normalizes_input() {
+        assert_eq!(find_extension(".DBF").unwrap().extension, "dbf");
+        assert_eq!(find_extension("Csv").unwrap().category, ExtensionCategory::Tabular);
+        assert!(find_extension("").is_none());
+        assert!(find_extension("xyz").is_none());
+    }
+
+    #[test]
+    fn test_is_datasus_data() {
+        assert!(is_datasus_data(".DBF"));
+        assert!(is_datasus_data("Dbc"));
+        assert!(!is_datasus_data("csv"));
+        assert!(!is_datasus_data("pdf"));
+        assert!(!is_datasus_data("unknown"));
+    }
+
+    #[test]
+    fn test_extensions_by_category() {
+        let docs = extensions_by_category(ExtensionCategory::Documentation);
+        assert!(docs.iter().any(|e| e.extension == "pdf"));
+        assert!(docs.iter().all(|e| e.category == ExtensionCategory::Documentation));
+
+        let compressed: Vec<_> = extensions_by_category(ExtensionCategory::Compressed)
+            .iter()
+            .map(|e| e.extension)
+            .collect();
+        assert_eq!(compressed, vec!["dbc", "rar", "zip"]);
+    }
+
+    #[test]
+    fn test_registry_keys_match_extensions() {
+        for (key, ext) in FILE_EXTENSIONS.iter() {
+            assert_eq!(*key, ext.extension);
+            assert_eq!(ext.extension, ext.extension.to_lowercase());
+        }
+    }
+}
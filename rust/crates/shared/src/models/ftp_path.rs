@@ -0,0 +1,146 @@
+//! Normalized absolute paths on an FTP server
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An absolute FTP path with a single leading slash, single slashes between
+/// segments and no trailing slash (the root is `/`)
+///
+/// Joining always yields a normalized path, so a base path and a relative
+/// path can be combined without worrying about `//` or missing slashes.
+///
+/// # Examples
+/// ```
+/// use shared::models::ftp_path::FtpPath;
+///
+/// let base = FtpPath::new("/dissemin/publicos/");
+/// assert_eq!(base.join("/SIHSUS/200801_/Dados").as_str(), "/dissemin/publicos/SIHSUS/200801_/Dados");
+/// assert_eq!(base.join("SIHSUS").as_str(), "/dissemin/publicos/SIHSUS");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FtpPath(String);
+
+impl FtpPath {
+    /// Normalize `path`, treating it as absolute whether or not it starts with `/`
+    pub fn new(path: &str) -> Self {
+        Self::root().join(path)
+    }
+
+    /// The server root, `/`
+    pub fn root() -> Self {
+        Self("/".to_string())
+    }
+
+    /// Append `path` below this one; a leading slash on `path` doesn't make it absolute
+    pub fn join(&self, path: &str) -> Self {
+        let mut joined = self.0.trim_end_matches('/').to_string();
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            joined.push('/');
+            joined.push_str(segment);
+        }
+        if joined.is_empty() {
+            joined.push('/');
+        }
+        Self(joined)
+    }
+
+    /// The path as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is the root
+    pub fn is_root(&self) -> bool {
+        self.0 == "/"
+    }
+
+    /// Last segment, or `None` for the root
+    pub fn file_name(&self) -> Option<&str> {
+        self.0.rsplit('/').next().filter(|name| !name.is_empty())
+    }
+
+    /// Containing directory (the root is its own parent)
+    pub fn parent(&self) -> Self {
+        match self.0.rsplit_once('/') {
+            Some((parent, _)) if !parent.is_empty() => Self(parent.to_string()),
+            _ => Self::root(),
+        }
+    }
+
+    /// The part of this path below `base`, without a leading slash
+    ///
+    /// Matches whole segments: `/pub` is a prefix of `/pub/data` but not of `/public`.
+    pub fn strip_prefix(&self, base: &FtpPath) -> Option<&str> {
+        if base.is_root() {
+            return Some(self.0.trim_start_matches('/'));
+        }
+        match self.0.strip_prefix(base.as_str())? {
+            "" => Some(""),
+            rest => rest.strip_prefix('/'),
+        }
+    }
+}
+
+impl Default for FtpPath {
+    fn default() -> Self {
+        Self::root()
+    }
+}
+
+impl fmt::Display for FtpPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for FtpPath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for FtpPath {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_normalizes_slashes() {
+        assert_eq!(FtpPath::new("/dissemin/publicos/").as_str(), "/dissemin/publicos");
+        assert_eq!(FtpPath::new("dissemin//publicos").as_str(), "/dissemin/publicos");
+        assert_eq!(FtpPath::new("").as_str(), "/");
+        assert_eq!(FtpPath::new("///").as_str(), "/");
+    }
+
+    #[test]
+    fn test_join_absolute_and_relative() {
+        let base = FtpPath::new("/dissemin/publicos");
+        assert_eq!(base.join("/SIHSUS").as_str(), "/dissemin/publicos/SIHSUS");
+        assert_eq!(base.join("SIHSUS/").as_str(), "/dissemin/publicos/SIHSUS");
+        assert_eq!(base.join("").as_str(), "/dissemin/publicos");
+
+        // A root base path must not produce `//`
+        assert_eq!(FtpPath::root().join("/SIHSUS/Dados").as_str(), "/SIHSUS/Dados");
+        assert_eq!(FtpPath::new("/").join("/").as_str(), "/");
+    }
+
+    #[test]
+    fn test_parent_file_name_and_strip_prefix() {
+        let path = FtpPath::new("/pub/data/RDSP2301.dbc");
+        assert_eq!(path.file_name(), Some("RDSP2301.dbc"));
+        assert_eq!(path.parent().as_str(), "/pub/data");
+        assert_eq!(FtpPath::new("/pub").parent(), FtpPath::root());
+        assert_eq!(FtpPath::root().parent(), FtpPath::root());
+        assert_eq!(FtpPath::root().file_name(), None);
+
+        assert_eq!(path.strip_prefix(&FtpPath::new("/pub/")), Some("data/RDSP2301.dbc"));
+        assert_eq!(path.strip_prefix(&FtpPath::root()), Some("pub/data/RDSP2301.dbc"));
+        assert_eq!(FtpPath::new("/public/x").strip_prefix(&FtpPath::new("/pub")), None);
+        assert_eq!(FtpPath::new("/pub").strip_prefix(&FtpPath::new("/pub")), Some(""));
+    }
+}
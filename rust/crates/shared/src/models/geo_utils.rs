@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
+use crate::models::utils::closest_match;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StateFormatError {
@@ -115,6 +116,64 @@ pub static UFS: Lazy<HashMap<&'static str, StateBR>> = Lazy::new(|| {
     ufs
 });
 
+/// Approximate geographic centroid (latitude, longitude) of each UF.
+pub static UF_CENTROIDS: Lazy<HashMap<&'static str, (f64, f64)>> = Lazy::new(|| {
+    let mut centroids = HashMap::new();
+    centroids.insert("RO", (-10.83, -63.34));
+    centroids.insert("AC", (-9.02, -70.81));
+    centroids.insert("AM", (-3.47, -65.10));
+    centroids.insert("RR", (2.05, -61.40));
+    centroids.insert("PA", (-3.79, -52.48));
+    centroids.insert("AP", (1.41, -51.77));
+    centroids.insert("TO", (-10.18, -48.33));
+    centroids.insert("MA", (-5.42, -45.44));
+    centroids.insert("PI", (-7.72, -42.73));
+    centroids.insert("CE", (-5.20, -39.53));
+    centroids.insert("RN", (-5.81, -36.59));
+    centroids.insert("PB", (-7.12, -36.72));
+    centroids.insert("PE", (-8.38, -37.86));
+    centroids.insert("AL", (-9.62, -36.55));
+    centroids.insert("SE", (-10.57, -37.45));
+    centroids.insert("BA", (-12.58, -41.70));
+    centroids.insert("MG", (-18.51, -44.56));
+    centroids.insert("ES", (-19.57, -40.67));
+    centroids.insert("RJ", (-22.25, -42.66));
+    centroids.insert("SP", (-22.19, -48.79));
+    centroids.insert("PR", (-24.89, -51.55));
+    centroids.insert("SC", (-27.45, -50.95));
+    centroids.insert("RS", (-29.75, -53.25));
+    centroids.insert("MS", (-20.51, -54.54));
+    centroids.insert("MT", (-12.64, -55.42));
+    centroids.insert("GO", (-15.98, -49.86));
+    centroids.insert("DF", (-15.78, -47.80));
+    centroids
+});
+
+/// Get the approximate centroid of a UF as `(latitude, longitude)`.
+/// 
+/// Good enough for placing markers or prototyping choropleths, not for
+/// precise geometry.
+/// 
+/// # Arguments
+/// * `uf` - State abbreviation (case insensitive)
+/// 
+/// # Returns
+/// * `Some((f64, f64))` - Latitude and longitude in degrees
+/// * `None` - If the UF is not in `UFS`
+/// 
+/// # Example
+/// ```rust
+/// use shared::models::geo_utils::uf_centroid;
+/// 
+/// let (lat, lon) = uf_centroid("sp").unwrap();
+/// assert!(lat < 0.0 && lon < 0.0);
+/// assert!(uf_centroid("XX").is_none());
+/// ```
+pub fn uf_centroid(uf: &str) -> Option<(f64, f64)> {
+    let state = get_state_info(uf).ok()?;
+    UF_CENTROIDS.get(state.uf.as_str()).copied()
+}
+
 /// Get state information by UF abbreviation.
 /// 
 /// # Arguments
@@ -138,7 +197,28 @@ pub fn get_state_info(uf: &str) -> Result<StateBR, StateNotFoundError> {
     let uf_upper = uf.to_uppercase();
     UFS.get(uf_upper.as_str())
         .cloned()
-        .ok_or_else(|| StateNotFoundError::new(uf, "UF não encontrada"))
+        .ok_or_else(|| match suggest_uf(uf) {
+            Some(suggestion) => StateNotFoundError::new(uf, &format!("UF não encontrada (você quis dizer '{}'?)", suggestion)),
+            None => StateNotFoundError::new(uf, "UF não encontrada"),
+        })
+}
+
+/// Suggest the UF closest to a mistyped `uf`.
+/// 
+/// Only one edit is allowed: every two-letter code is within two edits of
+/// every other, so a looser threshold would suggest arbitrary states.
+/// 
+/// # Example
+/// ```rust
+/// use shared::models::geo_utils::suggest_uf;
+/// 
+/// assert_eq!(suggest_uf("SPP"), Some("SP"));
+/// assert_eq!(suggest_uf("XX"), None);
+/// ```
+pub fn suggest_uf(uf: &str) -> Option<&'static str> {
+    let mut ufs: Vec<&'static str> = UFS.keys().copied().collect();
+    ufs.sort_unstable();
+    closest_match(uf, ufs, 1)
 }
 
 /// Async version of get_state_info
@@ -287,6 +367,21 @@ mod tests {
         assert_eq!(rj.name, "Rio de Janeiro");
     }
     
+    #[test]
+    fn test_uf_centroid() {
+        // São Paulo state spans roughly 19.8°S-25.3°S and 44.2°W-53.1°W
+        let (lat, lon) = uf_centroid("SP").unwrap();
+        assert!((-25.3..=-19.8).contains(&lat));
+        assert!((-53.1..=-44.2).contains(&lon));
+
+        assert_eq!(uf_centroid("sp"), uf_centroid("SP"));
+        assert!(uf_centroid("XX").is_none());
+
+        // Every UF has a centroid
+        assert_eq!(UF_CENTROIDS.len(), UFS.len());
+        assert!(UFS.keys().all(|uf| uf_centroid(uf).is_some()));
+    }
+    
     #[test]
     fn test_get_state_info() {
         let sp = get_state_info("SP").unwrap();
@@ -298,6 +393,19 @@ mod tests {
         
         // Test invalid UF
         assert!(get_state_info("XX").is_err());
+        
+        // Test typo suggestion
+        let error = get_state_info("SPP").unwrap_err();
+        assert!(error.message.contains("'SP'"));
+    }
+    
+    #[test]
+    fn test_suggest_uf() {
+        assert_eq!(suggest_uf("SPP"), Some("SP"));
+        assert_eq!(suggest_uf("dff"), Some("DF"));
+        assert_eq!(suggest_uf("XX"), None);
+        // "S" is one edit from SP, SC and SE
+        assert_eq!(suggest_uf("S"), None);
     }
     
     #[test]
@@ -1,4 +1,5 @@
-use arrow::datatypes::Schema;
+use arrow::datatypes::{DataType as ArrowDataType, Field, Schema, TimeUnit as ArrowTimeUnit};
+use polars::prelude::{DataFrame, DataType as PlDataType, Schema as PlSchema, TimeUnit as PlTimeUnit};
 use std::sync::Arc;
 
 /// Information about a DATASUS data group.
@@ -81,6 +82,153 @@ impl PartialEq for GroupInfo {
 
 impl Eq for GroupInfo {}
 
+/// Convert a Polars dtype to the Arrow dtype used in `GroupInfo` schemas
+///
+/// Categoricals become `Dictionary(UInt32, Utf8)`; dtypes without a DATASUS
+/// use fall back to `Utf8`.
+pub fn polars_dtype_to_arrow(dtype: &PlDataType) -> ArrowDataType {
+    match dtype {
+        PlDataType::Boolean => ArrowDataType::Boolean,
+        PlDataType::Int8 => ArrowDataType::Int8,
+        PlDataType::Int16 => ArrowDataType::Int16,
+        PlDataType::Int32 => ArrowDataType::Int32,
+        PlDataType::Int64 => ArrowDataType::Int64,
+        PlDataType::UInt8 => ArrowDataType::UInt8,
+        PlDataType::UInt16 => ArrowDataType::UInt16,
+        PlDataType::UInt32 => ArrowDataType::UInt32,
+        PlDataType::UInt64 => ArrowDataType::UInt64,
+        PlDataType::Float32 => ArrowDataType::Float32,
+        PlDataType::Float64 => ArrowDataType::Float64,
+        PlDataType::String => ArrowDataType::Utf8,
+        PlDataType::Binary => ArrowDataType::Binary,
+        PlDataType::Date => ArrowDataType::Date32,
+        PlDataType::Time => ArrowDataType::Time64(ArrowTimeUnit::Nanosecond),
+        PlDataType::Datetime(unit, _) => {
+            let unit = match unit {
+                PlTimeUnit::Milliseconds => ArrowTimeUnit::Millisecond,
+                PlTimeUnit::Microseconds => ArrowTimeUnit::Microsecond,
+                PlTimeUnit::Nanoseconds => ArrowTimeUnit::Nanosecond,
+            };
+            ArrowDataType::Timestamp(unit, None)
+        }
+        PlDataType::Decimal(precision, scale) => {
+            ArrowDataType::Decimal128(precision.unwrap_or(38) as u8, scale.unwrap_or(0) as i8)
+        }
+        PlDataType::Categorical(..) | PlDataType::Enum(..) => {
+            ArrowDataType::Dictionary(Box::new(ArrowDataType::UInt32), Box::new(ArrowDataType::Utf8))
+        }
+        _ => ArrowDataType::Utf8,
+    }
+}
+
+/// Convert an Arrow dtype to Polars, the inverse of [`polars_dtype_to_arrow`]
+pub fn arrow_dtype_to_polars(dtype: &ArrowDataType) -> PlDataType {
+    match dtype {
+        ArrowDataType::Boolean => PlDataType::Boolean,
+        ArrowDataType::Int8 => PlDataType::Int8,
+        ArrowDataType::Int16 => PlDataType::Int16,
+        ArrowDataType::Int32 => PlDataType::Int32,
+        ArrowDataType::Int64 => PlDataType::Int64,
+        ArrowDataType::UInt8 => PlDataType::UInt8,
+        ArrowDataType::UInt16 => PlDataType::UInt16,
+        ArrowDataType::UInt32 => PlDataType::UInt32,
+        ArrowDataType::UInt64 => PlDataType::UInt64,
+        ArrowDataType::Float16 | ArrowDataType::Float32 => PlDataType::Float32,
+        ArrowDataType::Float64 => PlDataType::Float64,
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => PlDataType::String,
+        ArrowDataType::Binary | ArrowDataType::LargeBinary => PlDataType::Binary,
+        ArrowDataType::Date32 | ArrowDataType::Date64 => PlDataType::Date,
+        ArrowDataType::Time32(_) | ArrowDataType::Time64(_) => PlDataType::Time,
+        ArrowDataType::Timestamp(unit, _) => {
+            let unit = match unit {
+                ArrowTimeUnit::Second | ArrowTimeUnit::Millisecond => PlTimeUnit::Milliseconds,
+                ArrowTimeUnit::Microsecond => PlTimeUnit::Microseconds,
+                ArrowTimeUnit::Nanosecond => PlTimeUnit::Nanoseconds,
+            };
+            PlDataType::Datetime(unit, None)
+        }
+        ArrowDataType::Decimal128(precision, scale) => {
+            PlDataType::Decimal(Some(*precision as usize), Some(*scale as usize))
+        }
+        ArrowDataType::Dictionary(_, value) if matches!(value.as_ref(), ArrowDataType::Utf8 | ArrowDataType::LargeUtf8) => {
+            PlDataType::from_categories(polars::prelude::Categories::global())
+        }
+        _ => PlDataType::String,
+    }
+}
+
+/// Convert a Polars schema to an Arrow schema (all fields nullable, as DBF values can be)
+pub fn arrow_schema_from_polars(schema: &PlSchema) -> Arc<Schema> {
+    let fields: Vec<Field> = schema
+        .iter()
+        .map(|(name, dtype)| Field::new(name.as_str(), polars_dtype_to_arrow(dtype), true))
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+/// Convert an Arrow schema to a Polars schema
+pub fn polars_schema_from_arrow(schema: &Schema) -> PlSchema {
+    schema
+        .fields()
+        .iter()
+        .map(|field| (field.name().as_str().into(), arrow_dtype_to_polars(field.data_type())))
+        .collect()
+}
+
+/// 64-bit FNV-1a: fixed constants, so fingerprints are identical across runs and machines
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Write a length-prefixed field so ("ab", "c") and ("a", "bc") differ
+    fn write_str(&mut self, value: &str) {
+        self.write(&(value.len() as u64).to_le_bytes());
+        self.write(value.as_bytes());
+    }
+}
+
+/// Stable hash of a Polars schema's ordered (name, dtype) pairs
+///
+/// Column order matters. The value is stable across process runs, so it can
+/// key on-disk caches; it may change when Polars renames a dtype.
+pub fn polars_schema_fingerprint(schema: &PlSchema) -> u64 {
+    let mut hasher = Fnv1a::new();
+    for (name, dtype) in schema.iter() {
+        hasher.write_str(name.as_str());
+        hasher.write_str(&dtype.to_string());
+    }
+    hasher.0
+}
+
+/// Stable hash of a DataFrame's schema, see [`polars_schema_fingerprint`]
+pub fn schema_fingerprint(df: &DataFrame) -> u64 {
+    polars_schema_fingerprint(df.schema())
+}
+
+/// Stable hash of an Arrow schema's ordered (name, dtype, nullable) fields
+pub fn arrow_schema_fingerprint(schema: &Schema) -> u64 {
+    let mut hasher = Fnv1a::new();
+    for field in schema.fields() {
+        hasher.write_str(field.name());
+        hasher.write_str(&field.data_type().to_string());
+        hasher.write(&[u8::from(field.is_nullable())]);
+    }
+    hasher.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +344,73 @@ mod tests {
         
         assert_eq!(group.schema_metadata(), &metadata);
     }
+
+    #[test]
+    fn test_polars_arrow_schema_round_trip() {
+        let polars_schema: PlSchema = [
+            ("MUNIC_RES".into(), PlDataType::String),
+            ("IDADE".into(), PlDataType::Int16),
+            ("N_AIH".into(), PlDataType::Int64),
+            ("DIAS_PERM".into(), PlDataType::Int32),
+            ("VAL_TOT".into(), PlDataType::Float64),
+            ("DT_INTER".into(), PlDataType::Date),
+            ("MORTE".into(), PlDataType::Boolean),
+            ("DT_PROC".into(), PlDataType::Datetime(PlTimeUnit::Milliseconds, None)),
+        ].into_iter().collect();
+
+        let arrow_schema = arrow_schema_from_polars(&polars_schema);
+        assert_eq!(arrow_schema.field_with_name("MUNIC_RES").unwrap().data_type(), &DataType::Utf8);
+        assert_eq!(arrow_schema.field_with_name("DT_INTER").unwrap().data_type(), &DataType::Date32);
+        assert_eq!(polars_schema_from_arrow(&arrow_schema), polars_schema);
+    }
+
+    #[test]
+    fn test_categorical_maps_to_dictionary() {
+        let categorical = PlDataType::from_categories(polars::prelude::Categories::global());
+        let arrow_dtype = polars_dtype_to_arrow(&categorical);
+        assert_eq!(arrow_dtype, DataType::Dictionary(Box::new(DataType::UInt32), Box::new(DataType::Utf8)));
+        assert!(matches!(arrow_dtype_to_polars(&arrow_dtype), PlDataType::Categorical(..)));
+    }
+
+    #[test]
+    fn test_arrow_group_schema_round_trip() {
+        let schema = create_test_schema();
+        let round_tripped = arrow_schema_from_polars(&polars_schema_from_arrow(&schema));
+        let names: Vec<&str> = round_tripped.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, vec!["id", "name", "value"]);
+        for (original, converted) in schema.fields().iter().zip(round_tripped.fields().iter()) {
+            assert_eq!(original.data_type(), converted.data_type());
+        }
+    }
+
+    #[test]
+    fn test_schema_fingerprints() {
+        use polars::prelude::{Column, DataFrame};
+
+        let df = DataFrame::new(vec![
+            Column::new("id".into(), [1i64, 2]),
+            Column::new("uf".into(), ["SP", "RJ"]),
+        ]).unwrap();
+        let same = DataFrame::new(vec![
+            Column::new("id".into(), [3i64]),
+            Column::new("uf".into(), ["MG"]),
+        ]).unwrap();
+        let reordered = df.select(["uf", "id"]).unwrap();
+
+        assert_eq!(schema_fingerprint(&df), schema_fingerprint(&same));
+        assert_ne!(schema_fingerprint(&df), schema_fingerprint(&reordered));
+
+        let arrow = create_test_schema();
+        let reordered_arrow = Schema::new(vec![
+            arrow.field(1).clone(),
+            arrow.field(0).clone(),
+            arrow.field(2).clone(),
+        ]);
+        assert_eq!(arrow_schema_fingerprint(&arrow), arrow_schema_fingerprint(&create_test_schema()));
+        assert_ne!(arrow_schema_fingerprint(&arrow), arrow_schema_fingerprint(&reordered_arrow));
+
+        // Unseeded: an empty schema hashes to the FNV-1a offset basis in every run
+        assert_eq!(arrow_schema_fingerprint(&Schema::empty()), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(polars_schema_fingerprint(&PlSchema::default()), 0xcbf2_9ce4_8422_2325);
+    }
 }
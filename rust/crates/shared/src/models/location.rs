@@ -0,0 +1,157 @@
+//! One entry point for reading DBC/DBF files whether they are local or on FTP
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use polars::prelude::DataFrame;
+
+use crate::models::directory::FtpFileSystemProvider;
+use crate::models::dbase_utils::is_dbc_file;
+use crate::models::download::{DownloadConfig, FtpDownloader, LocalLayout, OverwritePolicy};
+use crate::models::file::File;
+use crate::models::file_info::{FileInfo, FileSize};
+use crate::models::polars_utils::dbase_pl::{DbcConfig, DbcScanner};
+
+/// Where a DBC or DBF file lives
+#[derive(Debug, Clone)]
+pub enum DataLocation {
+    /// A file on the local filesystem
+    Local(PathBuf),
+    /// A file on an FTP server, with `path` relative to the provider's base path
+    Ftp { provider: FtpFileSystemProvider, path: String },
+}
+
+impl DataLocation {
+    /// File name of the location, used to pick the DBC or DBF reader
+    pub fn file_name(&self) -> Option<&str> {
+        match self {
+            DataLocation::Local(path) => path.file_name().and_then(|name| name.to_str()),
+            DataLocation::Ftp { path, .. } => path.rsplit('/').next().filter(|name| !name.is_empty()),
+        }
+    }
+}
+
+/// Read a DBC (or `.dbf`) file into a DataFrame from wherever it lives
+///
+/// Local files go straight to the dbase readers; FTP files are first
+/// downloaded without any terminal output into a temporary directory.
+pub async fn read_dbase_location(location: &DataLocation, config: Option<DbcConfig>) -> Result<DataFrame> {
+    match location {
+        DataLocation::Local(path) => read_local_dbase(path.clone(), config).await,
+        DataLocation::Ftp { provider, path } => read_dbase_from_ftp(provider, path, config).await,
+    }
+}
+
+/// Download a DBC/DBF file from FTP into a temporary directory and read it
+pub async fn read_dbase_from_ftp(
+    provider: &FtpFileSystemProvider,
+    path: &str,
+    config: Option<DbcConfig>,
+) -> Result<DataFrame> {
+    let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+    if name.is_empty() {
+        return Err(anyhow!("FTP path {} does not name a file", path));
+    }
+    let extension = Path::new(name)
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let file = File::new(
+        if parent.is_empty() { "/" } else { parent },
+        name,
+        FileInfo::new(FileSize::from_bytes(0), extension, Utc::now()),
+    );
+
+    let temp_dir = tempfile::tempdir()?;
+    let download_config = DownloadConfig {
+        output_dir: temp_dir.path().to_string_lossy().to_string(),
+        layout: LocalLayout::Flat,
+        overwrite: OverwritePolicy::Always,
+        ..DownloadConfig::default()
+    };
+    let downloader = FtpDownloader::new_silent(provider.clone(), download_config);
+    let result = downloader.download_file(&file).await?;
+    if !result.success {
+        return Err(anyhow!(
+            "Failed to download {}: {}",
+            path,
+            result.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+
+    // The temporary directory must outlive the read
+    let df = read_local_dbase(PathBuf::from(result.local_path), config).await?;
+    drop(temp_dir);
+    Ok(df)
+}
+
+/// Read a local DBC or DBF file on the blocking thread pool
+async fn read_local_dbase(path: PathBuf, config: Option<DbcConfig>) -> Result<DataFrame> {
+    let is_dbf = !is_dbc_file(path.as_path());
+
+    let df = tokio::task::spawn_blocking(move || {
+        let scanner = if is_dbf {
+            DbcScanner::from_dbf_path(&path, config)?
+        } else {
+            DbcScanner::from_dbc_path(&path, config)?
+        };
+        scanner.read_all()
+    })
+    .await??;
+    Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::mock_ftp::{MockFtpServer, MockReply};
+    use crate::models::polars_utils::dbase_pl::fixtures;
+
+    #[test]
+    fn test_data_location_file_name() {
+        assert_eq!(DataLocation::Local(PathBuf::from("/tmp/RDSP2301.dbc")).file_name(), Some("RDSP2301.dbc"));
+        let ftp = DataLocation::Ftp {
+            provider: FtpFileSystemProvider::new_datasus(),
+            path: "/SIHSUS/200801_/Dados/RDSP2301.dbc".to_string(),
+        };
+        assert_eq!(ftp.file_name(), Some("RDSP2301.dbc"));
+    }
+
+    #[tokio::test]
+    async fn test_read_local_location() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let df = read_dbase_location(&DataLocation::Local(path.clone()), None).await.unwrap();
+        let expected = DbcScanner::from_dbf_path(&path, None).unwrap().read_all().unwrap();
+        assert!(df.equals_missing(&expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_read_ftp_location() {
+        let fixture_dir = tempfile::tempdir().unwrap();
+        let fixture_path = fixture_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&fixture_path);
+        let body = std::fs::read(&fixture_path).unwrap();
+
+        // Serves basic.dbf from /pub/data
+        let server = MockFtpServer::start(move |command, arg| match (command, arg) {
+            ("CWD", "/pub/data") => None,
+            ("CWD", _) => Some(MockReply::line("550 not found")),
+            ("RETR", "basic.dbf") => Some(MockReply::data(body.clone())),
+            ("RETR", _) => Some(MockReply::line("550 not found")),
+            _ => None,
+        })
+        .await;
+
+        let provider = FtpFileSystemProvider::new("127.0.0.1".to_string(), "/pub".to_string(), Some(server.port()))
+            .with_operation_timeout(std::time::Duration::from_secs(5));
+        let location = DataLocation::Ftp { provider, path: "/data/basic.dbf".to_string() };
+
+        let df = read_dbase_location(&location, None).await.unwrap();
+        let expected = DbcScanner::from_dbf_path(&fixture_path, None).unwrap().read_all().unwrap();
+        assert!(df.equals_missing(&expected));
+    }
+}
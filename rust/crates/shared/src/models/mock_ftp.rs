@@ -0,0 +1,240 @@
+//! A local FTP server for tests, configurable per command
+//!
+//! The server logs every control line it receives and handles the login,
+//! `TYPE`, `CWD`, `PASV`, `REST` and `QUIT` on its own. Everything else goes
+//! to the test's handler, which can also override any of those defaults.
+//!
+//! ```ignore
+//! let server = MockFtpServer::start(|command, arg| match (command, arg) {
+//!     ("RETR", "basic.dbf") => Some(MockReply::data(body.clone())),
+//!     _ => None,
+//! }).await;
+//! let provider = FtpFileSystemProvider::new("127.0.0.1".into(), "/pub".into(), Some(server.port()));
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// What the server sends back for one command
+pub(crate) enum MockReply {
+    /// A single control-connection reply, e.g. `"250 ok"`
+    Line(String),
+    /// Send `body` over the passive data connection, then `done` on the control connection
+    Data { body: Vec<u8>, done: String },
+    /// Send `body` over the data connection, then hang up the whole session without a reply
+    Abort { body: Vec<u8> },
+}
+
+impl MockReply {
+    pub(crate) fn line(reply: &str) -> Self {
+        MockReply::Line(reply.to_string())
+    }
+
+    /// A transfer that completes normally
+    pub(crate) fn data(body: impl Into<Vec<u8>>) -> Self {
+        MockReply::Data {
+            body: body.into(),
+            done: "226 transfer complete".to_string(),
+        }
+    }
+}
+
+type Handler = dyn Fn(&str, &str) -> Option<MockReply> + Send + Sync;
+
+/// Counters and logs shared between the server tasks and the test
+#[derive(Default)]
+struct MockState {
+    commands: Mutex<Vec<String>>,
+    raw: Mutex<Vec<u8>>,
+    connections: AtomicUsize,
+    sessions: AtomicUsize,
+    peak_sessions: AtomicUsize,
+}
+
+/// A mock FTP server listening on a random local port
+///
+/// The server runs until the test's runtime shuts down.
+pub(crate) struct MockFtpServer {
+    port: u16,
+    state: Arc<MockState>,
+}
+
+impl MockFtpServer {
+    /// Start a server that asks `handler` first for every command
+    ///
+    /// Returning `None` falls back to the built-in replies; commands without
+    /// one get `502 not implemented`.
+    pub(crate) async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str, &str) -> Option<MockReply> + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let state = Arc::new(MockState::default());
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                server_state.connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(serve_session(socket, server_state.clone(), handler.clone()));
+            }
+        });
+
+        MockFtpServer { port, state }
+    }
+
+    /// Start a server that accepts connections but never greets
+    ///
+    /// Whatever the client sends is kept for [`MockFtpServer::raw_bytes`].
+    pub(crate) async fn silent() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let state = Arc::new(MockState::default());
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                server_state.connections.fetch_add(1, Ordering::SeqCst);
+                let state = server_state.clone();
+                tokio::spawn(async move {
+                    let mut buffer = [0u8; 1024];
+                    while let Ok(read) = socket.read(&mut buffer).await {
+                        if read == 0 {
+                            break;
+                        }
+                        state.raw.lock().unwrap().extend_from_slice(&buffer[..read]);
+                    }
+                });
+            }
+        });
+
+        MockFtpServer { port, state }
+    }
+
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Every control line received so far, across all connections
+    pub(crate) fn commands(&self) -> Vec<String> {
+        self.state.commands.lock().unwrap().clone()
+    }
+
+    /// Bytes received by a [`MockFtpServer::silent`] server
+    pub(crate) fn raw_bytes(&self) -> Vec<u8> {
+        self.state.raw.lock().unwrap().clone()
+    }
+
+    /// Control connections accepted so far
+    pub(crate) fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+
+    /// Sessions logged in and not yet closed with `QUIT`
+    pub(crate) fn open_sessions(&self) -> usize {
+        self.state.sessions.load(Ordering::SeqCst)
+    }
+
+    /// The most sessions that were logged in at once
+    pub(crate) fn peak_sessions(&self) -> usize {
+        self.state.peak_sessions.load(Ordering::SeqCst)
+    }
+}
+
+async fn serve_session(socket: tokio::net::TcpStream, state: Arc<MockState>, handler: Arc<Handler>) {
+    let (reader, mut writer) = socket.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut data_listener: Option<TcpListener> = None;
+    let mut logged_in = false;
+    // Offset from the last `REST`, applied to the next transfer
+    let mut restart_at = 0;
+    if writer.write_all(b"220 ready\r\n").await.is_err() {
+        return;
+    }
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        state.commands.lock().unwrap().push(line.clone());
+        let (command, arg) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+
+        let reply = match handler(command, arg) {
+            Some(reply) => reply,
+            None => match command {
+                "USER" => MockReply::line("331 password required"),
+                "PASS" => {
+                    logged_in = true;
+                    let now = state.sessions.fetch_add(1, Ordering::SeqCst) + 1;
+                    state.peak_sessions.fetch_max(now, Ordering::SeqCst);
+                    MockReply::line("230 logged in")
+                }
+                "TYPE" => MockReply::line("200 ok"),
+                "CWD" => MockReply::line("250 ok"),
+                "REST" => match arg.parse::<usize>() {
+                    Ok(offset) => {
+                        restart_at = offset;
+                        MockReply::Line(format!("350 restarting at {}", offset))
+                    }
+                    Err(_) => MockReply::line("501 bad offset"),
+                },
+                "PASV" => {
+                    let data = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                    let data_port = data.local_addr().unwrap().port();
+                    data_listener = Some(data);
+                    MockReply::Line(format!(
+                        "227 Entering Passive Mode (127,0,0,1,{},{})",
+                        data_port >> 8,
+                        data_port & 0xff
+                    ))
+                }
+                "QUIT" => {
+                    if logged_in {
+                        logged_in = false;
+                        state.sessions.fetch_sub(1, Ordering::SeqCst);
+                    }
+                    let _ = writer.write_all(b"221 bye\r\n").await;
+                    break;
+                }
+                _ => MockReply::line("502 not implemented"),
+            },
+        };
+
+        let sent = match reply {
+            MockReply::Line(reply) => writer.write_all(format!("{}\r\n", reply).as_bytes()).await,
+            MockReply::Data { body, done } => {
+                let offset = std::mem::take(&mut restart_at);
+                if !send_data(&mut writer, data_listener.take(), &body[offset.min(body.len())..]).await {
+                    continue;
+                }
+                writer.write_all(format!("{}\r\n", done).as_bytes()).await
+            }
+            MockReply::Abort { body } => {
+                let offset = std::mem::take(&mut restart_at);
+                send_data(&mut writer, data_listener.take(), &body[offset.min(body.len())..]).await;
+                break;
+            }
+        };
+        if sent.is_err() {
+            break;
+        }
+    }
+
+    if logged_in {
+        state.sessions.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Open the transfer and send `body` over the data connection; false without a prior `PASV`
+async fn send_data(writer: &mut tokio::net::tcp::OwnedWriteHalf, data_listener: Option<TcpListener>, body: &[u8]) -> bool {
+    let Some(data_listener) = data_listener else {
+        let _ = writer.write_all(b"425 use PASV first\r\n").await;
+        return false;
+    };
+    writer.write_all(b"150 opening data connection\r\n").await.unwrap();
+    let (mut data, _) = data_listener.accept().await.unwrap();
+    data.write_all(body).await.unwrap();
+    data.shutdown().await.unwrap();
+    true
+}
@@ -1,21 +1,30 @@
 pub mod file_info;
 pub mod file;
+pub mod file_extension;
 pub mod utils;
 pub mod async_utils;
 pub mod directory;
+pub mod ftp_path;
+pub mod retry;
 pub mod download;
+pub mod location;
 pub mod subsystem;
 pub mod regex_patterns;
 pub mod date_utils;
 pub mod geo_utils;
 pub mod group_info;
+pub mod cid;
 pub mod dbase_utils;
 pub mod polars_utils;
 
+#[cfg(test)]
+pub(crate) mod mock_ftp;
+
 pub use file_info::*;
 pub use file::*;
+pub use file_extension::*;
 // Re-export utils with specific items to avoid conflicts
-pub use utils::{to_vec, item_to_vec, slice_to_vec, option_to_vec, vec_from_slice, path_utils, cache, DEFAULT_CACHE_DIR, CACHE_PATH, CACHE_PATH_BUF, DIRECTORY_CACHE};
+pub use utils::{to_vec, item_to_vec, slice_to_vec, option_to_vec, vec_from_slice, path_utils, cache, resolve_cache_dir, CACHE_DIR_ENV, DEFAULT_CACHE_DIR, CACHE_PATH, CACHE_PATH_BUF, DIRECTORY_CACHE};
 // Re-export async_utils with async-specific items
 pub use async_utils::{
     to_vec_async, item_to_vec_async, slice_to_vec_async, option_to_vec_async, vec_from_slice_async,
@@ -24,6 +33,8 @@ pub use async_utils::{
 };
 // Re-export directory module
 pub use directory::*;
+// Re-export ftp path module
+pub use ftp_path::*;
 // Re-export subsystem module
 pub use subsystem::*;
 // Re-export regex patterns module
@@ -34,8 +45,14 @@ pub use date_utils::*;
 pub use geo_utils::*;
 // Re-export group info module
 pub use group_info::*;
+// Re-export cid module
+pub use cid::*;
+// Re-export retry module
+pub use retry::*;
 // Re-export download module
 pub use download::*;
+// Re-export location module
+pub use location::*;
 // Re-export dbase utils module
 pub use dbase_utils::*;
 // Re-export polars utils module
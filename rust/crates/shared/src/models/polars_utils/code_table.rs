@@ -0,0 +1,86 @@
+//! Code→label tables for expanding coded DATASUS columns
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use polars::prelude::{DataFrame, DataType, IntoSeries, StringChunked};
+
+use crate::models::dbase_utils::is_dbc_file;
+use crate::models::polars_utils::dbase_pl::{read_dbc_columns, read_dbf_columns, DbcResult};
+
+/// Load a code→label table from a DBF or DBC file (told apart by content)
+///
+/// Codes and labels are read as text and trimmed. Rows with a null or blank
+/// code or a null label are left out; a repeated code keeps its last label.
+pub fn load_code_table<P: AsRef<Path>>(path: P, code_col: &str, label_col: &str) -> DbcResult<HashMap<String, String>> {
+    let path = path.as_ref();
+    let df = if is_dbc_file(path) {
+        read_dbc_columns(path, &[code_col, label_col])?
+    } else {
+        read_dbf_columns(path, &[code_col, label_col])?
+    };
+
+    let codes = df.column(code_col)?.cast(&DataType::String)?;
+    let labels = df.column(label_col)?.cast(&DataType::String)?;
+    Ok(codes
+        .str()?
+        .into_iter()
+        .zip(labels.str()?)
+        .filter_map(|(code, label)| {
+            let (code, label) = (code?.trim(), label?.trim());
+            (!code.is_empty()).then(|| (code.to_string(), label.to_string()))
+        })
+        .collect())
+}
+
+/// Add `new_name` to `df` holding the label of each code in `column`
+///
+/// Codes are matched as trimmed text, so numeric code columns work too.
+/// Codes missing from `table`, and null codes, get a null label.
+pub fn decode_column(df: &DataFrame, column: &str, table: &HashMap<String, String>, new_name: &str) -> DbcResult<DataFrame> {
+    let codes = df.column(column)?.cast(&DataType::String)?;
+    let labels: StringChunked = codes
+        .str()?
+        .into_iter()
+        .map(|code| code.and_then(|code| table.get(code.trim())).map(String::as_str))
+        .collect();
+
+    let mut decoded = df.clone();
+    decoded.with_column(labels.with_name(new_name.into()).into_series())?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::polars_utils::dbase_pl::fixtures;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_decode_column_against_loaded_table() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("TABMUN.dbf");
+        fixtures::write_code_table_dbf(&path, &[("355030", "Sao Paulo"), ("330455", "Rio de Janeiro"), ("", "Ignorado")]);
+
+        let table = load_code_table(&path, "CO_MUNIC", "DS_MUNIC").unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table["355030"], "Sao Paulo");
+
+        let df = df!(
+            "MUNIC_RES" => [Some("355030"), Some("999999"), None, Some("330455 ")],
+            "N_AIH" => ["1", "2", "3", "4"],
+        ).unwrap();
+        let decoded = decode_column(&df, "MUNIC_RES", &table, "MUNIC_RES_NOME").unwrap();
+        assert_eq!(decoded.width(), 3);
+        let labels: Vec<Option<&str>> = decoded.column("MUNIC_RES_NOME").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(labels, [Some("Sao Paulo"), None, None, Some("Rio de Janeiro")]);
+
+        // Numeric code columns are matched through their text form
+        let numeric = df!("MUNIC_RES" => [355030i64, 1]).unwrap();
+        let decoded = decode_column(&numeric, "MUNIC_RES", &table, "NOME").unwrap();
+        assert_eq!(decoded.column("NOME").unwrap().str().unwrap().get(0), Some("Sao Paulo"));
+
+        assert!(load_code_table(&path, "CO_MUNIC", "MISSING").is_err());
+        assert!(decode_column(&df, "MISSING", &table, "NOME").is_err());
+    }
+}
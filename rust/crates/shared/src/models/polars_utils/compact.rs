@@ -0,0 +1,214 @@
+//! Compacting many small DATASUS files into one Parquet file per group, UF and year
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use polars::prelude::{DataFrame, DataType, ParquetWriter, PolarsResult, Schema as PlSchema};
+
+use crate::models::dbase_utils::is_dbc_file;
+use crate::models::polars_utils::dbase_pl::{common_columns, DbcConfig, DbcError, DbcResult, DbcScanner};
+use crate::models::regex_patterns::DataSusFileInfo;
+
+/// One Parquet file written by [`compact_to_parquet`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionResult {
+    /// Group code shared by the sources (e.g. `RD`)
+    pub group: String,
+    /// UF abbreviation shared by the sources
+    pub uf: String,
+    /// Four-digit year shared by the sources
+    pub year: u16,
+    /// Source files, in the order their records were appended
+    pub sources: Vec<PathBuf>,
+    /// The Parquet file written
+    pub output: PathBuf,
+    /// Records written across all sources
+    pub rows_written: u64,
+    /// Size of the Parquet file on disk
+    pub output_size_bytes: u64,
+}
+
+/// Merge DBC/DBF files into one Parquet file per group, UF and year
+///
+/// Files are grouped by their DATASUS name (`RDSP1901.dbc` and `RDSP1902.dbc`
+/// both go to `RDSP2019.parquet` under `out_dir`) and appended in name order,
+/// `chunk_size` records at a time, so memory use is bounded by one batch
+/// however many files a group has. Each output keeps the columns common to
+/// all of its sources; a column read with different types is widened with
+/// [`widen_dtype`]. Fails on a file name that isn't a DATASUS one.
+pub fn compact_to_parquet<P: AsRef<Path>>(
+    files: &[PathBuf],
+    out_dir: P,
+    config: Option<DbcConfig>,
+) -> DbcResult<Vec<CompactionResult>> {
+    let out_dir = out_dir.as_ref();
+    let mut groups: BTreeMap<(String, String, u16), Vec<PathBuf>> = BTreeMap::new();
+    for path in files {
+        let info = datasus_file_info(path).ok_or_else(|| {
+            DbcError::InvalidDbcFormat(format!("Not a DATASUS file name: {}", path.display()))
+        })?;
+        groups
+            .entry((info.group_name.clone(), info.uf_code.clone(), info.full_year()))
+            .or_default()
+            .push(path.clone());
+    }
+
+    std::fs::create_dir_all(out_dir).map_err(|e| DbcError::io_error(e, out_dir.display().to_string()))?;
+
+    groups
+        .into_iter()
+        .map(|((group, uf, year), mut sources)| {
+            sources.sort();
+            let output = out_dir.join(format!("{}{}{}.parquet", group, uf, year));
+            let rows_written = write_compacted(&sources, &output, config.clone())?;
+            let output_size_bytes = std::fs::metadata(&output)
+                .map_err(|e| DbcError::io_error(e, output.display().to_string()))?
+                .len();
+            Ok(CompactionResult {
+                group,
+                uf,
+                year,
+                sources,
+                output,
+                rows_written,
+                output_size_bytes,
+            })
+        })
+        .collect()
+}
+
+/// Type a column takes when its sources disagree
+///
+/// Integers of different widths become `Int64`, a mix of integers and floats
+/// `Float64`, and anything else `String`.
+pub fn widen_dtype(a: &DataType, b: &DataType) -> DataType {
+    let is_number = |dtype: &DataType| dtype.is_integer() || dtype.is_float();
+    if a == b {
+        a.clone()
+    } else if a.is_integer() && b.is_integer() {
+        DataType::Int64
+    } else if is_number(a) && is_number(b) {
+        DataType::Float64
+    } else {
+        DataType::String
+    }
+}
+
+/// Parse a `.dbc` or `.dbf` path's file name as a DATASUS one
+fn datasus_file_info(path: &Path) -> Option<DataSusFileInfo> {
+    let stem = path.file_stem()?.to_str()?;
+    let extension = path.extension()?.to_str()?;
+    if !extension.eq_ignore_ascii_case("dbc") && !extension.eq_ignore_ascii_case("dbf") {
+        return None;
+    }
+    DataSusFileInfo::parse(&format!("{}.dbc", stem))
+}
+
+/// Append every source to one Parquet file, returning the records written
+fn write_compacted(sources: &[PathBuf], output: &Path, config: Option<DbcConfig>) -> DbcResult<u64> {
+    let columns = common_columns(sources)?;
+    if columns.is_empty() {
+        return Err(DbcError::InvalidDbcFormat("No columns common to every file".to_string()));
+    }
+
+    let scanners = sources
+        .iter()
+        .map(|path| {
+            if is_dbc_file(path) {
+                DbcScanner::from_dbc_path(path, config.clone())
+            } else {
+                DbcScanner::from_dbf_path(path, config.clone())
+            }
+        })
+        .collect::<DbcResult<Vec<_>>>()?;
+
+    let mut schema = PlSchema::with_capacity(columns.len());
+    for column in &columns {
+        let dtype = scanners
+            .iter()
+            .filter_map(|scanner| scanner.schema().get(column).cloned())
+            .reduce(|a, b| widen_dtype(&a, &b))
+            .unwrap_or(DataType::String);
+        schema.with_column(column.as_str().into(), dtype);
+    }
+
+    let batch_size = config.unwrap_or_default().chunk_size.max(1);
+    let file = std::fs::File::create(output).map_err(|e| DbcError::io_error(e, output.display().to_string()))?;
+    let mut writer = ParquetWriter::new(file)
+        .with_row_group_size(Some(batch_size))
+        .batched(&schema)?;
+
+    let mut rows_written = 0u64;
+    for scanner in &scanners {
+        let total_rows = scanner.record_count()? as usize;
+        for start in (0..total_rows).step_by(batch_size) {
+            let batch = conform_to_schema(&scanner.read_rows(start..(start + batch_size).min(total_rows))?, &schema)?;
+            writer.write_batch(&batch)?;
+            rows_written += batch.height() as u64;
+        }
+    }
+    writer.finish()?;
+
+    Ok(rows_written)
+}
+
+/// Select the schema's columns from `batch` in schema order, cast to the schema's types
+fn conform_to_schema(batch: &DataFrame, schema: &PlSchema) -> PolarsResult<DataFrame> {
+    let columns = schema
+        .iter()
+        .map(|(name, dtype)| batch.column(name.as_str())?.cast(dtype))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::polars_utils::dbase_pl::fixtures;
+    use polars::prelude::{ParquetReader, SerReader};
+
+    #[test]
+    fn test_widen_dtype() {
+        assert_eq!(widen_dtype(&DataType::Int32, &DataType::Int32), DataType::Int32);
+        assert_eq!(widen_dtype(&DataType::Int16, &DataType::Int32), DataType::Int64);
+        assert_eq!(widen_dtype(&DataType::Int32, &DataType::Float64), DataType::Float64);
+        assert_eq!(widen_dtype(&DataType::Int32, &DataType::String), DataType::String);
+    }
+
+    #[test]
+    fn test_compact_monthly_files_per_uf_year() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let january = temp_dir.path().join("RDSP1901.dbf");
+        let february = temp_dir.path().join("RDSP1902.dbf");
+        // Carries an extra VALOR column the other months lack
+        let march = temp_dir.path().join("RDSP1903.dbf");
+        let rio = temp_dir.path().join("RDRJ1901.dbf");
+        fixtures::write_numbered_dbf(&january, 3);
+        fixtures::write_numbered_dbf(&february, 2);
+        fixtures::write_header_only_dbf(&march);
+        fixtures::write_numbered_dbf(&rio, 1);
+
+        let out_dir = temp_dir.path().join("compacted");
+        let files = vec![february.clone(), rio.clone(), march.clone(), january.clone()];
+        let results = compact_to_parquet(&files, &out_dir, None).unwrap();
+
+        assert_eq!(results.len(), 2);
+        let rio_result = &results[0];
+        assert_eq!((rio_result.uf.as_str(), rio_result.year, rio_result.rows_written), ("RJ", 2019, 1));
+
+        let sp = &results[1];
+        assert_eq!((sp.group.as_str(), sp.uf.as_str(), sp.year), ("RD", "SP", 2019));
+        assert_eq!(sp.sources, [january, february, march]);
+        assert_eq!(sp.output, out_dir.join("RDSP2019.parquet"));
+        assert_eq!(sp.rows_written, 5);
+        assert_eq!(sp.output_size_bytes, std::fs::metadata(&sp.output).unwrap().len());
+
+        let written = ParquetReader::new(std::fs::File::open(&sp.output).unwrap()).finish().unwrap();
+        let names: Vec<&str> = written.get_column_names().into_iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, ["ID", "UF"]);
+        let ids: Vec<Option<i64>> = written.column("ID").unwrap().cast(&DataType::Int64).unwrap().i64().unwrap().into_iter().collect();
+        assert_eq!(ids, [Some(0), Some(1), Some(2), Some(0), Some(1)]);
+
+        assert!(compact_to_parquet(&[temp_dir.path().join("notes.dbf")], &out_dir, None).is_err());
+    }
+}
@@ -4,10 +4,10 @@ use std::path::Path;
 
 use super::error::{DbcError, DbcResult};
 use crate::models::dbase_utils::{
-    dbase_header_to_arrow_schema, dbase_header_to_arrow_schema_with_metadata,
-    decompress_dbc_to_dbf,
+    dbase_header_to_arrow_schema_with_metadata_and_options, dbase_header_to_arrow_schema_with_options,
+    decompress_dbc_to_dbf, DbaseEncoding,
 };
-use dbase::{FieldInfo, Reader};
+use dbase::{FieldInfo, Reader, ReaderBuilder};
 use polars::prelude::{PlSmallStr, Schema as PlSchema, DataType};
 use arrow::datatypes::Schema as ArrowSchema;
 
@@ -72,9 +72,18 @@ pub fn arrow_schema_to_polars(arrow_schema: &ArrowSchema) -> DbcResult<PlSchema>
 pub fn dbf_header_to_polars_schema<P: AsRef<Path>>(
     file_path: P,
     single_column_name: Option<&PlSmallStr>,
+) -> DbcResult<PlSchema> {
+    dbf_header_to_polars_schema_with_options(file_path, single_column_name, true)
+}
+
+/// Convert DBF file header to Polars Schema, optionally narrowing integer numerics by width
+pub fn dbf_header_to_polars_schema_with_options<P: AsRef<Path>>(
+    file_path: P,
+    single_column_name: Option<&PlSmallStr>,
+    shrink_numeric_types: bool,
 ) -> DbcResult<PlSchema> {
     // Use existing Arrow schema conversion
-    let arrow_schema = dbase_header_to_arrow_schema(file_path)?;
+    let arrow_schema = dbase_header_to_arrow_schema_with_options(file_path, shrink_numeric_types)?;
     
     // Convert Arrow → Polars
     let mut polars_schema = arrow_schema_to_polars(&arrow_schema)?;
@@ -94,9 +103,21 @@ pub fn dbf_header_to_polars_schema<P: AsRef<Path>>(
 pub fn dbf_header_to_polars_schema_with_metadata<P: AsRef<Path>>(
     file_path: P,
     single_column_name: Option<&PlSmallStr>,
+) -> DbcResult<(PlSchema, Vec<FieldInfo>)> {
+    dbf_header_to_polars_schema_with_metadata_and_options(file_path, single_column_name, true)
+}
+
+/// Convert DBF file header to Polars Schema with metadata, optionally narrowing integer numerics by width
+///
+/// Pass `DbcConfig::shrink_numeric_types` to match the schema a scanner would infer.
+pub fn dbf_header_to_polars_schema_with_metadata_and_options<P: AsRef<Path>>(
+    file_path: P,
+    single_column_name: Option<&PlSmallStr>,
+    shrink_numeric_types: bool,
 ) -> DbcResult<(PlSchema, Vec<FieldInfo>)> {
     // Use existing Arrow schema conversion with metadata
-    let (arrow_schema, field_infos) = dbase_header_to_arrow_schema_with_metadata(file_path)?;
+    let (arrow_schema, field_infos) =
+        dbase_header_to_arrow_schema_with_metadata_and_options(file_path, shrink_numeric_types)?;
     
     // Convert Arrow → Polars
     let mut polars_schema = arrow_schema_to_polars(&arrow_schema)?;
@@ -135,10 +156,177 @@ pub fn create_dbf_reader_from_file<P: AsRef<Path>>(
     Reader::from_path(file_path).map_err(DbcError::from)
 }
 
+/// Create a DBF reader that decodes character fields with the given encoding
+pub fn create_dbf_reader_with_encoding<P: AsRef<Path>>(
+    file_path: P,
+    encoding: DbaseEncoding,
+) -> DbcResult<Reader<std::io::BufReader<std::fs::File>>> {
+    let reader = match encoding {
+        DbaseEncoding::Latin1 => Reader::from_path_with_encoding(file_path, yore::code_pages::CP28591),
+        DbaseEncoding::Cp850 => Reader::from_path_with_encoding(file_path, yore::code_pages::CP850),
+        DbaseEncoding::Utf8 => Reader::from_path_with_encoding(file_path, dbase::encoding::Unicode),
+    };
+    reader.map_err(DbcError::from)
+}
+
+/// Create a DBF reader that resolves memo fields from `memo_path`
+///
+/// Unlike [`create_dbf_reader_with_encoding`], which only finds a lower-case
+/// `.dbt`/`.fpt` named after `file_path`, the memo file is given explicitly,
+/// so copies of a DBF can still be read against the original memo file.
+pub fn create_dbf_reader_with_memo<P: AsRef<Path>>(
+    file_path: P,
+    memo_path: Option<&Path>,
+    encoding: DbaseEncoding,
+) -> DbcResult<Reader<std::io::BufReader<std::fs::File>>> {
+    let file_path = file_path.as_ref();
+    match encoding {
+        DbaseEncoding::Latin1 => build_dbf_reader(file_path, memo_path, yore::code_pages::CP28591),
+        DbaseEncoding::Cp850 => build_dbf_reader(file_path, memo_path, yore::code_pages::CP850),
+        DbaseEncoding::Utf8 => build_dbf_reader(file_path, memo_path, dbase::encoding::Unicode),
+    }
+}
+
+fn build_dbf_reader<E: dbase::Encoding + 'static>(
+    file_path: &Path,
+    memo_path: Option<&Path>,
+    encoding: E,
+) -> DbcResult<Reader<std::io::BufReader<std::fs::File>>> {
+    let open = |path: &Path| {
+        std::fs::File::open(path)
+            .map(std::io::BufReader::new)
+            .map_err(|e| DbcError::io_error(e, path.display().to_string()))
+    };
+    let mut builder = ReaderBuilder::new(open(file_path)?).with_encoding(encoding);
+    if let Some(memo_path) = memo_path {
+        builder = builder.with_memo(open(memo_path)?);
+    }
+    builder.build().map_err(DbcError::from)
+}
+
+/// Delay before the first retry of a locked DBF, doubled for each further retry
+const DBF_OPEN_RETRY_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Whether an open failure may succeed on a retry (an interrupted or timed-out open, or a sharing violation)
+///
+/// Malformed files, missing files and denied permissions are never transient.
+pub fn is_transient_open_error(error: &DbcError) -> bool {
+    let io_error = match error {
+        DbcError::IO(io_error, _) => io_error,
+        DbcError::Dbase(dbase_error) => match dbase_error.kind() {
+            dbase::ErrorKind::IoError(io_error) => io_error,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    use std::io::ErrorKind;
+    if matches!(io_error.kind(), ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::TimedOut) {
+        return true;
+    }
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION, e.g. while antivirus scans a new download
+    cfg!(windows) && matches!(io_error.raw_os_error(), Some(32 | 33))
+}
+
+/// Run `open`, retrying up to `retries` times with backoff while it fails with a transient lock
+pub fn open_with_retries<T>(retries: u32, mut open: impl FnMut() -> DbcResult<T>) -> DbcResult<T> {
+    let mut backoff = DBF_OPEN_RETRY_INITIAL_BACKOFF;
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match open() {
+            Err(e) if attempts <= retries && is_transient_open_error(&e) => {
+                log::debug!("Opening DBF failed (attempt {}), retrying: {}", attempts, e);
+                std::thread::sleep(backoff);
+                backoff = backoff.saturating_mul(2);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Parse the raw bytes of a numeric (`N`/`F`) field more leniently than dbase does
+///
+/// Space and NUL padding is trimmed, a leading `+` or `-` may be separated
+/// from the digits by spaces, and a `,` is read as the decimal separator when
+/// there is no `.`. Blank values, a lone `.` and `*` overflow markers are null.
+pub fn parse_dbf_numeric<T: std::str::FromStr>(bytes: &[u8]) -> Result<Option<T>, T::Err> {
+    let text: String = bytes.iter().map(|&byte| char::from(byte)).collect();
+    let text = text.trim_matches(|c| c == ' ' || c == '\0');
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", text.strip_prefix('+').unwrap_or(text)),
+    };
+    let digits = digits.trim_start();
+    if digits.is_empty() || digits == "." || digits.bytes().all(|byte| byte == b'*') {
+        return Ok(None);
+    }
+
+    let digits = if digits.contains('.') { digits.to_string() } else { digits.replacen(',', ".", 1) };
+    format!("{sign}{digits}").parse().map(Some)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_parse_dbf_numeric() {
+        assert_eq!(parse_dbf_numeric::<f64>(b"   12.50"), Ok(Some(12.5)));
+        assert_eq!(parse_dbf_numeric::<f64>(b"  +3.25 "), Ok(Some(3.25)));
+        assert_eq!(parse_dbf_numeric::<f64>(b"  - 0.75"), Ok(Some(-0.75)));
+        assert_eq!(parse_dbf_numeric::<f64>(b"    1,50"), Ok(Some(1.5)));
+        assert_eq!(parse_dbf_numeric::<f32>(b"7\0\0"), Ok(Some(7.0)));
+        for blank in [&b"        "[..], b"   .", b"****", b"-", b""] {
+            assert_eq!(parse_dbf_numeric::<f64>(blank), Ok(None), "{blank:?}");
+        }
+        for malformed in [&b"  1x.2!"[..], b"1.2.3", b"1,2,3", b"--1"] {
+            assert!(parse_dbf_numeric::<f64>(malformed).is_err(), "{malformed:?}");
+        }
+    }
     
+    #[test]
+    fn test_open_with_retries_only_retries_locks() {
+        use std::io::{Error, ErrorKind};
+
+        // Locked twice, then opens
+        let mut calls = 0;
+        let opened = open_with_retries(3, || {
+            calls += 1;
+            if calls <= 2 {
+                Err(DbcError::io_error(Error::from(ErrorKind::WouldBlock), "RDSP2301.dbf"))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(opened.unwrap(), 3);
+
+        // Still locked after every retry
+        let mut calls = 0;
+        let locked: DbcResult<()> = open_with_retries(1, || {
+            calls += 1;
+            Err(DbcError::io_error(Error::from(ErrorKind::TimedOut), "RDSP2301.dbf"))
+        });
+        assert!(locked.is_err());
+        assert_eq!(calls, 2);
+
+        // Malformed, missing or unreadable files fail at once
+        for error in [
+            DbcError::missing_header("truncated"),
+            DbcError::io_error(Error::from(ErrorKind::NotFound), "RDSP2301.dbf"),
+            DbcError::io_error(Error::from(ErrorKind::PermissionDenied), "RDSP2301.dbf"),
+            DbcError::io_error(Error::from(ErrorKind::InvalidData), "RDSP2301.dbf"),
+        ] {
+            let mut error = Some(error);
+            let mut calls = 0;
+            let failed: DbcResult<()> = open_with_retries(3, || {
+                calls += 1;
+                Err(error.take().unwrap())
+            });
+            assert!(failed.is_err());
+            assert_eq!(calls, 1);
+        }
+    }
+
     #[test]
     fn test_arrow_to_polars_schema_conversion() {
         // Create a simple Arrow schema
@@ -195,6 +383,29 @@ mod tests {
         assert_eq!(polars_schema.len(), 1);
     }
     
+    #[test]
+    fn test_schema_with_metadata_follows_shrink_option() {
+        use super::super::fixtures;
+        use super::super::DbcConfig;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let (schema, field_infos) = dbf_header_to_polars_schema_with_metadata(&path, None).unwrap();
+        assert_eq!(schema.get("IDADE"), Some(&DataType::Int16)); // N(3,0)
+        assert_eq!(field_infos.len(), schema.len());
+
+        let config = DbcConfig {
+            shrink_numeric_types: false,
+            ..Default::default()
+        };
+        let (schema, _) =
+            dbf_header_to_polars_schema_with_metadata_and_options(&path, None, config.shrink_numeric_types).unwrap();
+        assert_eq!(schema.get("IDADE"), Some(&DataType::Int64));
+        assert_eq!(schema.get("VALOR"), Some(&DataType::Float64)); // N(12,2)
+    }
+
     #[test]
     fn test_utility_functions_exist() {
         // Verify that our utility functions compile and can be referenced
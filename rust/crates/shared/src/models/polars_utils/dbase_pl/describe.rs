@@ -0,0 +1,260 @@
+//! Machine-readable descriptions of DBF/DBC file layouts, for data catalogs and tooling
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::des::dbf_header_to_polars_schema;
+use super::error::{DbcError, DbcResult};
+use super::scan::{max_threads, thread_pool};
+use crate::models::dbase_utils::{decompress_dbc_head_to_dbf, is_dbc_file, read_dbf_field_descriptors};
+
+/// One field as declared in the header, with the Polars type it is read as
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbaseFieldInfo {
+    /// Field name
+    pub name: String,
+    /// Single-letter dBase type code (`C`, `N`, `D`, ...)
+    pub dbase_type: char,
+    /// Declared width in bytes
+    pub width: u8,
+    /// Declared decimal places
+    pub decimals: u8,
+    /// Polars dtype the reader produces for this field
+    pub polars_type: String,
+}
+
+/// Layout summary of a DBF or DBC file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbaseFileSummary {
+    /// Path that was described
+    pub path: String,
+    /// Number of records declared in the header
+    pub record_count: u64,
+    /// Fields in declaration order
+    pub fields: Vec<DbaseFieldInfo>,
+}
+
+/// Describe the layout of a DBF or DBC file from its header
+///
+/// DBC files are only decompressed up to the end of the header.
+pub fn describe_dbase_file<P: AsRef<Path>>(file_path: P) -> DbcResult<DbaseFileSummary> {
+    let file_path = file_path.as_ref();
+    if is_dbc_file(file_path) {
+        let temp_dbf = tempfile::NamedTempFile::new()
+            .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
+        decompress_dbc_head_to_dbf(file_path, temp_dbf.path(), 0)?;
+
+        let mut summary = describe_dbf_header(temp_dbf.path())?;
+        summary.path = file_path.display().to_string();
+        Ok(summary)
+    } else {
+        describe_dbf_header(file_path)
+    }
+}
+
+/// Options for [`describe_many`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DescribeConfig {
+    /// Files described at once (None = the global reader limit, else all rayon threads)
+    pub num_threads: Option<usize>,
+    /// Show an overall progress bar on the terminal (default: false)
+    pub show_progress: bool,
+}
+
+/// Describe many DBF/DBC files in parallel, for building catalogs
+///
+/// Results come back in the order of `paths`, each with its own outcome, so
+/// one unreadable file doesn't stop the others from being described.
+pub fn describe_many(paths: &[PathBuf], config: DescribeConfig) -> Vec<(PathBuf, DbcResult<DbaseFileSummary>)> {
+    let progress = config.show_progress.then(|| {
+        let pb = ProgressBar::new(paths.len() as u64);
+        pb.set_style(
+            ProgressStyle::with_template("{prefix:>12.yellow.bold} [{wide_bar:.cyan}] {pos:>6.blue}/{len:<6.blue} files {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("█▉▊▋▌▍▎▏ ")
+        );
+        pb.set_prefix("Describing");
+        pb
+    });
+
+    let describe = |path: &PathBuf| {
+        let summary = describe_dbase_file(path);
+        if let Some(pb) = &progress {
+            pb.set_message(path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default());
+            pb.inc(1);
+        }
+        (path.clone(), summary)
+    };
+
+    let threads = config
+        .num_threads
+        .or_else(max_threads)
+        .unwrap_or_else(rayon::current_num_threads)
+        .max(1);
+    let results: Vec<(PathBuf, DbcResult<DbaseFileSummary>)> = match thread_pool(threads) {
+        Ok(pool) => pool.install(|| paths.par_iter().map(describe).collect()),
+        // Without a pool, fall back to describing on the calling thread
+        Err(_) => paths.iter().map(describe).collect(),
+    };
+
+    if let Some(pb) = progress {
+        let failed = results.iter().filter(|(_, summary)| summary.is_err()).count();
+        pb.finish_with_message(format!("{} described, {} failed", results.len() - failed, failed));
+    }
+    results
+}
+
+/// Field metadata (name, type, width, decimals) of a DBF or DBC file, without reading any records
+pub fn get_dbase_fields<P: AsRef<Path>>(file_path: P) -> DbcResult<Vec<DbaseFieldInfo>> {
+    describe_dbase_file(file_path).map(|summary| summary.fields)
+}
+
+/// Names of the fields every file declares, in the first file's declaration order
+///
+/// Only headers are read. An empty `paths` yields no columns.
+pub fn common_columns(paths: &[PathBuf]) -> DbcResult<Vec<String>> {
+    let Some((first, rest)) = paths.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    let mut columns: Vec<String> = get_dbase_fields(first)?.into_iter().map(|field| field.name).collect();
+    for path in rest {
+        let names: HashSet<String> = get_dbase_fields(path)?.into_iter().map(|field| field.name).collect();
+        columns.retain(|column| names.contains(column));
+    }
+    Ok(columns)
+}
+
+/// Describe a DBF or DBC file as pretty-printed JSON
+pub fn describe_dbase_file_json<P: AsRef<Path>>(file_path: P) -> DbcResult<String> {
+    let summary = describe_dbase_file(&file_path)?;
+    serde_json::to_string_pretty(&summary)
+        .map_err(|e| DbcError::io_error(e.into(), file_path.as_ref().display().to_string()))
+}
+
+/// Build the summary from a DBF header on disk
+fn describe_dbf_header(dbf_path: &Path) -> DbcResult<DbaseFileSummary> {
+    let descriptors = read_dbf_field_descriptors(dbf_path)?;
+    let schema = dbf_header_to_polars_schema(dbf_path, None)?;
+
+    let mut pre_header = [0u8; 8];
+    std::fs::File::open(dbf_path)
+        .and_then(|mut file| file.read_exact(&mut pre_header))
+        .map_err(|e| DbcError::io_error(e, dbf_path.display().to_string()))?;
+    let record_count = u64::from(u32::from_le_bytes([pre_header[4], pre_header[5], pre_header[6], pre_header[7]]));
+
+    let fields = descriptors
+        .into_iter()
+        .map(|descriptor| {
+            let polars_type = schema
+                .get(descriptor.name.as_str())
+                .map(|dtype| dtype.to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            DbaseFieldInfo {
+                name: descriptor.name,
+                dbase_type: descriptor.field_type,
+                width: descriptor.width,
+                decimals: descriptor.decimal_count,
+                polars_type,
+            }
+        })
+        .collect();
+
+    Ok(DbaseFileSummary {
+        path: dbf_path.display().to_string(),
+        record_count,
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::fixtures;
+
+    #[test]
+    fn test_describe_dbase_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let summary = describe_dbase_file(&path).unwrap();
+        assert_eq!(summary.record_count, 2);
+        assert_eq!(summary.fields.len(), 5);
+
+        let valor = &summary.fields[2];
+        assert_eq!(valor.name, "VALOR");
+        assert_eq!(valor.dbase_type, 'N');
+        assert_eq!(valor.width, 12);
+        assert_eq!(valor.decimals, 2);
+        assert_eq!(valor.polars_type, polars::prelude::DataType::Float64.to_string());
+    }
+
+    #[test]
+    fn test_get_dbase_fields() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let fields = get_dbase_fields(&path).unwrap();
+        let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(names.len(), 5);
+        assert_eq!(names[2], "VALOR");
+        assert_eq!((fields[2].width, fields[2].decimals), (12, 2));
+        assert_eq!(fields, describe_dbase_file(&path).unwrap().fields);
+    }
+
+    #[test]
+    fn test_describe_many_covers_every_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fixtures::write_basic_dbf(&temp_dir.path().join("basic.dbf"));
+        fixtures::write_numbered_dbf(&temp_dir.path().join("numbered.dbf"), 30);
+        fixtures::write_dbc_from_dbf(&temp_dir.path().join("numbered.dbf"), &temp_dir.path().join("RDSP2401.dbc"));
+        fixtures::write_header_only_dbf(&temp_dir.path().join("empty.dbf"));
+        std::fs::write(temp_dir.path().join("broken.dbf"), b"not a dbf").unwrap();
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        paths.sort();
+
+        let config = DescribeConfig {
+            num_threads: Some(2),
+            ..DescribeConfig::default()
+        };
+        let results = describe_many(&paths, config);
+        let described: Vec<&PathBuf> = results.iter().map(|(path, _)| path).collect();
+        assert_eq!(described, paths.iter().collect::<Vec<_>>());
+
+        let record_count = |name: &str| {
+            let (_, summary) = results.iter().find(|(path, _)| path.ends_with(name)).unwrap();
+            summary.as_ref().map(|summary| summary.record_count).ok()
+        };
+        assert_eq!(record_count("basic.dbf"), Some(2));
+        assert_eq!(record_count("numbered.dbf"), Some(30));
+        assert_eq!(record_count("RDSP2401.dbc"), Some(30));
+        assert_eq!(record_count("empty.dbf"), Some(0));
+        assert_eq!(record_count("broken.dbf"), None);
+    }
+
+    #[test]
+    fn test_describe_dbase_file_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let json = describe_dbase_file_json(&path).unwrap();
+        for key in ["\"path\"", "\"record_count\"", "\"fields\"", "\"name\"", "\"dbase_type\"", "\"width\"", "\"decimals\"", "\"polars_type\""] {
+            assert!(json.contains(key), "missing {key} in {json}");
+        }
+
+        let parsed: DbaseFileSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, describe_dbase_file(&path).unwrap());
+    }
+}
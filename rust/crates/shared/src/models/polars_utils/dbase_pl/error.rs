@@ -3,6 +3,7 @@
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::io;
+use std::path::PathBuf;
 
 use dbase::FieldInfo;
 use polars::error::PolarsError;
@@ -43,6 +44,12 @@ pub enum DbcError {
     CompressionError(String),
     /// DBF record parsing failed
     RecordParsingError(String),
+    /// A provided schema's field count (first) doesn't match the file header's (second)
+    SchemaFieldCountMismatch(usize, usize),
+    /// UF values that aren't Brazilian states (nulls show up as empty strings)
+    UnknownUfs(Vec<String>),
+    /// A DBF declares memo fields but has no `.dbt`/`.fpt` file next to it
+    MissingMemoFile(PathBuf),
 }
 
 impl Display for DbcError {
@@ -85,6 +92,18 @@ impl Display for DbcError {
             DbcError::RecordParsingError(msg) => {
                 write!(f, "DBF record parsing failed: {msg}")
             }
+            DbcError::SchemaFieldCountMismatch(expected, found) => {
+                write!(
+                    f,
+                    "Provided schema has {expected} fields but the DBF header declares {found}"
+                )
+            }
+            DbcError::UnknownUfs(ufs) => {
+                write!(f, "Unknown UF values: {}", ufs.join(", "))
+            }
+            DbcError::MissingMemoFile(path) => {
+                write!(f, "{} has memo fields but no .dbt or .fpt memo file was found next to it", path.display())
+            }
         }
     }
 }
@@ -164,6 +183,7 @@ mod tests {
             DbcError::MissingHeader("header missing".to_string()),
             DbcError::CompressionError("lzw failed".to_string()),
             DbcError::RecordParsingError("bad record".to_string()),
+            DbcError::SchemaFieldCountMismatch(3, 5),
         ];
 
         for err in errors {
@@ -0,0 +1,352 @@
+//! Small DBF fixtures written with dbase, shared by the reader tests
+
+use std::path::Path;
+
+use dbase::{FieldValue, Record, TableWriterBuilder};
+
+/// Write a DBF with the field types DATASUS files commonly use
+///
+/// Columns: `CODIGO` (C 10), `IDADE` (N 3,0), `VALOR` (N 12,2),
+/// `ATIVO` (L) and `DT_NASC` (D). The second record holds nulls.
+pub(crate) fn write_basic_dbf(path: &Path) {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("CODIGO".try_into().unwrap(), 10)
+        .add_numeric_field("IDADE".try_into().unwrap(), 3, 0)
+        .add_numeric_field("VALOR".try_into().unwrap(), 12, 2)
+        .add_logical_field("ATIVO".try_into().unwrap())
+        .add_date_field("DT_NASC".try_into().unwrap())
+        .build_with_file_dest(path)
+        .expect("failed to create DBF fixture");
+
+    let mut first = Record::default();
+    first.insert("CODIGO".to_string(), FieldValue::Character(Some("355030".to_string())));
+    first.insert("IDADE".to_string(), FieldValue::Numeric(Some(42.0)));
+    first.insert("VALOR".to_string(), FieldValue::Numeric(Some(1234.5)));
+    first.insert("ATIVO".to_string(), FieldValue::Logical(Some(true)));
+    first.insert("DT_NASC".to_string(), FieldValue::Date(Some(dbase::Date::new(15, 3, 1982))));
+
+    let mut second = Record::default();
+    second.insert("CODIGO".to_string(), FieldValue::Character(Some("330455".to_string())));
+    second.insert("IDADE".to_string(), FieldValue::Numeric(None));
+    second.insert("VALOR".to_string(), FieldValue::Numeric(Some(0.75)));
+    second.insert("ATIVO".to_string(), FieldValue::Logical(None));
+    second.insert("DT_NASC".to_string(), FieldValue::Date(None));
+
+    writer.write_records(&[first, second]).expect("failed to write DBF fixture");
+}
+
+/// Write a DBF with the FoxPro-only field types (Integer, Currency, Double, DateTime)
+pub(crate) fn write_foxpro_dbf(path: &Path) {
+    let mut writer = TableWriterBuilder::new()
+        .add_integer_field("QT_DIAS".try_into().unwrap())
+        .add_currency_field("VAL_TOT".try_into().unwrap())
+        .add_double_field("TAXA".try_into().unwrap())
+        .add_datetime_field("DT_PROC".try_into().unwrap())
+        .build_with_file_dest(path)
+        .expect("failed to create DBF fixture");
+
+    let mut record = Record::default();
+    record.insert("QT_DIAS".to_string(), FieldValue::Integer(7));
+    record.insert("VAL_TOT".to_string(), FieldValue::Currency(199.9));
+    record.insert("TAXA".to_string(), FieldValue::Double(0.125));
+    record.insert(
+        "DT_PROC".to_string(),
+        FieldValue::DateTime(dbase::DateTime::new(
+            dbase::Date::new(2, 1, 2020),
+            dbase::Time::new(13, 30, 5),
+        )),
+    );
+
+    writer.write_record(&record).expect("failed to write DBF fixture");
+}
+
+/// Write a DBF with `rows` records: `ID` (N 6,0) holding 0..rows and `UF` (C 2)
+pub(crate) fn write_numbered_dbf(path: &Path, rows: usize) {
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field("ID".try_into().unwrap(), 6, 0)
+        .add_character_field("UF".try_into().unwrap(), 2)
+        .build_with_file_dest(path)
+        .expect("failed to create DBF fixture");
+
+    let ufs = ["SP", "RJ", "MG"];
+    let records: Vec<Record> = (0..rows)
+        .map(|i| {
+            let mut record = Record::default();
+            record.insert("ID".to_string(), FieldValue::Numeric(Some(i as f64)));
+            record.insert("UF".to_string(), FieldValue::Character(Some(ufs[i % ufs.len()].to_string())));
+            record
+        })
+        .collect();
+
+    writer.write_records(&records).expect("failed to write DBF fixture");
+}
+
+/// Write a numbered DBF of `rows` records (see [`write_numbered_dbf`]) with record `deleted` flagged as deleted
+pub(crate) fn write_deleted_record_dbf(path: &Path, rows: usize, deleted: usize) {
+    write_numbered_dbf(path, rows);
+
+    let mut bytes = std::fs::read(path).expect("failed to read DBF fixture");
+    let header_size = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    let record_size = usize::from(u16::from_le_bytes([bytes[10], bytes[11]]));
+    bytes[header_size + deleted * record_size] = b'*';
+    std::fs::write(path, bytes).expect("failed to write DBF fixture");
+}
+
+/// Write a DBF by hand with a valid header and no records
+///
+/// Columns: `ID` (N 6,0), `UF` (C 2) and `VALOR` (N 12,2).
+pub(crate) fn write_header_only_dbf(path: &Path) {
+    let fields: [(&[u8], u8, u8, u8); 3] = [(b"ID", b'N', 6, 0), (b"UF", b'C', 2, 0), (b"VALOR", b'N', 12, 2)];
+    let header_size = (32 + 32 * fields.len() + 1) as u16;
+    let record_size = 1 + fields.iter().map(|(_, _, width, _)| u16::from(*width)).sum::<u16>();
+
+    let mut bytes = vec![0u8; 32];
+    bytes[0] = 0x03;
+    bytes[1..4].copy_from_slice(&[124, 1, 15]);
+    bytes[4..8].copy_from_slice(&0u32.to_le_bytes());
+    bytes[8..10].copy_from_slice(&header_size.to_le_bytes());
+    bytes[10..12].copy_from_slice(&record_size.to_le_bytes());
+
+    for (name, field_type, width, decimals) in fields {
+        let mut descriptor = [0u8; 32];
+        descriptor[..name.len()].copy_from_slice(name);
+        descriptor[11] = field_type;
+        descriptor[16] = width;
+        descriptor[17] = decimals;
+        bytes.extend_from_slice(&descriptor);
+    }
+    bytes.push(0x0D);
+    bytes.push(0x1A);
+
+    std::fs::write(path, bytes).expect("failed to write DBF fixture");
+}
+
+/// Write three records by hand, the second with an unparseable `DT`
+///
+/// Columns: `ID` (N 6,0), `UF` (C 2) and `DT` (D); the readable records are
+/// `(1, "SP")` and `(3, "MG")`.
+pub(crate) fn write_malformed_record_dbf(path: &Path) {
+    write_dated_rows_dbf(path, &[b"      1SP20240105", b"      2RJ2024AB05", b"      3MG20240305"]);
+}
+
+/// Write four records by hand: a deleted one at position 1 and an unparseable `DT` at position 2
+///
+/// Same columns as [`write_malformed_record_dbf`]; the readable records are
+/// `(1, "SP")` and `(4, "SP")`.
+pub(crate) fn write_deleted_then_malformed_dbf(path: &Path) {
+    write_dated_rows_dbf(
+        path,
+        &[b"      1SP20240105", b"*     2RJ20240205", b"      3MG2024AB05", b"      4SP20240405"],
+    );
+}
+
+/// Write `ID` (N 6,0), `UF` (C 2) and `DT` (D) records from raw bytes, deletion flag first
+fn write_dated_rows_dbf(path: &Path, rows: &[&[u8]]) {
+    let fields: [(&[u8], u8, u8); 3] = [(b"ID", b'N', 6), (b"UF", b'C', 2), (b"DT", b'D', 8)];
+    let header_size = (32 + 32 * fields.len() + 1) as u16;
+    let record_size = 1 + fields.iter().map(|(_, _, width)| u16::from(*width)).sum::<u16>();
+
+    let mut bytes = vec![0u8; 32];
+    bytes[0] = 0x03;
+    bytes[1..4].copy_from_slice(&[124, 1, 15]);
+    bytes[4..8].copy_from_slice(&(rows.len() as u32).to_le_bytes());
+    bytes[8..10].copy_from_slice(&header_size.to_le_bytes());
+    bytes[10..12].copy_from_slice(&record_size.to_le_bytes());
+
+    for (name, field_type, width) in fields {
+        let mut descriptor = [0u8; 32];
+        descriptor[..name.len()].copy_from_slice(name);
+        descriptor[11] = field_type;
+        descriptor[16] = width;
+        bytes.extend_from_slice(&descriptor);
+    }
+    bytes.push(0x0D);
+
+    for row in rows {
+        assert_eq!(row.len(), usize::from(record_size), "fixture row has the wrong width");
+        bytes.extend_from_slice(row);
+    }
+    bytes.push(0x1A);
+
+    std::fs::write(path, bytes).expect("failed to write DBF fixture");
+}
+
+/// Write one `VALOR` (N 8,2) field by hand, one record per raw value
+///
+/// The values are padded, signed, blank, a lone `.`, comma-separated and
+/// malformed: `12.5`, `3.25`, `-0.75`, null, null, `1.5`, null.
+pub(crate) fn write_decimal_dbf(path: &Path) {
+    let rows: [&[u8]; 7] = [b"   12.50", b"   +3.25", b"  - 0.75", b"        ", b"       .", b"    1,50", b"   1x.2!"];
+    let header_size: u16 = 32 + 32 + 1;
+    let record_size: u16 = 1 + 8;
+
+    let mut bytes = vec![0u8; 32];
+    bytes[0] = 0x03;
+    bytes[1..4].copy_from_slice(&[124, 1, 15]);
+    bytes[4..8].copy_from_slice(&(rows.len() as u32).to_le_bytes());
+    bytes[8..10].copy_from_slice(&header_size.to_le_bytes());
+    bytes[10..12].copy_from_slice(&record_size.to_le_bytes());
+
+    let mut descriptor = [0u8; 32];
+    descriptor[..5].copy_from_slice(b"VALOR");
+    descriptor[11] = b'N';
+    descriptor[16] = 8;
+    descriptor[17] = 2;
+    bytes.extend_from_slice(&descriptor);
+    bytes.push(0x0D);
+
+    for row in rows {
+        bytes.push(b' ');
+        bytes.extend_from_slice(row);
+    }
+    bytes.push(0x1A);
+
+    std::fs::write(path, bytes).expect("failed to write DBF fixture");
+}
+
+/// Write a single-record DBF by hand with one `MUNIC` (C 20) field holding raw `text` bytes
+///
+/// Bypasses dbase's encoder so tests control both the bytes on disk and
+/// the language driver ID (header byte 29).
+pub(crate) fn write_encoded_dbf(path: &Path, language_driver: u8, text: &[u8]) {
+    const WIDTH: usize = 20;
+    let header_size: u16 = 32 + 32 + 1;
+    let record_size: u16 = 1 + WIDTH as u16;
+
+    let mut bytes = vec![0u8; 32];
+    bytes[0] = 0x03;
+    bytes[1..4].copy_from_slice(&[124, 1, 15]);
+    bytes[4..8].copy_from_slice(&1u32.to_le_bytes());
+    bytes[8..10].copy_from_slice(&header_size.to_le_bytes());
+    bytes[10..12].copy_from_slice(&record_size.to_le_bytes());
+    bytes[29] = language_driver;
+
+    let mut descriptor = [0u8; 32];
+    descriptor[..5].copy_from_slice(b"MUNIC");
+    descriptor[11] = b'C';
+    descriptor[16] = WIDTH as u8;
+    bytes.extend_from_slice(&descriptor);
+    bytes.push(0x0D);
+
+    let mut field = text.to_vec();
+    field.resize(WIDTH, b' ');
+    bytes.push(b' ');
+    bytes.extend_from_slice(&field);
+    bytes.push(0x1A);
+
+    std::fs::write(path, bytes).expect("failed to write DBF fixture");
+}
+
+/// Write a dBase III DBF with memo fields plus its memo file at `memo_path`
+///
+/// Columns: `ID` (N 4,0) and `OBS` (M 10). Each entry of `memos` becomes a
+/// record whose `OBS` points at its own 512-byte block in the memo file;
+/// `None` leaves the block number blank (an empty memo).
+pub(crate) fn write_memo_dbf(path: &Path, memo_path: &Path, memos: &[Option<&str>]) {
+    const BLOCK_SIZE: usize = 512;
+    let header_size: u16 = 32 + 32 * 2 + 1;
+    let record_size: u16 = 1 + 4 + 10;
+
+    let mut bytes = vec![0u8; 32];
+    // dBase III with a memo file
+    bytes[0] = 0x83;
+    bytes[1..4].copy_from_slice(&[124, 1, 15]);
+    bytes[4..8].copy_from_slice(&(memos.len() as u32).to_le_bytes());
+    bytes[8..10].copy_from_slice(&header_size.to_le_bytes());
+    bytes[10..12].copy_from_slice(&record_size.to_le_bytes());
+
+    for (name, field_type, width) in [(&b"ID"[..], b'N', 4u8), (&b"OBS"[..], b'M', 10u8)] {
+        let mut descriptor = [0u8; 32];
+        descriptor[..name.len()].copy_from_slice(name);
+        descriptor[11] = field_type;
+        descriptor[16] = width;
+        bytes.extend_from_slice(&descriptor);
+    }
+    bytes.push(0x0D);
+
+    // Block 0 is the header: next free block, then the block size (0 = 512)
+    let mut memo_bytes = vec![0u8; BLOCK_SIZE];
+    let mut next_block = 1u32;
+    for (i, memo) in memos.iter().enumerate() {
+        let block = match memo {
+            Some(text) => {
+                let mut data = text.as_bytes().to_vec();
+                data.extend_from_slice(&[0x1A, 0x1A]);
+                data.resize(data.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+                let block = next_block;
+                next_block += (data.len() / BLOCK_SIZE) as u32;
+                memo_bytes.extend_from_slice(&data);
+                format!("{:>10}", block)
+            }
+            None => " ".repeat(10),
+        };
+        bytes.push(b' ');
+        bytes.extend_from_slice(format!("{:>4}", i).as_bytes());
+        bytes.extend_from_slice(block.as_bytes());
+    }
+    bytes.push(0x1A);
+    memo_bytes[..4].copy_from_slice(&next_block.to_le_bytes());
+
+    std::fs::write(path, bytes).expect("failed to write DBF fixture");
+    std::fs::write(memo_path, memo_bytes).expect("failed to write memo fixture");
+}
+
+/// Compress `data` as a PKWARE DCL ("implode") stream made only of uncoded literals
+///
+/// Enough for DBC fixtures: explode doesn't care whether the compressor
+/// found any repeats.
+fn implode_literals(data: &[u8]) -> Vec<u8> {
+    // Header: literals stored uncoded, 4 KiB dictionary
+    let mut out = vec![0u8, 6];
+
+    // Bits are packed starting from the least significant bit of each byte
+    let mut bits: Vec<bool> = Vec::with_capacity(data.len() * 9 + 16);
+    for &byte in data {
+        bits.push(false);
+        bits.extend((0..8).map(|i| (byte >> i) & 1 == 1));
+    }
+    // End of stream is a length of 519: length symbol 15 (Huffman code
+    // 1111111, stored inverted) followed by 8 extra bits holding 255
+    bits.push(true);
+    bits.extend([false; 7]);
+    bits.extend([true; 8]);
+
+    for chunk in bits.chunks(8) {
+        out.push(chunk.iter().enumerate().fold(0u8, |acc, (i, &bit)| acc | (u8::from(bit) << i)));
+    }
+    out
+}
+
+/// Write a DBC holding the DBF at `dbf_path`: its header, a 4-byte CRC32, then the imploded records
+pub(crate) fn write_dbc_from_dbf(dbf_path: &Path, dbc_path: &Path) {
+    let dbf = std::fs::read(dbf_path).expect("failed to read DBF fixture");
+    let header_size = usize::from(u16::from_le_bytes([dbf[8], dbf[9]]));
+
+    let mut dbc = dbf[..header_size].to_vec();
+    // The CRC isn't checked when reading
+    dbc.extend_from_slice(&[0u8; 4]);
+    dbc.extend_from_slice(&implode_literals(&dbf[header_size..]));
+
+    std::fs::write(dbc_path, dbc).expect("failed to write DBC fixture");
+}
+
+/// Write a code table DBF like the auxiliary tables DATASUS ships: `CO_MUNIC` (C 6) and `DS_MUNIC` (C 40)
+pub(crate) fn write_code_table_dbf(path: &Path, entries: &[(&str, &str)]) {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("CO_MUNIC".try_into().unwrap(), 6)
+        .add_character_field("DS_MUNIC".try_into().unwrap(), 40)
+        .build_with_file_dest(path)
+        .expect("failed to create DBF fixture");
+
+    let records: Vec<Record> = entries
+        .iter()
+        .map(|(code, label)| {
+            let mut record = Record::default();
+            record.insert("CO_MUNIC".to_string(), FieldValue::Character(Some(code.to_string())));
+            record.insert("DS_MUNIC".to_string(), FieldValue::Character(Some(label.to_string())));
+            record
+        })
+        .collect();
+
+    writer.write_records(&records).expect("failed to write DBF fixture");
+}
@@ -6,13 +6,33 @@
 pub mod error;
 pub mod des;
 pub mod scan;
+pub mod sample;
+pub mod describe;
+
+#[cfg(test)]
+pub(crate) mod fixtures;
 
 pub use error::{DbcError, DbcResult};
 pub use des::{
     arrow_schema_to_polars, dbf_header_to_polars_schema, dbf_header_to_polars_schema_with_metadata,
+    dbf_header_to_polars_schema_with_metadata_and_options, dbf_header_to_polars_schema_with_options,
     dbc_to_polars_schema, create_dbf_reader_from_file,
+    create_dbf_reader_with_encoding, create_dbf_reader_with_memo, open_with_retries, is_transient_open_error, parse_dbf_numeric,
 };
 pub use scan::{
-    DbcScanner, DbcConfig, read_dbc, read_dbc_with_config, read_dbc_columns, scan_dbc_lazy,
-    read_dbf, read_dbf_columns, scan_dbf_lazy, scan_dbc, scan_dbf,
+    DbcScanner, DbcConfig, DbcConfigBuilder, read_dbc, read_dbc_with_config, read_dbc_columns, scan_dbc_lazy,
+    read_dbf, read_dbf_columns, scan_dbf_lazy, scan_dbc, scan_dbf, read_dbc_with_bar, read_dbf_with_bar,
+    DbcReadResult, estimate_dataframe_memory, shrink_integer_columns, read_dbf_with_config,
+    ParallelizationStrategy, determine_parallelization_strategy, set_max_threads, max_threads,
+    head_dbc, head_dbf, TrimPolicy, RecordErrorPolicy, RecordError, null_numeric_sentinels,
+    quick_scan_dbase, read_dbf_rows, dbase_row_count_fast, verify_dbase_file, read_common_columns,
+    BoundedRead, read_dbase_bounded,
+    ParquetConversionResult, convert_dbc_to_parquet_streaming, convert_dbf_to_parquet_streaming, dbc_to_dbf_file,
+};
+#[cfg(feature = "mmap")]
+pub use scan::read_dbase_mmap;
+pub use sample::{SampleConfig, create_sample_from_schema, create_sample_from_dbase_schema};
+pub use describe::{
+    DbaseFieldInfo, DbaseFileSummary, DescribeConfig, describe_dbase_file, describe_dbase_file_json, describe_many,
+    get_dbase_fields, common_columns,
 };
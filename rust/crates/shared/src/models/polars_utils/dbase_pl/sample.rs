@@ -0,0 +1,187 @@
+//! Synthetic DataFrames matching a DBF layout, for testing pipelines without real files
+
+use std::path::Path;
+
+use polars::prelude::{DataFrame, DataType, PlSmallStr, Schema as PlSchema, Series, TimeUnit};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::des::{dbc_to_polars_schema, dbf_header_to_polars_schema};
+use super::error::DbcResult;
+use crate::models::dbase_utils::is_dbc_file;
+
+/// Controls for sample generation
+#[derive(Debug, Clone)]
+pub struct SampleConfig {
+    /// Number of rows to generate (default: 10)
+    pub rows: usize,
+    /// Probability in `[0, 1]` that any value is null (default: 0.0)
+    pub null_fraction: f64,
+    /// RNG seed; the same seed and schema always yield the same frame (default: 42)
+    pub seed: u64,
+}
+
+impl Default for SampleConfig {
+    fn default() -> Self {
+        Self {
+            rows: 10,
+            null_fraction: 0.0,
+            seed: 42,
+        }
+    }
+}
+
+/// Build a sample DataFrame with random values for every column in `schema`
+pub fn create_sample_from_schema(schema: &PlSchema, config: &SampleConfig) -> DbcResult<DataFrame> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let null_fraction = config.null_fraction.clamp(0.0, 1.0);
+
+    let mut columns = Vec::with_capacity(schema.len());
+    for (name, dtype) in schema.iter() {
+        let series = sample_series(name, dtype, config.rows, null_fraction, &mut rng)?;
+        columns.push(series.into());
+    }
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Build a sample DataFrame matching the layout of a DBF or DBC file
+pub fn create_sample_from_dbase_schema<P: AsRef<Path>>(
+    file_path: P,
+    config: &SampleConfig,
+) -> DbcResult<DataFrame> {
+    let schema = if is_dbc_file(file_path.as_ref()) {
+        dbc_to_polars_schema(file_path, None)?
+    } else {
+        dbf_header_to_polars_schema(file_path, None)?
+    };
+
+    create_sample_from_schema(&schema, config)
+}
+
+/// Generate one column of random values for a dtype
+fn sample_series(
+    name: &PlSmallStr,
+    dtype: &DataType,
+    rows: usize,
+    null_fraction: f64,
+    rng: &mut StdRng,
+) -> DbcResult<Series> {
+    // Draw the null mask first so it doesn't depend on the value generator
+    let mask: Vec<bool> = (0..rows).map(|_| rng.gen_bool(null_fraction)).collect();
+
+    let series = match dtype {
+        DataType::Boolean => {
+            let values: Vec<Option<bool>> = mask.iter().map(|&null| (!null).then(|| rng.gen_bool(0.5))).collect();
+            Series::new(name.clone(), values)
+        }
+        DataType::Int8 | DataType::Int16 => {
+            let values: Vec<Option<i16>> = mask.iter().map(|&null| (!null).then(|| rng.gen_range(0..100))).collect();
+            Series::new(name.clone(), values).cast(dtype)?
+        }
+        DataType::Int32 => {
+            let values: Vec<Option<i32>> = mask.iter().map(|&null| (!null).then(|| rng.gen_range(0..100_000))).collect();
+            Series::new(name.clone(), values)
+        }
+        DataType::Int64 => {
+            let values: Vec<Option<i64>> = mask.iter().map(|&null| (!null).then(|| rng.gen_range(0..10_000_000))).collect();
+            Series::new(name.clone(), values)
+        }
+        DataType::Float32 | DataType::Float64 => {
+            let values: Vec<Option<f64>> = mask
+                .iter()
+                .map(|&null| (!null).then(|| (rng.gen_range(0.0..10_000.0_f64) * 100.0).round() / 100.0))
+                .collect();
+            Series::new(name.clone(), values).cast(dtype)?
+        }
+        DataType::Date => {
+            // 2000-01-01 .. 2024-12-31 as days since the epoch
+            let values: Vec<Option<i32>> = mask.iter().map(|&null| (!null).then(|| rng.gen_range(10_957..20_089))).collect();
+            Series::new(name.clone(), values).cast(&DataType::Date)?
+        }
+        DataType::Datetime(_, tz) => {
+            let values: Vec<Option<i64>> = mask
+                .iter()
+                .map(|&null| (!null).then(|| rng.gen_range(946_684_800_000_i64..1_735_689_600_000)))
+                .collect();
+            Series::new(name.clone(), values).cast(&DataType::Datetime(TimeUnit::Milliseconds, tz.clone()))?
+        }
+        _ => {
+            // Digit strings, like the coded fields in DATASUS files
+            let values: Vec<Option<String>> = mask
+                .iter()
+                .map(|&null| (!null).then(|| format!("{:06}", rng.gen_range(0..1_000_000))))
+                .collect();
+            Series::new(name.clone(), values)
+        }
+    };
+
+    Ok(series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::fixtures;
+
+    fn sample_schema() -> PlSchema {
+        PlSchema::from_iter([
+            ("MUNIC_RES".into(), DataType::String),
+            ("IDADE".into(), DataType::Int16),
+            ("VAL_TOT".into(), DataType::Float64),
+            ("DT_INTER".into(), DataType::Date),
+            ("MORTE".into(), DataType::Boolean),
+        ])
+    }
+
+    #[test]
+    fn test_same_seed_yields_identical_frames() {
+        let config = SampleConfig {
+            rows: 50,
+            null_fraction: 0.2,
+            seed: 7,
+        };
+        let first = create_sample_from_schema(&sample_schema(), &config).unwrap();
+        let second = create_sample_from_schema(&sample_schema(), &config).unwrap();
+        assert!(first.equals_missing(&second));
+
+        let other = create_sample_from_schema(&sample_schema(), &SampleConfig { seed: 8, ..config }).unwrap();
+        assert!(!first.equals_missing(&other));
+    }
+
+    #[test]
+    fn test_sample_matches_schema() {
+        let config = SampleConfig { rows: 25, ..Default::default() };
+        let df = create_sample_from_schema(&sample_schema(), &config).unwrap();
+
+        assert_eq!(df.height(), 25);
+        assert_eq!(df.schema().as_ref(), &sample_schema());
+        assert_eq!(df.column("IDADE").unwrap().null_count(), 0);
+    }
+
+    #[test]
+    fn test_null_fraction_extremes() {
+        let all_null = SampleConfig { rows: 20, null_fraction: 1.0, seed: 1 };
+        let df = create_sample_from_schema(&sample_schema(), &all_null).unwrap();
+        for column in df.get_columns() {
+            assert_eq!(column.null_count(), 20);
+        }
+
+        // Out-of-range fractions are clamped rather than panicking
+        let clamped = SampleConfig { rows: 5, null_fraction: -3.0, seed: 1 };
+        let df = create_sample_from_schema(&sample_schema(), &clamped).unwrap();
+        assert_eq!(df.column("MUNIC_RES").unwrap().null_count(), 0);
+    }
+
+    #[test]
+    fn test_sample_from_dbf_layout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let df = create_sample_from_dbase_schema(&path, &SampleConfig::default()).unwrap();
+        let expected = dbf_header_to_polars_schema(&path, None).unwrap();
+        assert_eq!(df.height(), 10);
+        assert_eq!(df.schema().as_ref(), &expected);
+    }
+}
@@ -1,28 +1,204 @@
 //! Ultra-fast DBC scanner with maximum performance defaults and LazyFrame support
 
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::io::BufReader;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
-use dbase::{Reader, Record};
-use polars::prelude::{DataFrame, Series, LazyFrame, Schema as PlSchema, PlSmallStr, IntoLazy};
+use dbase::{FieldValue, Reader, Record};
+use indicatif::{ProgressBar, ProgressStyle};
+use polars::prelude::{DataFrame, Series, LazyFrame, Schema as PlSchema, PlSmallStr, IntoLazy, ParquetWriter};
+use tokio_util::sync::CancellationToken;
 
 use super::error::{DbcError, DbcResult};
-use super::des::{dbc_to_polars_schema, create_dbf_reader_from_file};
-use crate::models::dbase_utils::decompress_dbc_to_dbf;
+use super::describe::common_columns;
+use super::des::{
+    create_dbf_reader_with_memo, dbf_header_to_polars_schema_with_options, open_with_retries, parse_dbf_numeric,
+};
+use crate::models::dbase_utils::{
+    dbc_to_dbf_reader, decompress_dbc_head_to_dbf, decompress_dbc_to_dbf, decompress_dbc_to_dbf_with_mode,
+    extract_dbf_record_window, find_memo_file, is_dbc_file, read_dbf_field_descriptors, read_dbf_language_driver,
+    undelete_dbf_records, DbaseEncoding, DbcHeaderReport, DbcParseMode, LiveRecordPositions,
+};
+
+/// Files with fewer records than this are converted sequentially
+const PARALLEL_RECORD_THRESHOLD: usize = 1_000;
+
+/// Records read between checks of a bounded read's deadline and cancellation token
+const INTERRUPT_CHECK_INTERVAL: usize = 1_024;
+
+/// Global reader thread limit (0 = use all available)
+static MAX_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Rayon pools keyed by thread count, built once and reused across reads
+static THREAD_POOLS: Lazy<Mutex<HashMap<usize, Arc<rayon::ThreadPool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Limit the number of threads used by the DBC/DBF readers (None = all available)
+pub fn set_max_threads(max_threads: Option<usize>) {
+    MAX_THREADS.store(max_threads.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Current global reader thread limit, if any
+pub fn max_threads() -> Option<usize> {
+    match MAX_THREADS.load(Ordering::Relaxed) {
+        0 => None,
+        n => Some(n),
+    }
+}
+
+/// How records are converted into columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParallelizationStrategy {
+    /// Convert on the calling thread
+    Sequential,
+    /// Convert in chunks on a shared rayon pool
+    Parallel { threads: usize },
+}
+
+/// Pick a strategy from the record count and thread limit
+///
+/// `max_threads` overrides the global limit set with [`set_max_threads`];
+/// without either, all rayon threads are used.
+pub fn determine_parallelization_strategy(
+    record_count: usize,
+    max_threads: Option<usize>,
+) -> ParallelizationStrategy {
+    let threads = max_threads
+        .or_else(self::max_threads)
+        .unwrap_or_else(rayon::current_num_threads)
+        .max(1);
+
+    if threads == 1 || record_count < PARALLEL_RECORD_THRESHOLD {
+        ParallelizationStrategy::Sequential
+    } else {
+        ParallelizationStrategy::Parallel { threads }
+    }
+}
+
+/// Get (or build once) the rayon pool for a thread count
+pub(crate) fn thread_pool(threads: usize) -> DbcResult<Arc<rayon::ThreadPool>> {
+    let mut pools = THREAD_POOLS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(pool) = pools.get(&threads) {
+        return Ok(pool.clone());
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("dbc-reader-{}", i))
+        .build()
+        .map_err(|e| DbcError::InvalidDbcFormat(format!("Failed to build thread pool: {}", e)))?;
+    let pool = Arc::new(pool);
+    pools.insert(threads, pool.clone());
+    Ok(pool)
+}
+
+/// Merge per-chunk column vectors into one vector per field
+fn merge_chunks(
+    chunked_columns: Vec<Vec<Vec<Option<String>>>>,
+    num_fields: usize,
+    total_records: usize,
+) -> Vec<Vec<Option<String>>> {
+    let mut final_columns: Vec<Vec<Option<String>>> = (0..num_fields)
+        .map(|_| Vec::with_capacity(total_records))
+        .collect();
+
+    for chunk_columns in chunked_columns {
+        for (field_idx, mut chunk_values) in chunk_columns.into_iter().enumerate() {
+            final_columns[field_idx].append(&mut chunk_values);
+        }
+    }
+
+    final_columns
+}
+
+/// Whitespace trimming applied to character fields during conversion
+///
+/// DBF character fields are space-padded to their declared width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrimPolicy {
+    /// Keep values as read, for fields where spacing is significant
+    None,
+    /// Strip trailing padding
+    #[default]
+    Trailing,
+    /// Strip leading and trailing whitespace
+    Both,
+}
+
+impl TrimPolicy {
+    /// Apply the policy to a single value
+    pub fn apply(self, value: &str) -> &str {
+        match self {
+            TrimPolicy::None => value,
+            TrimPolicy::Trailing => value.trim_end(),
+            TrimPolicy::Both => value.trim(),
+        }
+    }
+}
+
+/// What the readers do with a record the dbase reader can't parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordErrorPolicy {
+    /// Abort the read with `DbcError::RecordParsingError`
+    Fail,
+    /// Drop the record and count it (logged as a warning)
+    #[default]
+    Skip,
+    /// Drop the record and keep its index and error message
+    Collect,
+}
+
+/// A record dropped under [`RecordErrorPolicy::Collect`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordError {
+    /// Zero-based position of the record in the file
+    pub index: usize,
+    /// Why the record couldn't be parsed
+    pub message: String,
+}
 
 /// Performance configuration with optimal defaults
 #[derive(Debug, Clone)]
 pub struct DbcConfig {
     /// Chunk size for parallel processing (auto-tuned by default)
     pub chunk_size: usize,
-    /// Number of parallel threads (uses all available by default)
+    /// Per-call thread limit, overriding `set_max_threads` (uses all available by default)
     pub num_threads: Option<usize>,
     /// Columns to select (None = all columns)
     pub columns: Option<Vec<String>>,
     /// Memory limit per chunk in MB (default: 100MB)
     pub memory_limit_mb: usize,
+    /// Narrow integer numerics to Int16/Int32 based on declared width (default: true)
+    pub shrink_numeric_types: bool,
+    /// Schema to use instead of inferring one from each header (None = infer)
+    ///
+    /// Lets batch jobs over identically-structured files infer once and reuse it.
+    /// Only the field count is checked against the header.
+    pub provided_schema: Option<Arc<PlSchema>>,
+    /// Encoding of character fields (None = from the header's language driver, else Latin-1)
+    pub encoding: Option<DbaseEncoding>,
+    /// Whitespace trimming for string columns (default: trailing)
+    pub trim_strings: TrimPolicy,
+    /// Turn blank string values (empty once trimmed) into nulls, whatever the trim policy (default: false)
+    pub empty_as_null: bool,
+    /// Numeric values meaning "ignored/unknown" (e.g. `9999`), nulled by `read_with_report` (default: none)
+    pub numeric_sentinels: Vec<f64>,
+    /// Column renames (source name -> new name) applied right after reading (default: none)
+    pub rename: HashMap<String, String>,
+    /// Handling of malformed records (default: skip them)
+    pub on_record_error: RecordErrorPolicy,
+    /// Retries when the file is transiently locked on open, e.g. right after a download (default: 3)
+    pub open_retries: u32,
+    /// Keep records flagged as deleted instead of dropping them (default: false)
+    pub include_deleted: bool,
+    /// How strictly a DBC header's declared size is checked (default: strict)
+    pub parse_mode: DbcParseMode,
 }
 
 impl Default for DbcConfig {
@@ -36,15 +212,270 @@ impl Default for DbcConfig {
             num_threads: None, // Use all available
             columns: None,     // Read all columns
             memory_limit_mb: 100,
+            shrink_numeric_types: true,
+            provided_schema: None,
+            encoding: None,
+            trim_strings: TrimPolicy::default(),
+            empty_as_null: false,
+            numeric_sentinels: Vec::new(),
+            rename: HashMap::new(),
+            on_record_error: RecordErrorPolicy::default(),
+            open_retries: 3,
+            include_deleted: false,
+            parse_mode: DbcParseMode::default(),
+        }
+    }
+}
+
+impl DbcConfig {
+    /// Start from the defaults and set only what differs
+    ///
+    /// ```rust
+    /// use shared::models::polars_utils::dbase_pl::DbcConfig;
+    ///
+    /// let config = DbcConfig::builder().columns(["UF_ZI", "ANO_CMPT"]).empty_as_null(true).build();
+    /// assert_eq!(config.columns, Some(vec!["UF_ZI".to_string(), "ANO_CMPT".to_string()]));
+    /// ```
+    pub fn builder() -> DbcConfigBuilder {
+        DbcConfigBuilder::default()
+    }
+}
+
+/// Chainable setters for [`DbcConfig`], from [`DbcConfig::builder`]
+#[derive(Debug, Clone, Default)]
+pub struct DbcConfigBuilder {
+    config: DbcConfig,
+}
+
+impl DbcConfigBuilder {
+    /// Records per parallel chunk
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.config.chunk_size = chunk_size;
+        self
+    }
+
+    /// Threads for this read, overriding `set_max_threads`
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.config.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Read only these columns
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Memory limit per chunk, in MB
+    pub fn memory_limit_mb(mut self, memory_limit_mb: usize) -> Self {
+        self.config.memory_limit_mb = memory_limit_mb;
+        self
+    }
+
+    /// Whether integer numerics are narrowed to Int16/Int32
+    pub fn shrink_numeric_types(mut self, shrink: bool) -> Self {
+        self.config.shrink_numeric_types = shrink;
+        self
+    }
+
+    /// Use `schema` instead of inferring one from the header
+    pub fn provided_schema(mut self, schema: Arc<PlSchema>) -> Self {
+        self.config.provided_schema = Some(schema);
+        self
+    }
+
+    /// Decode character fields with `encoding` whatever the header says
+    pub fn encoding(mut self, encoding: DbaseEncoding) -> Self {
+        self.config.encoding = Some(encoding);
+        self
+    }
+
+    /// Whitespace trimming for string columns
+    pub fn trim_strings(mut self, policy: TrimPolicy) -> Self {
+        self.config.trim_strings = policy;
+        self
+    }
+
+    /// Whether blank string values become nulls
+    pub fn empty_as_null(mut self, empty_as_null: bool) -> Self {
+        self.config.empty_as_null = empty_as_null;
+        self
+    }
+
+    /// Numeric values meaning "ignored/unknown"
+    pub fn numeric_sentinels(mut self, sentinels: impl IntoIterator<Item = f64>) -> Self {
+        self.config.numeric_sentinels = sentinels.into_iter().collect();
+        self
+    }
+
+    /// Rename column `from` to `to` after reading (may be called once per column)
+    pub fn rename(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.config.rename.insert(from.into(), to.into());
+        self
+    }
+
+    /// Handling of malformed records
+    pub fn on_record_error(mut self, policy: RecordErrorPolicy) -> Self {
+        self.config.on_record_error = policy;
+        self
+    }
+
+    /// Retries when the file is transiently locked on open
+    pub fn open_retries(mut self, retries: u32) -> Self {
+        self.config.open_retries = retries;
+        self
+    }
+
+    /// Whether records flagged as deleted are kept
+    pub fn include_deleted(mut self, include_deleted: bool) -> Self {
+        self.config.include_deleted = include_deleted;
+        self
+    }
+
+    /// How strictly a DBC header's declared size is checked
+    pub fn parse_mode(mut self, mode: DbcParseMode) -> Self {
+        self.config.parse_mode = mode;
+        self
+    }
+
+    /// The finished configuration
+    pub fn build(self) -> DbcConfig {
+        self.config
+    }
+}
+
+/// DataFrame read from a DBC/DBF file plus a report of how it was produced
+#[derive(Debug, Clone)]
+pub struct DbcReadResult {
+    /// The data read from the file
+    pub dataframe: DataFrame,
+    /// Estimated in-memory size of the frame as read
+    pub memory_before_bytes: usize,
+    /// Estimated in-memory size after integer columns were shrunk
+    pub memory_after_bytes: usize,
+    /// Number of string values changed by the trim policy
+    pub trimmed_values: usize,
+    /// Sentinel values replaced with null, per column (columns with none are omitted)
+    pub sentinels_nulled: HashMap<String, usize>,
+    /// Malformed records dropped under the `Skip` or `Collect` policy
+    pub skipped_records: usize,
+    /// The dropped records, filled only under `RecordErrorPolicy::Collect`
+    pub record_errors: Vec<RecordError>,
+    /// Records flagged as deleted that were left out (always 0 with `include_deleted`)
+    pub deleted_records: usize,
+    /// Unparseable numeric values read as null instead of failing their record
+    pub invalid_numeric_values: usize,
+    /// Encoding character fields were decoded with (the configured one when overridden)
+    pub detected_encoding: DbaseEncoding,
+    /// Language driver ID from the header (byte 29), None if the header couldn't be read
+    pub language_driver_id: Option<u8>,
+    /// Outcome of the DBC header check (None when reading a DBF)
+    pub dbc_header: Option<DbcHeaderReport>,
+}
+
+impl DbcReadResult {
+    /// Whether trimming changed any string value
+    pub fn strings_trimmed(&self) -> bool {
+        self.trimmed_values > 0
+    }
+
+    /// Bytes saved by the optimization pass, measured on the in-memory frame
+    pub fn memory_saved_bytes(&self) -> usize {
+        self.memory_before_bytes.saturating_sub(self.memory_after_bytes)
+    }
+}
+
+/// Summary of a streaming Parquet conversion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParquetConversionResult {
+    /// Records written to the Parquet file
+    pub rows_written: u64,
+    /// Batches read and written, one row group each
+    pub batches: usize,
+    /// Size of the Parquet file on disk
+    pub output_size_bytes: u64,
+}
+
+/// A record read with its unparseable numbers left out for a second look
+///
+/// dbase rejects a whole record over one number `str::parse` refuses, such as
+/// `1,50`. Such fields are skipped instead and `unparsed` keeps their
+/// positions in declaration order, for [`reparse_numeric_fields`] to parse
+/// from the raw record.
+struct LenientRecord {
+    record: Record,
+    unparsed: Vec<usize>,
+}
+
+impl dbase::ReadableRecord for LenientRecord {
+    fn read_using<Source, MemoSource>(
+        fields: &mut dbase::FieldIterator<Source, MemoSource>,
+    ) -> Result<Self, dbase::FieldIOError>
+    where
+        Source: std::io::Read + std::io::Seek,
+        MemoSource: std::io::Read + std::io::Seek,
+    {
+        let mut record = Record::default();
+        let mut unparsed = Vec::new();
+        // A field's bytes are consumed even when they don't parse, so the next read is the next field
+        for position in 0.. {
+            match fields.read_next_field() {
+                Ok(dbase::NamedValue { name, value }) => {
+                    record.insert(name.to_string(), value);
+                }
+                Err(e) => match e.kind() {
+                    dbase::ErrorKind::EndOfRecord => break,
+                    dbase::ErrorKind::ParseFloatError(_) => unparsed.push(position),
+                    _ => return Err(e),
+                },
+            }
         }
+        Ok(Self { record, unparsed })
     }
 }
 
+/// A field's name, type and byte range within a raw record
+struct RawField {
+    name: String,
+    field_type: dbase::FieldType,
+    span: std::ops::Range<usize>,
+}
+
 /// Ultra-fast scanner leveraging existing utilities
 pub struct DbcScanner {
     dbf_path: std::path::PathBuf,
     schema: Arc<PlSchema>,
     config: DbcConfig,
+    encoding: DbaseEncoding,
+    /// Language driver ID declared by the header
+    language_driver_id: Option<u8>,
+    /// Memo file holding the text of memo fields, if the file declares any
+    memo_path: Option<PathBuf>,
+    /// Outcome of the DBC header check, for scanners built from a DBC
+    dbc_header: Option<DbcHeaderReport>,
+    /// String values changed by trimming during the last conversion
+    trimmed_values: AtomicUsize,
+    /// Malformed records dropped during the last read
+    skipped_records: AtomicUsize,
+    /// Dropped records kept during the last read under `RecordErrorPolicy::Collect`
+    record_errors: Mutex<Vec<RecordError>>,
+    /// Records flagged as deleted left out of the last read
+    deleted_records: AtomicUsize,
+    /// Unparseable numeric values nulled during the last read
+    invalid_numeric_values: AtomicUsize,
+}
+
+/// Frame from [`DbcScanner::read_bounded`], possibly cut short
+#[derive(Debug, Clone)]
+pub struct BoundedRead {
+    /// Records read before the read finished or was interrupted
+    pub dataframe: DataFrame,
+    /// Whether the deadline passed or the read was cancelled before the end of the file
+    pub truncated: bool,
 }
 
 impl DbcScanner {
@@ -55,29 +486,39 @@ impl DbcScanner {
     ) -> DbcResult<Self> {
         let config = config.unwrap_or_default();
         
-        // Set rayon thread pool if specified
-        if let Some(num_threads) = config.num_threads {
-            rayon::ThreadPoolBuilder::new()
-                .num_threads(num_threads)
-                .build_global()
-                .map_err(|e| DbcError::InvalidDbcFormat(format!("Failed to set thread pool: {}", e)))?;
-        }
-        
-        // Get schema using existing utility
-        let schema = Arc::new(dbc_to_polars_schema(&dbc_path, None)?);
-        
         // Create temp DBF file  
         let temp_dbf = tempfile::NamedTempFile::new()
             .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
         
-        // Decompress using existing utility
-        decompress_dbc_to_dbf(&dbc_path, temp_dbf.path())?;
+        // Decompress once, then read the schema from the decompressed header
+        let dbc_header = decompress_dbc_to_dbf_with_mode(&dbc_path, temp_dbf.path(), config.parse_mode)?;
+        if dbc_header.recovered() {
+            log::warn!(
+                "{}: recovered DBC header size {} (declared {}): {}",
+                dbc_path.as_ref().display(),
+                dbc_header.header_size,
+                dbc_header.declared_header_size,
+                dbc_header.anomalies.join("; ")
+            );
+        }
+        let schema = Self::resolve_schema(temp_dbf.path(), &config)?;
+        let (encoding, language_driver_id) = Self::resolve_encoding(temp_dbf.path(), &config)?;
+        let memo_path = Self::resolve_memo_file(temp_dbf.path(), dbc_path.as_ref())?;
         
         Ok(Self {
             dbf_path: temp_dbf.into_temp_path().keep()
                 .map_err(|e| DbcError::IO(e.error, "keeping temp file".to_string()))?,
             schema,
             config,
+            encoding,
+            language_driver_id,
+            memo_path,
+            dbc_header: Some(dbc_header),
+            trimmed_values: AtomicUsize::new(0),
+            skipped_records: AtomicUsize::new(0),
+            record_errors: Mutex::new(Vec::new()),
+            deleted_records: AtomicUsize::new(0),
+            invalid_numeric_values: AtomicUsize::new(0),
         })
     }
 
@@ -88,21 +529,107 @@ impl DbcScanner {
     ) -> DbcResult<Self> {
         let config = config.unwrap_or_default();
         
+        // Wait out a transient lock before reading the header
+        open_with_retries(config.open_retries, || {
+            std::fs::File::open(dbf_path.as_ref())
+                .map_err(|e| DbcError::io_error(e, dbf_path.as_ref().display().to_string()))
+        })?;
+
         // Get schema using existing utility
-        let schema = Arc::new(super::des::dbf_header_to_polars_schema(&dbf_path, None)?);
+        let schema = Self::resolve_schema(dbf_path.as_ref(), &config)?;
+        let (encoding, language_driver_id) = Self::resolve_encoding(dbf_path.as_ref(), &config)?;
+        let memo_path = Self::resolve_memo_file(dbf_path.as_ref(), dbf_path.as_ref())?;
         
         Ok(Self {
             dbf_path: dbf_path.as_ref().to_path_buf(),
             schema,
             config,
+            encoding,
+            language_driver_id,
+            memo_path,
+            dbc_header: None,
+            trimmed_values: AtomicUsize::new(0),
+            skipped_records: AtomicUsize::new(0),
+            record_errors: Mutex::new(Vec::new()),
+            deleted_records: AtomicUsize::new(0),
+            invalid_numeric_values: AtomicUsize::new(0),
         })
     }
 
+    /// Use the configured encoding, else the one declared by the header, else Latin-1
+    ///
+    /// Also returns the header's language driver ID, which is still read when
+    /// the encoding is overridden so reads can report what the file declared.
+    fn resolve_encoding(dbf_path: &Path, config: &DbcConfig) -> DbcResult<(DbaseEncoding, Option<u8>)> {
+        let driver_id = match config.encoding {
+            Some(_) => read_dbf_language_driver(dbf_path).ok(),
+            None => Some(read_dbf_language_driver(dbf_path)?),
+        };
+        let encoding = config.encoding
+            .or_else(|| driver_id.and_then(DbaseEncoding::from_language_driver))
+            .unwrap_or_default();
+        Ok((encoding, driver_id))
+    }
+
+    /// Locate the memo file next to `source_path` when the header at `dbf_path` declares memo fields
+    ///
+    /// The two paths differ for DBC files, whose header is read from a
+    /// decompressed temporary copy.
+    fn resolve_memo_file(dbf_path: &Path, source_path: &Path) -> DbcResult<Option<PathBuf>> {
+        let has_memo_fields = read_dbf_field_descriptors(dbf_path)?
+            .iter()
+            .any(|field| field.field_type == 'M');
+        if !has_memo_fields {
+            return Ok(None);
+        }
+        find_memo_file(source_path)
+            .map(Some)
+            .ok_or_else(|| DbcError::MissingMemoFile(source_path.to_path_buf()))
+    }
+
+    /// Use the provided schema when its field count matches the header, otherwise infer it
+    fn resolve_schema(dbf_path: &Path, config: &DbcConfig) -> DbcResult<Arc<PlSchema>> {
+        match &config.provided_schema {
+            Some(schema) => {
+                let header_fields = read_dbf_field_descriptors(dbf_path)?.len();
+                if schema.len() != header_fields {
+                    return Err(DbcError::SchemaFieldCountMismatch(schema.len(), header_fields));
+                }
+                Ok(schema.clone())
+            }
+            None => Ok(Arc::new(dbf_header_to_polars_schema_with_options(
+                dbf_path,
+                None,
+                config.shrink_numeric_types,
+            )?)),
+        }
+    }
+
     /// Get the schema
     pub fn schema(&self) -> Arc<PlSchema> {
         self.schema.clone()
     }
 
+    /// Encoding used to decode character fields
+    pub fn encoding(&self) -> DbaseEncoding {
+        self.encoding
+    }
+
+    /// Language driver ID declared by the header (byte 29)
+    pub fn language_driver_id(&self) -> Option<u8> {
+        self.language_driver_id
+    }
+
+    /// Memo file memo fields are read from, if the file declares any
+    pub fn memo_path(&self) -> Option<&Path> {
+        self.memo_path.as_deref()
+    }
+
+    /// Outcome of the DBC header check (None for a scanner built from a DBF)
+    pub fn dbc_header(&self) -> Option<&DbcHeaderReport> {
+        self.dbc_header.as_ref()
+    }
+
     /// Create a LazyFrame for efficient lazy evaluation
     pub fn lazy(&self) -> DbcResult<LazyFrame> {
         // For now, read the data and convert to lazy
@@ -112,6 +639,8 @@ impl DbcScanner {
     }
 
     /// Read with column selection for better performance
+    ///
+    /// Columns come back in DBF field declaration order, like every other read.
     pub fn read_columns(&self, columns: &[&str]) -> DbcResult<DataFrame> {
         // Filter schema to only requested columns
         let filtered_schema: PlSchema = self.schema
@@ -125,105 +654,490 @@ impl DbcScanner {
         }
 
         // Read all data but only process requested columns
-        let mut reader = create_dbf_reader_from_file(&self.dbf_path)?;
-        
-        let mut records = Vec::new();
-        for record_result in reader.iter_records() {
-            match record_result {
-                Ok(record) => records.push(record),
-                Err(e) => return Err(DbcError::RecordParsingError(format!("Failed to read record: {}", e))),
-            }
+        let records = self.collect_records(None)?;
+
+        // Process only selected columns in parallel, in declaration order whatever order they were requested in
+        let field_names: Vec<&str> = filtered_schema.iter_names().map(|name| name.as_str()).collect();
+        self.records_to_dataframe_filtered(records, &filtered_schema, &field_names)
+    }
+
+    /// Read entire file as single DataFrame with parallel processing
+    ///
+    /// A file without records yields an empty frame with the full schema.
+    pub fn read_all(&self) -> DbcResult<DataFrame> {
+        let span = tracing::info_span!(
+            "read_dbase",
+            path = %self.dbf_path.display(),
+            size = std::fs::metadata(&self.dbf_path).map(|m| m.len()).unwrap_or(0),
+            rows = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+        let start = Instant::now();
+
+        let records = self.collect_records(None)?;
+        tracing::debug!(records = records.len(), "records collected");
+
+        // Process in parallel chunks
+        let df = self.records_to_dataframe_parallel(records)?;
+        span.record("rows", df.height());
+        span.record("duration_ms", start.elapsed().as_millis() as u64);
+        tracing::info!(skipped = self.skipped_records(), deleted = self.deleted_records(), "read finished");
+        Ok(df)
+    }
+
+    /// Read the whole file unless `deadline` passes or `cancel` fires first
+    ///
+    /// Both are checked between records, so an interrupted read returns the
+    /// records read so far as a partial frame with `truncated` set.
+    pub fn read_bounded(&self, deadline: Instant, cancel: &CancellationToken) -> DbcResult<BoundedRead> {
+        self.read_interruptible(&|| cancel.is_cancelled() || Instant::now() >= deadline)
+    }
+
+    /// Read the whole file unless `interrupt` returns true while records remain
+    fn read_interruptible(&self, interrupt: &dyn Fn() -> bool) -> DbcResult<BoundedRead> {
+        let fired = Cell::new(false);
+        let interrupt = || {
+            fired.set(interrupt());
+            fired.get()
+        };
+        let records = self.read_records(&self.dbf_path, 0, usize::MAX, None, Some(&interrupt))?;
+
+        Ok(BoundedRead {
+            dataframe: self.records_to_dataframe_parallel(records)?,
+            truncated: fired.get(),
+        })
+    }
+
+    /// Read only the first `n` records, stopping as soon as they're read
+    ///
+    /// Malformed records dropped per `on_record_error` don't count towards
+    /// `n`. Skips the optimization pass; intended for quick inspection.
+    pub fn head(&self, n: usize) -> DbcResult<DataFrame> {
+        let records = self.read_records(&self.dbf_path, 0, n, None, None)?;
+        self.records_to_dataframe_parallel(records)
+    }
+
+    /// Read only the records in `range`, seeking past everything before it
+    ///
+    /// Ranges past the end of the file are clamped; an empty window yields an empty frame.
+    pub fn read_rows(&self, range: std::ops::Range<usize>) -> DbcResult<DataFrame> {
+        let window = tempfile::NamedTempFile::new()
+            .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
+        extract_dbf_record_window(&self.dbf_path, window.path(), range.start, range.len())?;
+
+        let records = self.read_records(window.path(), range.start, usize::MAX, None, None)?;
+        self.records_to_dataframe_parallel(records)
+    }
+
+    /// Read entire file and report the in-memory size before and after optimization
+    ///
+    /// Configured `numeric_sentinels` are nulled first, so they don't skew the
+    /// value range. When `shrink_numeric_types` is enabled, integer columns are
+    /// then cast to the smallest type that holds their actual values.
+    pub fn read_with_report(&self) -> DbcResult<DbcReadResult> {
+        let df = self.read_all()?;
+        let memory_before_bytes = estimate_dataframe_memory(&df);
+
+        let (df, sentinels_nulled) = null_numeric_sentinels(df, &self.config.numeric_sentinels)?;
+
+        let df = if self.config.shrink_numeric_types {
+            shrink_integer_columns(df)?
+        } else {
+            df
+        };
+        let memory_after_bytes = estimate_dataframe_memory(&df);
+
+        Ok(DbcReadResult {
+            dataframe: df,
+            memory_before_bytes,
+            memory_after_bytes,
+            trimmed_values: self.trimmed_values.load(Ordering::Relaxed),
+            sentinels_nulled,
+            skipped_records: self.skipped_records(),
+            record_errors: self.record_errors(),
+            deleted_records: self.deleted_records.load(Ordering::Relaxed),
+            invalid_numeric_values: self.invalid_numeric_values(),
+            detected_encoding: self.encoding,
+            language_driver_id: self.language_driver_id,
+            dbc_header: self.dbc_header.clone(),
+        })
+    }
+
+    /// Read entire file while showing a progress bar sized to the header's record count
+    pub fn read_all_with_bar(&self) -> DbcResult<DataFrame> {
+        let pb = ProgressBar::new(self.record_count()?);
+        pb.set_style(
+            ProgressStyle::with_template("{msg}\n{spinner:.yellow} [{elapsed_precise}] [{wide_bar:.magenta}] {pos:>8.blue}/{len:<8.blue} records ({per_sec:>10.blue}, {eta:>4.blue})")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("█▉▊▋▌▍▎▏ ")
+        );
+        pb.set_message(format!("📖 Reading {}", self.dbf_path.display()));
+
+        let records = self.collect_records(Some(&pb))?;
+        pb.finish_with_message(format!("✅ Read {} records", records.len()));
+
+        self.records_to_dataframe_parallel(records)
+    }
+
+    /// Read the whole file through a memory map, slicing each field out of its record
+    ///
+    /// Handles files whose fields are all stored as text (`C`, `N`, `F`, `L`,
+    /// `D`), which covers DATASUS files; others are read with [`DbcScanner::read_all`].
+    /// Unlike the dbase reader, an unparseable number or date becomes a null
+    /// rather than a record error, so `on_record_error` doesn't apply.
+    #[cfg(feature = "mmap")]
+    pub fn read_all_mmap(&self) -> DbcResult<DataFrame> {
+        let descriptors = read_dbf_field_descriptors(&self.dbf_path)?;
+        if !descriptors.iter().all(|field| matches!(field.field_type, 'C' | 'N' | 'F' | 'L' | 'D')) {
+            return self.read_all();
         }
-        
+
+        let file = std::fs::File::open(&self.dbf_path)
+            .map_err(|e| DbcError::io_error(e, self.dbf_path.display().to_string()))?;
+        // SAFETY: the map is only read, and dropped before returning
+        let map = unsafe { memmap2::Mmap::map(&file) }
+            .map_err(|e| DbcError::io_error(e, self.dbf_path.display().to_string()))?;
+        if map.len() < 12 {
+            return Err(DbcError::missing_header("DBF header is shorter than 12 bytes"));
+        }
+        let record_count = u32::from_le_bytes([map[4], map[5], map[6], map[7]]) as usize;
+        let header_size = usize::from(u16::from_le_bytes([map[8], map[9]]));
+        let record_size = usize::from(u16::from_le_bytes([map[10], map[11]]));
+        if record_size == 0 || header_size + record_count * record_size > map.len() {
+            return Err(DbcError::RecordParsingError("DBF file is shorter than its header declares".to_string()));
+        }
+
+        // Like dbase, skip records flagged as deleted unless asked to keep them
+        let records: Vec<&[u8]> = map[header_size..header_size + record_count * record_size]
+            .chunks_exact(record_size)
+            .filter(|record| self.config.include_deleted || record[0] != b'*')
+            .collect();
+        let deleted = if self.config.include_deleted { 0 } else { record_count - records.len() };
+        self.deleted_records.store(deleted, Ordering::Relaxed);
+        self.trimmed_values.store(0, Ordering::Relaxed);
+        self.invalid_numeric_values.store(0, Ordering::Relaxed);
         if records.is_empty() {
-            return Err(DbcError::EmptySources);
+            return self.apply_rename(DataFrame::empty_with_schema(&self.schema));
         }
 
-        // Process only selected columns in parallel
-        self.records_to_dataframe_filtered(records, &filtered_schema, columns)
+        // Fields follow the one-byte deletion flag in declaration order
+        let mut field_offsets = Vec::with_capacity(descriptors.len());
+        let mut offset = 1;
+        for field in &descriptors {
+            field_offsets.push(offset);
+            offset += usize::from(field.width);
+        }
+
+        let extract_column = |field_idx: usize| -> Vec<Option<String>> {
+            let start = field_offsets[field_idx];
+            let end = start + usize::from(descriptors[field_idx].width);
+            let field_type = descriptors[field_idx].field_type;
+            records
+                .par_iter()
+                .map(|record| self.raw_field_to_text(field_type, &record[start..end]))
+                .collect()
+        };
+        let strategy = determine_parallelization_strategy(records.len(), self.config.num_threads);
+        let threads = match strategy {
+            ParallelizationStrategy::Sequential => 1,
+            ParallelizationStrategy::Parallel { threads } => threads,
+        };
+
+        let pool = thread_pool(threads)?;
+        let columns = pool.install(|| {
+            self.schema
+                .iter()
+                .map(|(field_name, field_dtype)| {
+                    let values = match descriptors.iter().position(|field| field.name == field_name.as_str()) {
+                        Some(field_idx) => extract_column(field_idx),
+                        None => vec![None; records.len()],
+                    };
+                    self.strings_to_series(field_name, field_dtype, &values).map(polars::prelude::Column::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+
+        let df = DataFrame::new(columns)?;
+        self.apply_rename(df)
     }
 
-    /// Read entire file as single DataFrame with parallel processing
-    pub fn read_all(&self) -> DbcResult<DataFrame> {
-        let mut reader = create_dbf_reader_from_file(&self.dbf_path)?;
-        
-        // Collect all records using iterator
-        let mut records = Vec::new();
-        for record_result in reader.iter_records() {
-            match record_result {
-                Ok(record) => records.push(record),
-                Err(e) => return Err(DbcError::RecordParsingError(format!("Failed to read record: {}", e))),
+    /// Text of a raw text-encoded field, the same text [`field_value_to_text`] gives for it
+    #[cfg(feature = "mmap")]
+    fn raw_field_to_text(&self, field_type: char, bytes: &[u8]) -> Option<String> {
+        // Like dbase: the value ends at the first NUL and is space-padded on both sides
+        let bytes = bytes.split(|&byte| byte == 0).next().unwrap_or_default();
+        let start = bytes.iter().position(|&byte| byte != b' ')?;
+        let end = bytes.iter().rposition(|&byte| byte != b' ')? + 1;
+        let value = &bytes[start..end];
+
+        match field_type {
+            'C' => Some(self.encoding.decode_lossy(value)),
+            'L' => match value[0] {
+                b'1' | b'0' | b'T' | b't' | b'Y' | b'y' => Some(true.to_string()),
+                b'N' | b'n' | b'F' | b'f' => Some(false.to_string()),
+                _ => None,
+            },
+            'D' => {
+                let date = std::str::from_utf8(value).ok().filter(|date| date.len() == 8)?;
+                Some(format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..]))
             }
+            'F' => self.numeric_field_to_text::<f32>(value),
+            _ => self.numeric_field_to_text::<f64>(value),
         }
-        
-        if records.is_empty() {
-            return Err(DbcError::EmptySources);
+    }
+
+    /// Text of a numeric field, counting unparseable values (which become null)
+    #[cfg(feature = "mmap")]
+    fn numeric_field_to_text<T: std::str::FromStr + ToString>(&self, value: &[u8]) -> Option<String> {
+        match parse_dbf_numeric::<T>(value) {
+            Ok(number) => number.map(|number| number.to_string()),
+            Err(_) => {
+                self.invalid_numeric_values.fetch_add(1, Ordering::Relaxed);
+                None
+            }
         }
+    }
 
-        // Process in parallel chunks
-        self.records_to_dataframe_parallel(records)
+    /// Malformed records dropped by the last read
+    pub fn skipped_records(&self) -> usize {
+        self.skipped_records.load(Ordering::Relaxed)
     }
 
-    /// Convert records to DataFrame using parallel processing
-    fn records_to_dataframe_parallel(&self, records: Vec<Record>) -> DbcResult<DataFrame> {
-        let schema = &self.schema;
-        let num_fields = schema.len();
-        
-        // Use optimized chunk size from config
-        let parallel_chunk_size = std::cmp::max(1, records.len() / rayon::current_num_threads());
-        
-        // Process records in parallel chunks and collect field data
-        let chunked_columns: Vec<Vec<Vec<String>>> = records
-            .par_chunks(parallel_chunk_size)
-            .map(|record_chunk| {
-                let mut chunk_columns: Vec<Vec<String>> = (0..num_fields)
-                    .map(|_| Vec::with_capacity(record_chunk.len()))
-                    .collect();
+    /// Records flagged as deleted that the last read left out
+    pub fn deleted_records(&self) -> usize {
+        self.deleted_records.load(Ordering::Relaxed)
+    }
 
-                for record in record_chunk {
-                    // Get field names from schema to access record values
-                    for (field_idx, (field_name, _)) in schema.iter().enumerate() {
-                        let field_str = if let Some(field_value) = record.get(field_name.as_str()) {
-                            format!("{}", field_value)
-                        } else {
-                            String::new() // Empty string for missing fields
-                        };
-                        chunk_columns[field_idx].push(field_str);
-                    }
-                }
+    /// Unparseable numeric values the last read turned into nulls
+    pub fn invalid_numeric_values(&self) -> usize {
+        self.invalid_numeric_values.load(Ordering::Relaxed)
+    }
 
-                chunk_columns
-            })
-            .collect();
+    /// Records dropped by the last read, kept only under [`RecordErrorPolicy::Collect`]
+    pub fn record_errors(&self) -> Vec<RecordError> {
+        self.record_errors.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
 
-        // Merge parallel chunks
-        let mut final_columns: Vec<Vec<String>> = (0..num_fields)
-            .map(|_| Vec::with_capacity(records.len()))
-            .collect();
+    /// Number of records declared in the DBF header
+    pub fn record_count(&self) -> DbcResult<u64> {
+        use std::io::Read;
+
+        let mut file = std::fs::File::open(&self.dbf_path)
+            .map_err(|e| DbcError::io_error(e, self.dbf_path.display().to_string()))?;
+        let mut pre_header = [0u8; 8];
+        file.read_exact(&mut pre_header)
+            .map_err(|_| DbcError::missing_header("DBF header is shorter than 8 bytes"))?;
+
+        Ok(u64::from(u32::from_le_bytes([pre_header[4], pre_header[5], pre_header[6], pre_header[7]])))
+    }
+
+    /// Write the file to Parquet one `chunk_size` window at a time
+    ///
+    /// Each window becomes its own row group, so at most one batch is held in
+    /// memory regardless of file size.
+    pub fn write_parquet_streaming<P: AsRef<Path>>(&self, output: P) -> DbcResult<ParquetConversionResult> {
+        let output = output.as_ref();
+        let total_rows = self.record_count()? as usize;
+        let batch_size = self.config.chunk_size.max(1);
+
+        let file = std::fs::File::create(output)
+            .map_err(|e| DbcError::io_error(e, output.display().to_string()))?;
+        let mut parquet = Some(ParquetWriter::new(file).with_row_group_size(Some(batch_size)));
+
+        let mut writer = None;
+        let mut rows_written = 0u64;
+        let mut batches = 0usize;
+        for start in (0..total_rows).step_by(batch_size) {
+            let batch = self.read_rows(start..(start + batch_size).min(total_rows))?;
+            if writer.is_none() {
+                writer = parquet.take().map(|parquet| parquet.batched(batch.schema())).transpose()?;
+            }
+            if let Some(writer) = writer.as_mut() {
+                writer.write_batch(&batch)?;
+            }
+            rows_written += batch.height() as u64;
+            batches += 1;
+        }
 
-        for chunk_columns in chunked_columns {
-            for (field_idx, mut chunk_values) in chunk_columns.into_iter().enumerate() {
-                final_columns[field_idx].append(&mut chunk_values);
+        match (writer, parquet) {
+            (Some(writer), _) => {
+                writer.finish()?;
             }
+            // No records: still write a valid file carrying the schema
+            (None, Some(parquet)) => {
+                parquet.finish(&mut DataFrame::empty_with_schema(&self.schema))?;
+            }
+            (None, None) => {}
         }
 
-        // Convert to Polars Series in parallel
-        let series_results: Result<Vec<Series>, polars::error::PolarsError> = schema
+        let output_size_bytes = std::fs::metadata(output)
+            .map_err(|e| DbcError::io_error(e, output.display().to_string()))?
+            .len();
+
+        Ok(ParquetConversionResult {
+            rows_written,
+            batches,
+            output_size_bytes,
+        })
+    }
+
+    /// Collect all records, advancing the optional progress bar once per record
+    fn collect_records(&self, progress: Option<&ProgressBar>) -> DbcResult<Vec<Record>> {
+        self.read_records(&self.dbf_path, 0, usize::MAX, progress, None)
+    }
+
+    /// Read records of `dbf_path` until `limit` are kept, handling malformed ones per `on_record_error`
+    ///
+    /// `first_index` is the position of the file's first record in the scanned
+    /// file, so reported indices always refer to the scanned file. The dbase
+    /// reader has consumed a record before parsing it, so reading carries on
+    /// past a malformed one; its position in the file is looked up only then.
+    ///
+    /// `interrupt` is polled every [`INTERRUPT_CHECK_INTERVAL`] records while
+    /// records remain; once it returns true, the records read so far are returned.
+    ///
+    /// Numbers `str::parse` rejects, such as `1,50`, don't make a record
+    /// malformed: they are re-parsed from the raw record with
+    /// [`parse_dbf_numeric`], and read as null when that fails too.
+    ///
+    /// The dbase reader always drops records flagged as deleted, so with
+    /// `include_deleted` a copy with the flags cleared is read instead.
+    fn read_records(
+        &self,
+        dbf_path: &Path,
+        first_index: usize,
+        limit: usize,
+        progress: Option<&ProgressBar>,
+        interrupt: Option<&dyn Fn() -> bool>,
+    ) -> DbcResult<Vec<Record>> {
+        let undeleted = if self.config.include_deleted {
+            let copy = tempfile::NamedTempFile::new()
+                .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
+            undelete_dbf_records(dbf_path, copy.path())?;
+            Some(copy)
+        } else {
+            None
+        };
+        let dbf_path = undeleted.as_ref().map_or(dbf_path, |file| file.path());
+
+        let mut reader = open_with_retries(self.config.open_retries, || {
+            create_dbf_reader_with_memo(dbf_path, self.memo_path.as_deref(), self.encoding)
+        })?;
+        let declared_records = reader.header().num_records as usize;
+        // Fields follow the one-byte deletion flag in declaration order
+        let mut offset = 1;
+        let raw_fields: Vec<RawField> = reader
+            .fields()
             .iter()
-            .enumerate()
-            .collect::<Vec<_>>()
-            .par_iter()
-            .map(|(field_idx, (field_name, field_dtype))| {
-                let values = &final_columns[*field_idx];
-                self.strings_to_series(field_name, field_dtype, values)
+            .map(|field| {
+                let start = offset;
+                offset += usize::from(field.length());
+                RawField {
+                    name: field.name().to_string(),
+                    field_type: field.field_type(),
+                    span: start..offset,
+                }
             })
             .collect();
+        // Opened on the first record needing a second look, to find where it sits in the file
+        let mut positions: Option<LiveRecordPositions> = None;
 
-        let series = series_results.map_err(DbcError::Polars)?;
-        
-        // Create DataFrame
-        let columns: Vec<polars::prelude::Column> = series.into_iter().map(|s| s.into()).collect();
-        DataFrame::new(columns).map_err(DbcError::Polars)
+        let mut records = Vec::new();
+        let mut skipped = 0;
+        let mut invalid_numbers = 0;
+        let mut collected = Vec::new();
+
+        let mut record_results = reader.iter_records_as::<LenientRecord>();
+        // Records the reader has yielded, and whether it ran out of them
+        let mut read = 0;
+        let mut exhausted = false;
+        while records.len() < limit {
+            let Some(record_result) = record_results.next() else {
+                exhausted = true;
+                break;
+            };
+            // Polled only once another record is in hand, so a read that ends on a check isn't interrupted
+            if read % INTERRUPT_CHECK_INTERVAL == 0 && interrupt.is_some_and(|interrupt| interrupt()) {
+                break;
+            }
+            let index = read;
+            read += 1;
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+            let record_result = match record_result {
+                Ok(LenientRecord { record, unparsed }) if unparsed.is_empty() => {
+                    records.push(record);
+                    continue;
+                }
+                record_result => record_result,
+            };
+
+            let positions = match positions.as_mut() {
+                Some(positions) => positions,
+                None => positions.insert(LiveRecordPositions::open(dbf_path)?),
+            };
+            let position = positions.position(index)?;
+            let error = match record_result {
+                Ok(LenientRecord { mut record, unparsed }) => {
+                    invalid_numbers += reparse_numeric_fields(&mut record, &unparsed, positions.record(), &raw_fields);
+                    records.push(record);
+                    continue;
+                }
+                Err(e) => e,
+            };
+
+            let file_index = first_index + position;
+            // dbase's own message numbers records as it yields them, so only its field and cause are kept
+            let message = match error.field() {
+                Some(field) => format!("field {}: {}", field.name(), error.kind()),
+                None => error.kind().to_string(),
+            };
+            match self.config.on_record_error {
+                RecordErrorPolicy::Fail => {
+                    return Err(DbcError::RecordParsingError(format!("Failed to read record {}: {}", file_index, message)));
+                }
+                RecordErrorPolicy::Skip => {}
+                RecordErrorPolicy::Collect => collected.push(RecordError { index: file_index, message }),
+            }
+            skipped += 1;
+        }
+
+        // The reader steps over deleted records, so they're told apart from the ones it
+        // yielded: by the header count after a full read, by their flags after a partial one
+        let deleted = if undeleted.is_some() {
+            0
+        } else if exhausted {
+            declared_records.saturating_sub(read)
+        } else if read == 0 {
+            0
+        } else {
+            let positions = match positions.as_mut() {
+                Some(positions) => positions,
+                None => positions.insert(LiveRecordPositions::open(dbf_path)?),
+            };
+            positions.position(read - 1)? + 1 - read
+        };
+
+        if skipped > 0 {
+            log::warn!("Skipped {} malformed record(s) in {}", skipped, self.dbf_path.display());
+        }
+        if invalid_numbers > 0 {
+            log::warn!("Read {} unparseable numeric value(s) in {} as null", invalid_numbers, self.dbf_path.display());
+        }
+        self.skipped_records.store(skipped, Ordering::Relaxed);
+        self.deleted_records.store(deleted, Ordering::Relaxed);
+        self.invalid_numeric_values.store(invalid_numbers, Ordering::Relaxed);
+        *self.record_errors.lock().unwrap_or_else(|e| e.into_inner()) = collected;
+
+        Ok(records)
+    }
+
+    /// Convert records to DataFrame using parallel processing
+    fn records_to_dataframe_parallel(&self, records: Vec<Record>) -> DbcResult<DataFrame> {
+        let field_names: Vec<&str> = self.schema.iter_names().map(|name| name.as_str()).collect();
+        self.records_to_dataframe(records, &self.schema, &field_names)
     }
 
     /// Convert records to DataFrame with column filtering (for better performance)
@@ -233,63 +1147,97 @@ impl DbcScanner {
         filtered_schema: &PlSchema,
         selected_columns: &[&str]
     ) -> DbcResult<DataFrame> {
-        let num_fields = filtered_schema.len();
-        
-        // Use optimized chunk size from config
-        let parallel_chunk_size = std::cmp::max(1, records.len() / rayon::current_num_threads());
-        
-        // Process records in parallel chunks - only selected columns
-        let chunked_columns: Vec<Vec<Vec<String>>> = records
-            .par_chunks(parallel_chunk_size)
-            .map(|record_chunk| {
-                let mut chunk_columns: Vec<Vec<String>> = (0..num_fields)
-                    .map(|_| Vec::with_capacity(record_chunk.len()))
-                    .collect();
+        self.records_to_dataframe(records, filtered_schema, selected_columns)
+    }
 
-                for record in record_chunk {
-                    // Only process selected columns
-                    for (field_idx, column_name) in selected_columns.iter().enumerate() {
-                        let field_str = if let Some(field_value) = record.get(column_name) {
-                            format!("{}", field_value)
-                        } else {
-                            String::new()
-                        };
-                        chunk_columns[field_idx].push(field_str);
-                    }
+    /// Convert records to DataFrame, extracting `field_names` and typing them with `schema`
+    ///
+    /// Runs sequentially or on a shared rayon pool depending on the
+    /// [`ParallelizationStrategy`] chosen for the record count and thread limit.
+    fn records_to_dataframe(
+        &self,
+        records: Vec<Record>,
+        schema: &PlSchema,
+        field_names: &[&str],
+    ) -> DbcResult<DataFrame> {
+        let num_fields = field_names.len();
+        let strategy = determine_parallelization_strategy(records.len(), self.config.num_threads);
+        self.trimmed_values.store(0, Ordering::Relaxed);
+
+        // Header-only files: same columns and dtypes as a non-empty read, zero rows
+        if records.is_empty() {
+            return self.apply_rename(DataFrame::empty_with_schema(schema));
+        }
+
+        // Extract field values as text, one column vector per field
+        let extract_chunk = |record_chunk: &[Record]| -> Vec<Vec<Option<String>>> {
+            let mut chunk_columns: Vec<Vec<Option<String>>> = (0..num_fields)
+                .map(|_| Vec::with_capacity(record_chunk.len()))
+                .collect();
+
+            for record in record_chunk {
+                for (field_idx, field_name) in field_names.iter().enumerate() {
+                    let field_str = record.get(field_name).and_then(field_value_to_text); // None for missing fields
+                    chunk_columns[field_idx].push(field_str);
                 }
+            }
 
-                chunk_columns
-            })
-            .collect();
+            chunk_columns
+        };
 
-        // Merge parallel chunks
-        let mut final_columns: Vec<Vec<String>> = (0..num_fields)
-            .map(|_| Vec::with_capacity(records.len()))
-            .collect();
+        let fields: Vec<(usize, (&PlSmallStr, &polars::prelude::DataType))> = schema.iter().enumerate().collect();
 
-        for chunk_columns in chunked_columns {
-            for (field_idx, mut chunk_values) in chunk_columns.into_iter().enumerate() {
-                final_columns[field_idx].append(&mut chunk_values);
+        let series_results: Result<Vec<Series>, polars::error::PolarsError> = match strategy {
+            ParallelizationStrategy::Sequential => {
+                let chunked_columns = vec![extract_chunk(&records)];
+                let final_columns = merge_chunks(chunked_columns, num_fields, records.len());
+                fields
+                    .iter()
+                    .map(|(field_idx, (field_name, field_dtype))| {
+                        self.strings_to_series(field_name, field_dtype, &final_columns[*field_idx])
+                    })
+                    .collect()
             }
-        }
+            ParallelizationStrategy::Parallel { threads } => {
+                let pool = thread_pool(threads)?;
+                let parallel_chunk_size = std::cmp::max(1, records.len() / threads);
+                pool.install(|| {
+                    // Process records in parallel chunks and collect field data
+                    let chunked_columns: Vec<Vec<Vec<Option<String>>>> = records
+                        .par_chunks(parallel_chunk_size)
+                        .map(extract_chunk)
+                        .collect();
+                    let final_columns = merge_chunks(chunked_columns, num_fields, records.len());
 
-        // Convert to Polars Series in parallel
-        let series_results: Result<Vec<Series>, polars::error::PolarsError> = filtered_schema
-            .iter()
-            .enumerate()
-            .collect::<Vec<_>>()
-            .par_iter()
-            .map(|(field_idx, (field_name, field_dtype))| {
-                let values = &final_columns[*field_idx];
-                self.strings_to_series(field_name, field_dtype, values)
-            })
-            .collect();
+                    // Convert to Polars Series in parallel
+                    fields
+                        .par_iter()
+                        .map(|(field_idx, (field_name, field_dtype))| {
+                            self.strings_to_series(field_name, field_dtype, &final_columns[*field_idx])
+                        })
+                        .collect()
+                })
+            }
+        };
 
         let series = series_results.map_err(DbcError::Polars)?;
         
         // Create DataFrame
         let columns: Vec<polars::prelude::Column> = series.into_iter().map(|s| s.into()).collect();
-        DataFrame::new(columns).map_err(DbcError::Polars)
+        let df = DataFrame::new(columns).map_err(DbcError::Polars)?;
+        self.apply_rename(df)
+    }
+
+    /// Rename columns per `config.rename`, warning about source names the frame doesn't have
+    fn apply_rename(&self, mut df: DataFrame) -> DbcResult<DataFrame> {
+        for (source, target) in &self.config.rename {
+            if df.get_column_index(source).is_some() {
+                df.rename(source, target.as_str().into())?;
+            } else if !self.schema.contains(source) {
+                log::warn!("Cannot rename {} to {}: no such column in {}", source, target, self.dbf_path.display());
+            }
+        }
+        Ok(df)
     }
 
     /// Convert string values to appropriate Polars Series based on data type
@@ -297,25 +1245,57 @@ impl DbcScanner {
         &self,
         field_name: &PlSmallStr,
         field_dtype: &polars::prelude::DataType,
-        values: &[String],
+        values: &[Option<String>],
     ) -> Result<Series, polars::error::PolarsError> {
         use polars::prelude::*;
 
         match field_dtype {
             DataType::String => {
-                Ok(Series::new(field_name.clone(), values))
+                let policy = self.config.trim_strings;
+                let empty_as_null = self.config.empty_as_null;
+                let mut trimmed = 0;
+                let str_values: Vec<Option<&str>> = values
+                    .iter()
+                    .map(|s| {
+                        s.as_deref()
+                            .filter(|s| !(empty_as_null && s.trim().is_empty()))
+                            .map(|s| {
+                                let value = policy.apply(s);
+                                if value.len() != s.len() {
+                                    trimmed += 1;
+                                }
+                                value
+                            })
+                    })
+                    .collect();
+                self.trimmed_values.fetch_add(trimmed, Ordering::Relaxed);
+                Ok(Series::new(field_name.clone(), str_values))
+            }
+            DataType::Int16 => {
+                let int_values: Vec<Option<i16>> = values
+                    .iter()
+                    .map(|s| s.as_deref().and_then(|s| s.trim().parse().ok()))
+                    .collect();
+                Ok(Series::new(field_name.clone(), int_values))
             }
             DataType::Int32 => {
                 let int_values: Vec<Option<i32>> = values
                     .iter()
-                    .map(|s| s.trim().parse().ok())
+                    .map(|s| s.as_deref().and_then(|s| s.trim().parse().ok()))
+                    .collect();
+                Ok(Series::new(field_name.clone(), int_values))
+            }
+            DataType::Int64 => {
+                let int_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|s| s.as_deref().and_then(|s| s.trim().parse().ok()))
                     .collect();
                 Ok(Series::new(field_name.clone(), int_values))
             }
             DataType::Float64 => {
                 let float_values: Vec<Option<f64>> = values
                     .iter()
-                    .map(|s| s.trim().parse().ok())
+                    .map(|s| s.as_deref().and_then(|s| s.trim().parse().ok()))
                     .collect();
                 Ok(Series::new(field_name.clone(), float_values))
             }
@@ -323,15 +1303,31 @@ impl DbcScanner {
                 let bool_values: Vec<Option<bool>> = values
                     .iter()
                     .map(|s| {
-                        match s.trim().to_lowercase().as_str() {
-                            "true" | "t" | "1" | "y" | "yes" => Some(true),
-                            "false" | "f" | "0" | "n" | "no" => Some(false),
+                        match s.as_deref().map(|s| s.trim().to_lowercase()).as_deref() {
+                            Some("true" | "t" | "1" | "y" | "yes") => Some(true),
+                            Some("false" | "f" | "0" | "n" | "no") => Some(false),
                             _ => None,
                         }
                     })
                     .collect();
                 Ok(Series::new(field_name.clone(), bool_values))
             }
+            DataType::Date => {
+                let day_values: Vec<Option<i32>> = values
+                    .iter()
+                    .map(|s| s.as_deref().and_then(parse_dbase_date))
+                    .collect();
+                Series::new(field_name.clone(), day_values).cast(&DataType::Date)
+            }
+            DataType::Datetime(time_unit, time_zone) => {
+                let ms_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|s| s.as_deref().and_then(parse_dbase_datetime_ms))
+                    .collect();
+                Series::new(field_name.clone(), ms_values)
+                    .cast(&DataType::Datetime(TimeUnit::Milliseconds, time_zone.clone()))?
+                    .cast(&DataType::Datetime(*time_unit, time_zone.clone()))
+            }
             _ => {
                 // Fallback to string for other types
                 Ok(Series::new(field_name.clone(), values))
@@ -340,6 +1336,160 @@ impl DbcScanner {
     }
 }
 
+/// Estimate the in-memory size of a DataFrame by summing per-column estimates
+pub fn estimate_dataframe_memory(df: &DataFrame) -> usize {
+    df.get_columns()
+        .iter()
+        .map(|column| column.as_materialized_series().estimated_size())
+        .sum()
+}
+
+/// Cast integer columns to the smallest integer type that holds their values
+pub fn shrink_integer_columns(mut df: DataFrame) -> DbcResult<DataFrame> {
+    use polars::prelude::DataType;
+
+    let names: Vec<PlSmallStr> = df.get_column_names().into_iter().cloned().collect();
+    for name in names {
+        let series = df.column(&name)?.as_materialized_series().clone();
+        if !matches!(series.dtype(), DataType::Int16 | DataType::Int32 | DataType::Int64) {
+            continue;
+        }
+
+        let (Some(min), Some(max)) = (series.min::<i64>()?, series.max::<i64>()?) else {
+            continue; // All-null column, nothing to learn from the values
+        };
+        let target = if min >= i64::from(i8::MIN) && max <= i64::from(i8::MAX) {
+            DataType::Int8
+        } else if min >= i64::from(i16::MIN) && max <= i64::from(i16::MAX) {
+            DataType::Int16
+        } else if min >= i64::from(i32::MIN) && max <= i64::from(i32::MAX) {
+            DataType::Int32
+        } else {
+            DataType::Int64
+        };
+
+        if &target != series.dtype() {
+            df.with_column(series.cast(&target)?)?;
+        }
+    }
+
+    Ok(df)
+}
+
+/// Replace sentinel values in numeric columns with null
+///
+/// Returns the frame and the number of values nulled per column; columns
+/// without any sentinel are left untouched and omitted from the counts.
+pub fn null_numeric_sentinels(
+    mut df: DataFrame,
+    sentinels: &[f64],
+) -> DbcResult<(DataFrame, HashMap<String, usize>)> {
+    use polars::prelude::{BooleanChunked, NewChunkedArray};
+
+    let mut nulled = HashMap::new();
+    if sentinels.is_empty() {
+        return Ok((df, nulled));
+    }
+
+    let names: Vec<PlSmallStr> = df.get_column_names().into_iter().cloned().collect();
+    for name in names {
+        let series = df.column(&name)?.as_materialized_series().clone();
+        if !series.dtype().is_primitive_numeric() {
+            continue;
+        }
+
+        let values = series.cast(&polars::prelude::DataType::Float64)?;
+        let keep: Vec<bool> = values
+            .f64()?
+            .into_iter()
+            .map(|value| !value.is_some_and(|v| sentinels.contains(&v)))
+            .collect();
+        let count = keep.iter().filter(|keep| !**keep).count();
+        if count == 0 {
+            continue;
+        }
+
+        let mask = BooleanChunked::from_slice(name.clone(), &keep);
+        let nulls = Series::full_null(name.clone(), series.len(), series.dtype());
+        df.with_column(series.zip_with(&mask, &nulls)?)?;
+        nulled.insert(name.to_string(), count);
+    }
+
+    Ok((df, nulled))
+}
+
+/// Render a dbase field value as text, returning None for null values
+///
+/// Dates are rendered as `YYYY-MM-DD` and datetimes as `YYYY-MM-DD HH:MM:SS`,
+/// which is what the date parsers in this module expect.
+pub fn field_value_to_text(value: &FieldValue) -> Option<String> {
+    match value {
+        FieldValue::Character(value) => value.clone(),
+        FieldValue::Memo(value) => Some(value.clone()),
+        FieldValue::Numeric(value) => value.map(|v| v.to_string()),
+        FieldValue::Float(value) => value.map(|v| v.to_string()),
+        FieldValue::Logical(value) => value.map(|v| v.to_string()),
+        FieldValue::Integer(value) => Some(value.to_string()),
+        FieldValue::Currency(value) => Some(value.to_string()),
+        FieldValue::Double(value) => Some(value.to_string()),
+        FieldValue::Date(value) => value.as_ref().map(|date| {
+            format!("{:04}-{:02}-{:02}", date.year(), date.month(), date.day())
+        }),
+        FieldValue::DateTime(value) => {
+            let date = value.date();
+            let time = value.time();
+            Some(format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                date.year(),
+                date.month(),
+                date.day(),
+                time.hours(),
+                time.minutes(),
+                time.seconds()
+            ))
+        }
+    }
+}
+
+/// Parse the `unparsed` fields of `record` from the raw record with [`parse_dbf_numeric`]
+///
+/// `raw` starts with the deletion flag and `fields` locates each field in it.
+/// Returns how many values stay unparseable, which are read as null.
+fn reparse_numeric_fields(record: &mut Record, unparsed: &[usize], raw: &[u8], fields: &[RawField]) -> usize {
+    let mut invalid = 0;
+    for field in unparsed.iter().filter_map(|&position| fields.get(position)) {
+        let bytes = raw.get(field.span.clone()).unwrap_or_default();
+        let value = match field.field_type {
+            dbase::FieldType::Float => {
+                parse_dbf_numeric::<f32>(bytes).map(FieldValue::Float).map_err(|_| FieldValue::Float(None))
+            }
+            _ => parse_dbf_numeric::<f64>(bytes).map(FieldValue::Numeric).map_err(|_| FieldValue::Numeric(None)),
+        };
+        if value.is_err() {
+            invalid += 1;
+        }
+        record.insert(field.name.clone(), value.unwrap_or_else(|null| null));
+    }
+    invalid
+}
+
+/// Parse a dbase date (`YYYY-MM-DD` or raw `YYYYMMDD`) into days since the Unix epoch
+fn parse_dbase_date(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| chrono::NaiveDate::parse_from_str(value, "%Y%m%d"))
+        .ok()?;
+    let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)?;
+    i32::try_from((date - epoch).num_days()).ok()
+}
+
+/// Parse a dbase datetime (`YYYY-MM-DD HH:MM:SS`) into milliseconds since the Unix epoch
+fn parse_dbase_datetime_ms(value: &str) -> Option<i64> {
+    chrono::NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|datetime| datetime.and_utc().timestamp_millis())
+}
+
 /// Read entire DBC file with maximum performance defaults
 pub fn read_dbc<P: AsRef<Path>>(dbc_path: P) -> DbcResult<DataFrame> {
     let scanner = DbcScanner::from_dbc_path(dbc_path, None)?;
@@ -358,6 +1508,27 @@ pub fn read_dbc_columns<P: AsRef<Path>>(dbc_path: P, columns: &[&str]) -> DbcRes
     scanner.read_columns(columns)
 }
 
+/// Read DBC file showing a progress bar (for interactive use)
+pub fn read_dbc_with_bar<P: AsRef<Path>>(dbc_path: P, config: Option<DbcConfig>) -> DbcResult<DataFrame> {
+    let scanner = DbcScanner::from_dbc_path(dbc_path, config)?;
+    scanner.read_all_with_bar()
+}
+
+/// Read the first `n` records of a DBC file, decompressing only what's needed
+pub fn head_dbc<P: AsRef<Path>>(dbc_path: P, n: usize) -> DbcResult<DataFrame> {
+    let temp_dbf = tempfile::NamedTempFile::new()
+        .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
+    decompress_dbc_head_to_dbf(&dbc_path, temp_dbf.path(), n)?;
+
+    let scanner = DbcScanner::from_dbf_path(temp_dbf.path(), None)?;
+    let head = scanner.head(n)?;
+    // Deleted or malformed records leave the first `n` short; the rest of the file makes up for them
+    if head.height() < n && scanner.record_count()? > n as u64 {
+        return DbcScanner::from_dbc_path(&dbc_path, None)?.head(n);
+    }
+    Ok(head)
+}
+
 /// Create LazyFrame from DBC file (recommended for chaining operations)
 pub fn scan_dbc_lazy<P: AsRef<Path>>(dbc_path: P) -> DbcResult<LazyFrame> {
     let scanner = DbcScanner::from_dbc_path(dbc_path, None)?;
@@ -370,18 +1541,231 @@ pub fn read_dbf<P: AsRef<Path>>(dbf_path: P) -> DbcResult<DataFrame> {
     scanner.read_all()
 }
 
+/// Read DBF file with custom configuration
+pub fn read_dbf_with_config<P: AsRef<Path>>(dbf_path: P, config: DbcConfig) -> DbcResult<DataFrame> {
+    let scanner = DbcScanner::from_dbf_path(dbf_path, Some(config))?;
+    scanner.read_all()
+}
+
 /// Read DBF file with column selection
 pub fn read_dbf_columns<P: AsRef<Path>>(dbf_path: P, columns: &[&str]) -> DbcResult<DataFrame> {
     let scanner = DbcScanner::from_dbf_path(dbf_path, None)?;
     scanner.read_columns(columns)
 }
 
+/// Read DBC/DBF files (told apart by content) into one frame of the columns they all share
+///
+/// Projecting to [`common_columns`] keeps schema drift between files (e.g.
+/// fields added in later years) from showing up as all-null columns. Integer
+/// columns read with different widths are widened to a common type.
+pub fn read_common_columns(paths: &[PathBuf], config: Option<DbcConfig>) -> DbcResult<DataFrame> {
+    use polars::prelude::{concat, UnionArgs};
+
+    let columns = common_columns(paths)?;
+    if columns.is_empty() {
+        return Err(DbcError::InvalidDbcFormat("No columns common to every file".to_string()));
+    }
+    let column_refs: Vec<&str> = columns.iter().map(String::as_str).collect();
+
+    let frames = paths
+        .iter()
+        .map(|path| {
+            let scanner = if is_dbc_file(path) {
+                DbcScanner::from_dbc_path(path, config.clone())?
+            } else {
+                DbcScanner::from_dbf_path(path, config.clone())?
+            };
+            // Same column order in every frame, whatever each file declares
+            Ok(scanner.read_columns(&column_refs)?.select(columns.iter().cloned())?.lazy())
+        })
+        .collect::<DbcResult<Vec<LazyFrame>>>()?;
+
+    let union = UnionArgs {
+        to_supertypes: true,
+        ..UnionArgs::default()
+    };
+    Ok(concat(frames, union)?.collect()?)
+}
+
+/// Read the records in `range` of a DBF file without reading the ones before it
+pub fn read_dbf_rows<P: AsRef<Path>>(dbf_path: P, range: std::ops::Range<usize>) -> DbcResult<DataFrame> {
+    let scanner = DbcScanner::from_dbf_path(dbf_path, None)?;
+    scanner.read_rows(range)
+}
+
+/// Convert a DBC file to Parquet in `chunk_size` batches, never reading it whole
+pub fn convert_dbc_to_parquet_streaming<P: AsRef<Path>, Q: AsRef<Path>>(
+    dbc_path: P,
+    output: Q,
+    config: Option<DbcConfig>,
+) -> DbcResult<ParquetConversionResult> {
+    let scanner = DbcScanner::from_dbc_path(dbc_path, config)?;
+    scanner.write_parquet_streaming(output)
+}
+
+/// Convert a DBF file to Parquet in `chunk_size` batches, never reading it whole
+pub fn convert_dbf_to_parquet_streaming<P: AsRef<Path>, Q: AsRef<Path>>(
+    dbf_path: P,
+    output: Q,
+    config: Option<DbcConfig>,
+) -> DbcResult<ParquetConversionResult> {
+    let scanner = DbcScanner::from_dbf_path(dbf_path, config)?;
+    scanner.write_parquet_streaming(output)
+}
+
+/// Decompress a DBC file to a plain DBF at `output` for use with other tools
+///
+/// Missing parent directories of `output` are created, and a partly written
+/// output is removed if decompression fails. Returns the bytes written.
+pub fn dbc_to_dbf_file<P: AsRef<Path>, Q: AsRef<Path>>(input: P, output: Q) -> DbcResult<u64> {
+    let (input, output) = (input.as_ref(), output.as_ref());
+    if let Some(parent) = output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| DbcError::io_error(e, parent.display().to_string()))?;
+    }
+
+    let dbc_file = std::fs::File::open(input).map_err(|e| DbcError::io_error(e, input.display().to_string()))?;
+    let mut dbf_reader = dbc_to_dbf_reader(dbc_file)?;
+    let mut dbf_file = std::fs::File::create(output).map_err(|e| DbcError::io_error(e, output.display().to_string()))?;
+
+    let copied = std::io::copy(&mut dbf_reader, &mut dbf_file);
+    drop(dbf_file);
+    copied.map_err(|e| {
+        let _ = std::fs::remove_file(output);
+        DbcError::io_error(e, input.display().to_string())
+    })
+}
+
+/// Read a DBF file through a memory map, or a DBC file with the buffered reader
+///
+/// DBC files are decompressed to a temporary DBF first, so mapping them gains
+/// nothing over [`read_dbc_with_config`].
+#[cfg(feature = "mmap")]
+pub fn read_dbase_mmap<P: AsRef<Path>>(path: P, config: Option<DbcConfig>) -> DbcResult<DataFrame> {
+    if is_dbc_file(path.as_ref()) {
+        DbcScanner::from_dbc_path(path, config)?.read_all()
+    } else {
+        DbcScanner::from_dbf_path(path, config)?.read_all_mmap()
+    }
+}
+
+/// Read a DBC or DBF file (told apart by content), stopping early at `deadline` or on `cancel`
+///
+/// DBC files are decompressed in full before reading starts, and that step
+/// isn't interruptible.
+pub fn read_dbase_bounded<P: AsRef<Path>>(
+    path: P,
+    config: Option<DbcConfig>,
+    deadline: Instant,
+    cancel: CancellationToken,
+) -> DbcResult<BoundedRead> {
+    let scanner = if is_dbc_file(path.as_ref()) {
+        DbcScanner::from_dbc_path(path, config)?
+    } else {
+        DbcScanner::from_dbf_path(path, config)?
+    };
+    scanner.read_bounded(deadline, &cancel)
+}
+
+/// Read DBF file showing a progress bar (for interactive use)
+pub fn read_dbf_with_bar<P: AsRef<Path>>(dbf_path: P, config: Option<DbcConfig>) -> DbcResult<DataFrame> {
+    let scanner = DbcScanner::from_dbf_path(dbf_path, config)?;
+    scanner.read_all_with_bar()
+}
+
+/// Read the first `n` records of a DBF file
+pub fn head_dbf<P: AsRef<Path>>(dbf_path: P, n: usize) -> DbcResult<DataFrame> {
+    let scanner = DbcScanner::from_dbf_path(dbf_path, None)?;
+    scanner.head(n)
+}
+
 /// Create LazyFrame from DBF file
 pub fn scan_dbf_lazy<P: AsRef<Path>>(dbf_path: P) -> DbcResult<LazyFrame> {
     let scanner = DbcScanner::from_dbf_path(dbf_path, None)?;
     scanner.lazy()
 }
 
+/// Number of records declared in a DBF or DBC header, without reading any record
+///
+/// For DBF this reads 8 bytes. DBC headers are compressed with the data, so
+/// the stream is decompressed only up to the end of the header; the count is
+/// still the one the file declares, not a tally of readable records.
+pub fn dbase_row_count_fast<P: AsRef<Path>>(path: P) -> DbcResult<u64> {
+    let header_count = |dbf_path: &Path| -> DbcResult<u64> {
+        use std::io::Read;
+
+        let mut pre_header = [0u8; 8];
+        std::fs::File::open(dbf_path)
+            .and_then(|mut file| file.read_exact(&mut pre_header))
+            .map_err(|e| DbcError::io_error(e, dbf_path.display().to_string()))?;
+        Ok(u64::from(u32::from_le_bytes([pre_header[4], pre_header[5], pre_header[6], pre_header[7]])))
+    };
+
+    if is_dbc_file(path.as_ref()) {
+        let temp_dbf = tempfile::NamedTempFile::new()
+            .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
+        decompress_dbc_head_to_dbf(&path, temp_dbf.path(), 0)?;
+        header_count(temp_dbf.path())
+    } else {
+        header_count(path.as_ref())
+    }
+}
+
+/// Check that a DBC or DBF file parses and holds every record its header declares
+///
+/// Returns the declared record count. DBC files are fully decompressed to a
+/// temporary DBF first, so a corrupt compressed stream fails too.
+pub fn verify_dbase_file<P: AsRef<Path>>(path: P) -> DbcResult<u64> {
+    let verify_dbf = |dbf_path: &Path| -> DbcResult<u64> {
+        use std::io::Read;
+
+        read_dbf_field_descriptors(dbf_path)?;
+        let mut file = std::fs::File::open(dbf_path)
+            .map_err(|e| DbcError::io_error(e, dbf_path.display().to_string()))?;
+        let mut pre_header = [0u8; 12];
+        file.read_exact(&mut pre_header)
+            .map_err(|e| DbcError::io_error(e, dbf_path.display().to_string()))?;
+        let record_count = u64::from(u32::from_le_bytes([pre_header[4], pre_header[5], pre_header[6], pre_header[7]]));
+        let header_size = u64::from(u16::from_le_bytes([pre_header[8], pre_header[9]]));
+        let record_size = u64::from(u16::from_le_bytes([pre_header[10], pre_header[11]]));
+
+        let expected = header_size + record_count * record_size;
+        let actual = file
+            .metadata()
+            .map_err(|e| DbcError::io_error(e, dbf_path.display().to_string()))?
+            .len();
+        if actual < expected {
+            return Err(DbcError::RecordParsingError(format!(
+                "header declares {} records ({} bytes) but the file has {} bytes",
+                record_count, expected, actual
+            )));
+        }
+        Ok(record_count)
+    };
+
+    if is_dbc_file(path.as_ref()) {
+        let temp_dbf = tempfile::NamedTempFile::new()
+            .map_err(|e| DbcError::IO(e, "creating temp file".to_string()))?;
+        decompress_dbc_to_dbf(&path, temp_dbf.path())?;
+        verify_dbf(temp_dbf.path())
+    } else {
+        verify_dbf(path.as_ref())
+    }
+}
+
+/// Zero-config LazyFrame over a DBC or DBF file, told apart by content
+///
+/// Unlike [`scan_dbc_lazy`]/[`scan_dbf_lazy`], integer columns are shrunk to
+/// their smallest type (as in [`DbcScanner::read_with_report`]) before the
+/// frame is handed over, and no progress bar is shown.
+pub fn quick_scan_dbase<P: AsRef<Path>>(path: P) -> DbcResult<LazyFrame> {
+    let scanner = if is_dbc_file(path.as_ref()) {
+        DbcScanner::from_dbc_path(path, None)?
+    } else {
+        DbcScanner::from_dbf_path(path, None)?
+    };
+    Ok(scanner.read_with_report()?.dataframe.lazy())
+}
+
 /// Legacy function for compatibility
 pub fn scan_dbc<P: AsRef<Path>>(dbc_path: P, _chunk_size: Option<usize>) -> DbcResult<DbcScanner> {
     DbcScanner::from_dbc_path(dbc_path, None)
@@ -395,8 +1779,38 @@ pub fn scan_dbf<P: AsRef<Path>>(dbf_path: P, _chunk_size: Option<usize>) -> DbcR
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::des::dbc_to_polars_schema;
+    use super::super::fixtures;
     use std::time::Instant;
 
+    #[test]
+    fn test_config_builder() {
+        let config = DbcConfig::builder()
+            .columns(["N_AIH", "UF_ZI"])
+            .num_threads(2)
+            .shrink_numeric_types(false)
+            .encoding(DbaseEncoding::Utf8)
+            .numeric_sentinels([9999.0])
+            .rename("UF_ZI", "MUNIC")
+            .on_record_error(RecordErrorPolicy::Collect)
+            .include_deleted(true)
+            .build();
+
+        assert_eq!(config.columns, Some(vec!["N_AIH".to_string(), "UF_ZI".to_string()]));
+        assert_eq!(config.num_threads, Some(2));
+        assert!(!config.shrink_numeric_types);
+        assert_eq!(config.encoding, Some(DbaseEncoding::Utf8));
+        assert_eq!(config.numeric_sentinels, [9999.0]);
+        assert_eq!(config.rename.get("UF_ZI").map(String::as_str), Some("MUNIC"));
+        assert_eq!(config.on_record_error, RecordErrorPolicy::Collect);
+        assert!(config.include_deleted);
+
+        let defaults = DbcConfig::default();
+        assert_eq!(config.chunk_size, defaults.chunk_size);
+        assert_eq!(config.open_retries, defaults.open_retries);
+        assert_eq!(DbcConfig::builder().build().columns, defaults.columns);
+    }
+
     #[test]
     fn test_scanner_creation() {
         let test_path = "/nonexistent/test.dbc";
@@ -540,4 +1954,950 @@ mod tests {
         
         println!("String-based type conversion working correctly");
     }
+
+    #[test]
+    fn test_field_value_to_text() {
+        assert_eq!(field_value_to_text(&FieldValue::Character(Some("ABC".to_string()))), Some("ABC".to_string()));
+        assert_eq!(field_value_to_text(&FieldValue::Character(None)), None);
+        assert_eq!(field_value_to_text(&FieldValue::Memo("long text".to_string())), Some("long text".to_string()));
+        assert_eq!(field_value_to_text(&FieldValue::Numeric(Some(42.0))), Some("42".to_string()));
+        assert_eq!(field_value_to_text(&FieldValue::Integer(-7)), Some("-7".to_string()));
+        assert_eq!(field_value_to_text(&FieldValue::Currency(19.99)), Some("19.99".to_string()));
+        assert_eq!(field_value_to_text(&FieldValue::Double(0.5)), Some("0.5".to_string()));
+        assert_eq!(
+            field_value_to_text(&FieldValue::Date(Some(dbase::Date::new(1, 2, 2019)))),
+            Some("2019-02-01".to_string())
+        );
+        let datetime = dbase::DateTime::new(dbase::Date::new(31, 12, 2020), dbase::Time::new(23, 59, 58));
+        assert_eq!(
+            field_value_to_text(&FieldValue::DateTime(datetime)),
+            Some("2020-12-31 23:59:58".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_dbase_dates() {
+        assert_eq!(parse_dbase_date("1970-01-02"), Some(1));
+        assert_eq!(parse_dbase_date("19700101"), Some(0));
+        assert_eq!(parse_dbase_date("not a date"), None);
+        assert_eq!(parse_dbase_datetime_ms("1970-01-01 00:00:01"), Some(1_000));
+        assert_eq!(parse_dbase_datetime_ms(""), None);
+    }
+
+    #[test]
+    fn test_read_dbf_typed_fields() {
+        use polars::prelude::DataType;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let df = read_dbf(&path).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.column("CODIGO").unwrap().dtype(), &DataType::String);
+        assert_eq!(df.column("ATIVO").unwrap().dtype(), &DataType::Boolean);
+        assert_eq!(df.column("DT_NASC").unwrap().dtype(), &DataType::Date);
+
+        let codes = df.column("CODIGO").unwrap().str().unwrap();
+        assert_eq!(codes.get(0), Some("355030"));
+        let active = df.column("ATIVO").unwrap().bool().unwrap();
+        assert_eq!(active.get(0), Some(true));
+        assert_eq!(active.get(1), None);
+        let born = df.column("DT_NASC").unwrap().cast(&DataType::Int32).unwrap();
+        assert_eq!(born.i32().unwrap().get(0), parse_dbase_date("1982-03-15"));
+        assert_eq!(born.i32().unwrap().get(1), None);
+    }
+
+    #[test]
+    fn test_read_dbf_foxpro_fields() {
+        use polars::prelude::{DataType, TimeUnit};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("foxpro.dbf");
+        fixtures::write_foxpro_dbf(&path);
+
+        let df = read_dbf(&path).unwrap();
+        assert_eq!(df.column("QT_DIAS").unwrap().dtype(), &DataType::Int32);
+        assert_eq!(df.column("VAL_TOT").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("TAXA").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(
+            df.column("DT_PROC").unwrap().dtype(),
+            &DataType::Datetime(TimeUnit::Milliseconds, None)
+        );
+
+        assert_eq!(df.column("QT_DIAS").unwrap().i32().unwrap().get(0), Some(7));
+        assert_eq!(df.column("VAL_TOT").unwrap().f64().unwrap().get(0), Some(199.9));
+        assert_eq!(df.column("TAXA").unwrap().f64().unwrap().get(0), Some(0.125));
+        let processed = df.column("DT_PROC").unwrap().cast(&DataType::Int64).unwrap();
+        assert_eq!(
+            processed.i64().unwrap().get(0),
+            parse_dbase_datetime_ms("2020-01-02 13:30:05")
+        );
+    }
+
+    #[test]
+    fn test_numeric_width_narrowing() {
+        use polars::prelude::DataType;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        let schema = scanner.schema();
+        assert_eq!(schema.get("IDADE"), Some(&DataType::Int16)); // N(3,0)
+        assert_eq!(schema.get("VALOR"), Some(&DataType::Float64)); // N(12,2)
+
+        let df = scanner.read_all().unwrap();
+        let ages = df.column("IDADE").unwrap().i16().unwrap();
+        assert_eq!(ages.get(0), Some(42));
+        assert_eq!(ages.get(1), None);
+        assert_eq!(df.column("VALOR").unwrap().f64().unwrap().get(0), Some(1234.5));
+
+        let config = DbcConfig {
+            shrink_numeric_types: false,
+            ..Default::default()
+        };
+        let scanner = DbcScanner::from_dbf_path(&path, Some(config)).unwrap();
+        assert_eq!(scanner.schema().get("IDADE"), Some(&DataType::Int64));
+    }
+
+    #[test]
+    fn test_read_with_bar_matches_plain_read() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        assert_eq!(scanner.record_count().unwrap(), 2);
+
+        let with_bar = read_dbf_with_bar(&path, None).unwrap();
+        let plain = read_dbf(&path).unwrap();
+        assert!(with_bar.equals_missing(&plain));
+    }
+
+    #[test]
+    fn test_header_only_dbf_reads_as_empty_typed_frame() {
+        use polars::prelude::DataType;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("empty.dbf");
+        fixtures::write_header_only_dbf(&path);
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        assert_eq!(scanner.record_count().unwrap(), 0);
+        assert_eq!(dbase_row_count_fast(&path).unwrap(), 0);
+
+        let df = read_dbf(&path).unwrap();
+        assert_eq!(df.shape(), (0, 3));
+        assert_eq!(df.schema().as_ref(), scanner.schema().as_ref());
+        assert_eq!(df.column("UF").unwrap().dtype(), &DataType::String);
+        assert_eq!(df.column("VALOR").unwrap().dtype(), &DataType::Float64);
+
+        // Every read path agrees on the empty frame
+        assert!(read_dbf_with_bar(&path, None).unwrap().equals_missing(&df));
+        assert!(scanner.head(5).unwrap().equals_missing(&df));
+        assert!(scanner.read_rows(0..10).unwrap().equals_missing(&df));
+        assert!(scanner.lazy().unwrap().collect().unwrap().equals_missing(&df));
+
+        let columns = read_dbf_columns(&path, &["VALOR", "UF"]).unwrap();
+        assert_eq!(columns.shape(), (0, 2));
+        let names: Vec<&str> = columns.get_column_names().into_iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, ["UF", "VALOR"]);
+    }
+
+    #[test]
+    fn test_record_error_policy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("malformed.dbf");
+        fixtures::write_malformed_record_dbf(&path);
+        let with_policy = |policy| DbcConfig { on_record_error: policy, ..Default::default() };
+
+        // Fail: the first bad record aborts the read
+        let scanner = DbcScanner::from_dbf_path(&path, Some(with_policy(RecordErrorPolicy::Fail))).unwrap();
+        match scanner.read_all() {
+            Err(DbcError::RecordParsingError(message)) => assert!(message.contains("record 1"), "{message}"),
+            other => panic!("expected RecordParsingError, got {:?}", other.map(|df| df.shape())),
+        }
+
+        // Skip (default): the records around the bad one are still read
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        let report = scanner.read_with_report().unwrap();
+        assert_eq!(report.skipped_records, 1);
+        assert!(report.record_errors.is_empty());
+        let ufs: Vec<Option<&str>> = report.dataframe.column("UF").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(ufs, [Some("SP"), Some("MG")]);
+
+        // Collect: the bad record's index and message are kept
+        let scanner = DbcScanner::from_dbf_path(&path, Some(with_policy(RecordErrorPolicy::Collect))).unwrap();
+        assert_eq!(scanner.read_all().unwrap().height(), 2);
+        let errors = scanner.record_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 1);
+        assert!(!errors[0].message.is_empty());
+
+        // Windowed reads report indices within the whole file
+        assert_eq!(scanner.read_rows(1..3).unwrap().height(), 1);
+        assert_eq!(scanner.record_errors()[0].index, 1);
+        assert_eq!(scanner.head(1).unwrap().height(), 1);
+        assert_eq!(scanner.skipped_records(), 0);
+
+        // A dropped record inside the head window is made up for by the next one
+        let head = scanner.head(2).unwrap();
+        let ufs: Vec<Option<&str>> = head.column("UF").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(ufs, [Some("SP"), Some("MG")]);
+        assert_eq!(scanner.skipped_records(), 1);
+    }
+
+    #[test]
+    fn test_record_errors_after_deleted_record_use_file_positions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("deleted_malformed.dbf");
+        fixtures::write_deleted_then_malformed_dbf(&path);
+        let config = DbcConfig { on_record_error: RecordErrorPolicy::Collect, ..Default::default() };
+        let scanner = DbcScanner::from_dbf_path(&path, Some(config)).unwrap();
+        let ids = |df: &DataFrame| -> Vec<Option<i64>> {
+            df.column("ID").unwrap().cast(&polars::prelude::DataType::Int64).unwrap().i64().unwrap().into_iter().collect()
+        };
+
+        // Each readable record exactly once, and the bad one reported at its place in the file
+        let df = scanner.read_all().unwrap();
+        assert_eq!(ids(&df), [Some(1), Some(4)]);
+        assert_eq!(scanner.deleted_records(), 1);
+        assert_eq!(scanner.skipped_records(), 1);
+        let errors = scanner.record_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 2);
+        assert!(errors[0].message.starts_with("field DT:"), "{}", errors[0].message);
+
+        for range in [1..4, 2..4] {
+            let df = scanner.read_rows(range.clone()).unwrap();
+            assert_eq!(ids(&df), [Some(4)], "{range:?}");
+            assert_eq!(scanner.record_errors()[0].index, 2, "{range:?}");
+        }
+    }
+
+    #[test]
+    fn test_head_reads_past_dropped_records() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dbf = temp_dir.path().join("deleted_malformed.dbf");
+        let dbc = temp_dir.path().join("deleted_malformed.dbc");
+        fixtures::write_deleted_then_malformed_dbf(&dbf);
+        fixtures::write_dbc_from_dbf(&dbf, &dbc);
+        let ufs = |df: &DataFrame| -> Vec<String> {
+            df.column("UF").unwrap().str().unwrap().into_no_null_iter().map(str::to_string).collect()
+        };
+
+        assert_eq!(ufs(&head_dbf(&dbf, 2).unwrap()), ["SP", "SP"]);
+        assert_eq!(ufs(&head_dbc(&dbc, 2).unwrap()), ["SP", "SP"]);
+        assert_eq!(head_dbc(&dbc, 10).unwrap().height(), 2);
+    }
+
+    #[test]
+    fn test_deleted_records_excluded_unless_requested() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("deleted.dbf");
+        fixtures::write_deleted_record_dbf(&path, 5, 2);
+        let ufs = |df: &DataFrame| -> Vec<String> {
+            df.column("UF").unwrap().str().unwrap().into_no_null_iter().map(str::to_string).collect()
+        };
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        let report = scanner.read_with_report().unwrap();
+        assert_eq!(report.deleted_records, 1);
+        assert_eq!(ufs(&report.dataframe), ["SP", "RJ", "SP", "RJ"]);
+
+        // A partial read counts only the deleted records it stepped over
+        assert_eq!(scanner.head(2).unwrap().height(), 2);
+        assert_eq!(scanner.deleted_records(), 0);
+        assert_eq!(scanner.head(3).unwrap().height(), 3);
+        assert_eq!(scanner.deleted_records(), 1);
+
+        let config = DbcConfig { include_deleted: true, ..Default::default() };
+        let scanner = DbcScanner::from_dbf_path(&path, Some(config)).unwrap();
+        let report = scanner.read_with_report().unwrap();
+        assert_eq!(report.deleted_records, 0);
+        assert_eq!(ufs(&report.dataframe), ["SP", "RJ", "MG", "SP", "RJ"]);
+        assert_eq!(scanner.head(3).unwrap().height(), 3);
+    }
+
+    #[test]
+    fn test_lenient_decimal_parsing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("decimal.dbf");
+        fixtures::write_decimal_dbf(&path);
+
+        let report = DbcScanner::from_dbf_path(&path, None).unwrap().read_with_report().unwrap();
+        assert_eq!(report.skipped_records, 0);
+        assert_eq!(report.invalid_numeric_values, 1);
+        let values: Vec<Option<f64>> = report.dataframe.column("VALOR").unwrap().f64().unwrap().into_iter().collect();
+        assert_eq!(values, [Some(12.5), Some(3.25), Some(-0.75), None, None, Some(1.5), None]);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_reader_matches_buffered_reader() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let basic = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&basic);
+        let numbered = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&numbered, 2_500);
+        let encoded = temp_dir.path().join("encoded.dbf");
+        fixtures::write_encoded_dbf(&encoded, 0x02, b"S\xc6o Paulo");
+        // Binary FoxPro fields fall back to the buffered reader
+        let foxpro = temp_dir.path().join("foxpro.dbf");
+        fixtures::write_foxpro_dbf(&foxpro);
+
+        for path in [&basic, &numbered, &encoded, &foxpro] {
+            let mapped = read_dbase_mmap(path, None).unwrap();
+            let buffered = read_dbf(path).unwrap();
+            assert_eq!(mapped.schema(), buffered.schema(), "{}", path.display());
+            assert!(mapped.equals_missing(&buffered), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_estimate_dataframe_memory() {
+        use polars::prelude::*;
+
+        let df = df!(
+            "a" => [1i64, 2, 3],
+            "b" => ["x", "y", "z"],
+        ).unwrap();
+        let expected: usize = df.get_columns()
+            .iter()
+            .map(|c| c.as_materialized_series().estimated_size())
+            .sum();
+        assert_eq!(estimate_dataframe_memory(&df), expected);
+        assert!(estimate_dataframe_memory(&df) > 0);
+    }
+
+    #[test]
+    fn test_shrink_integer_columns() {
+        use polars::prelude::*;
+
+        let df = df!(
+            "small" => [1i64, -5, 100],
+            "medium" => [1_000i64, 2_000, 30_000],
+            "large" => [1i64, 3_000_000_000, 5],
+            "nulls" => [None::<i64>, None, None],
+        ).unwrap();
+        let shrunk = shrink_integer_columns(df.clone()).unwrap();
+
+        assert_eq!(shrunk.column("small").unwrap().dtype(), &DataType::Int8);
+        assert_eq!(shrunk.column("medium").unwrap().dtype(), &DataType::Int16);
+        assert_eq!(shrunk.column("large").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(shrunk.column("nulls").unwrap().dtype(), &DataType::Int64);
+        assert!(estimate_dataframe_memory(&shrunk) <= estimate_dataframe_memory(&df));
+    }
+
+    #[test]
+    fn test_null_numeric_sentinels() {
+        use polars::prelude::*;
+
+        let df = df!(
+            "IDADE" => [Some(34i32), Some(9999), None, Some(71)],
+            "VAL_TOT" => [12.5f64, 9999.0, 3.0, 9999.0],
+            "MUNIC" => ["355030", "9999", "330455", "310620"],
+        ).unwrap();
+
+        let (cleaned, nulled) = null_numeric_sentinels(df, &[9999.0]).unwrap();
+        assert_eq!(cleaned.column("IDADE").unwrap().null_count(), 2);
+        assert_eq!(cleaned.column("IDADE").unwrap().i32().unwrap().max(), Some(71));
+        assert_eq!(cleaned.column("VAL_TOT").unwrap().null_count(), 2);
+        // String columns are never touched
+        assert_eq!(cleaned.column("MUNIC").unwrap().null_count(), 0);
+
+        assert_eq!(nulled.get("IDADE"), Some(&1));
+        assert_eq!(nulled.get("VAL_TOT"), Some(&2));
+        assert!(!nulled.contains_key("MUNIC"));
+    }
+
+    #[test]
+    fn test_read_with_report() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        let result = scanner.read_with_report().unwrap();
+
+        assert_eq!(result.dataframe.height(), 2);
+        assert!(result.memory_after_bytes <= result.memory_before_bytes);
+        assert_eq!(
+            result.memory_saved_bytes(),
+            result.memory_before_bytes - result.memory_after_bytes
+        );
+        assert_eq!(result.memory_after_bytes, estimate_dataframe_memory(&result.dataframe));
+    }
+
+    #[test]
+    fn test_parallelization_strategy() {
+        assert_eq!(
+            determine_parallelization_strategy(1_000_000, Some(1)),
+            ParallelizationStrategy::Sequential
+        );
+        assert_eq!(
+            determine_parallelization_strategy(10, Some(8)),
+            ParallelizationStrategy::Sequential
+        );
+        assert_eq!(
+            determine_parallelization_strategy(1_000_000, Some(4)),
+            ParallelizationStrategy::Parallel { threads: 4 }
+        );
+        assert_eq!(
+            determine_parallelization_strategy(1_000_000, Some(0)),
+            ParallelizationStrategy::Sequential
+        );
+    }
+
+    #[test]
+    fn test_thread_pool_is_reused() {
+        let first = thread_pool(3).unwrap();
+        let second = thread_pool(3).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.current_num_threads(), 3);
+    }
+
+    #[test]
+    fn test_read_with_single_thread_matches_default() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let config = DbcConfig {
+            num_threads: Some(1),
+            ..Default::default()
+        };
+        let single = read_dbf_with_config(&path, config).unwrap();
+        let default = read_dbf(&path).unwrap();
+        assert!(single.equals_missing(&default));
+    }
+
+    #[test]
+    fn test_head_dbf_reads_exactly_n_rows() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 250);
+
+        let df = head_dbf(&path, 10).unwrap();
+        assert_eq!(df.height(), 10);
+        let ids = df.column("ID").unwrap().i32().unwrap();
+        assert_eq!(ids.get(0), Some(0));
+        assert_eq!(ids.get(9), Some(9));
+
+        // Asking for more rows than exist returns the whole file
+        assert_eq!(head_dbf(&path, 1_000).unwrap().height(), 250);
+    }
+
+    #[test]
+    fn test_head_dbc_reads_first_records() {
+        use polars::prelude::DataType;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dbf = temp_dir.path().join("numbered.dbf");
+        let dbc = temp_dir.path().join("numbered.dbc");
+        fixtures::write_numbered_dbf(&dbf, 250);
+        fixtures::write_dbc_from_dbf(&dbf, &dbc);
+
+        let df = head_dbc(&dbc, 5).unwrap();
+        assert_eq!(df.height(), 5);
+        let ids = df.column("ID").unwrap().cast(&DataType::Int64).unwrap();
+        assert_eq!(ids.i64().unwrap().into_no_null_iter().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+        // Asking for more than the file holds returns every record
+        assert_eq!(head_dbc(&dbc, 1_000).unwrap().height(), 250);
+    }
+
+    #[test]
+    fn test_provided_schema_is_reused() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let first = temp_dir.path().join("first.dbf");
+        let second = temp_dir.path().join("second.dbf");
+        fixtures::write_basic_dbf(&first);
+        fixtures::write_basic_dbf(&second);
+
+        let schema = DbcScanner::from_dbf_path(&first, None).unwrap().schema();
+        let config = DbcConfig {
+            provided_schema: Some(schema.clone()),
+            ..Default::default()
+        };
+
+        let scanner = DbcScanner::from_dbf_path(&second, Some(config)).unwrap();
+        assert!(Arc::ptr_eq(&scanner.schema(), &schema));
+        assert!(scanner.read_all().unwrap().equals_missing(&read_dbf(&first).unwrap()));
+    }
+
+    #[test]
+    fn test_provided_schema_field_count_mismatch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let basic = temp_dir.path().join("basic.dbf");
+        let numbered = temp_dir.path().join("numbered.dbf");
+        fixtures::write_basic_dbf(&basic);
+        fixtures::write_numbered_dbf(&numbered, 3);
+
+        let config = DbcConfig {
+            provided_schema: Some(DbcScanner::from_dbf_path(&basic, None).unwrap().schema()),
+            ..Default::default()
+        };
+
+        let err = DbcScanner::from_dbf_path(&numbered, Some(config)).err().unwrap();
+        assert!(matches!(err, DbcError::SchemaFieldCountMismatch(5, 2)));
+        assert!(err.to_string().contains("5 fields"));
+    }
+
+    #[test]
+    fn test_latin1_character_fields_are_decoded() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("latin1.dbf");
+        // "São Paulo" in Latin-1, header without a language driver
+        fixtures::write_encoded_dbf(&path, 0x00, b"S\xe3o Paulo");
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        assert_eq!(scanner.encoding(), DbaseEncoding::Latin1);
+        let df = scanner.read_all().unwrap();
+        let names = df.column("MUNIC").unwrap().str().unwrap();
+        assert_eq!(names.get(0).map(str::trim_end), Some("São Paulo"));
+    }
+
+    #[test]
+    fn test_encoding_from_language_driver_and_override() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("cp850.dbf");
+        // "São Paulo" in CP850 (ã = 0xC6), header declaring code page 850
+        fixtures::write_encoded_dbf(&path, 0x02, b"S\xc6o Paulo");
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        assert_eq!(scanner.encoding(), DbaseEncoding::Cp850);
+        let df = scanner.read_all().unwrap();
+        let names = df.column("MUNIC").unwrap().str().unwrap();
+        assert_eq!(names.get(0).map(str::trim_end), Some("São Paulo"));
+
+        let config = DbcConfig {
+            encoding: Some(DbaseEncoding::Latin1),
+            ..Default::default()
+        };
+        let scanner = DbcScanner::from_dbf_path(&path, Some(config)).unwrap();
+        assert_eq!(scanner.encoding(), DbaseEncoding::Latin1);
+    }
+
+    #[test]
+    fn test_dbf_with_memo_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("TABAUX.DBF");
+        // Upper-case extension, which dbase's own lower-case lookup would miss
+        let memo = temp_dir.path().join("TABAUX.DBT");
+        fixtures::write_memo_dbf(&path, &memo, &[Some("Paciente transferido"), None, Some("Alta a pedido")]);
+
+        let scanner = DbcScanner::from_dbf_path(&path, None).unwrap();
+        assert_eq!(scanner.memo_path(), Some(memo.as_path()));
+        let df = scanner.read_all().unwrap();
+        let obs = df.column("OBS").unwrap();
+        assert_eq!(obs.dtype(), &polars::prelude::DataType::String);
+        let obs = obs.str().unwrap();
+        assert_eq!(obs.get(0), Some("Paciente transferido"));
+        assert_eq!(obs.get(2), Some("Alta a pedido"));
+
+        std::fs::remove_file(&memo).unwrap();
+        match DbcScanner::from_dbf_path(&path, None) {
+            Err(DbcError::MissingMemoFile(missing)) => assert_eq!(missing, path),
+            Err(other) => panic!("expected MissingMemoFile, got {other:?}"),
+            Ok(_) => panic!("expected MissingMemoFile"),
+        }
+
+        // Files without memo fields don't look for one
+        let plain = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&plain, 1);
+        assert_eq!(DbcScanner::from_dbf_path(&plain, None).unwrap().memo_path(), None);
+    }
+
+    #[test]
+    fn test_read_result_reports_encoding_provenance() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("cp850.dbf");
+        fixtures::write_encoded_dbf(&path, 0x02, b"S\xc6o Paulo");
+
+        let result = DbcScanner::from_dbf_path(&path, None).unwrap().read_with_report().unwrap();
+        assert_eq!(result.detected_encoding, DbaseEncoding::Cp850);
+        assert_eq!(result.language_driver_id, Some(0x02));
+
+        // An override changes the decode choice but the header's driver ID is still reported
+        let config = DbcConfig {
+            encoding: Some(DbaseEncoding::Utf8),
+            ..Default::default()
+        };
+        let result = DbcScanner::from_dbf_path(&path, Some(config)).unwrap().read_with_report().unwrap();
+        assert_eq!(result.detected_encoding, DbaseEncoding::Utf8);
+        assert_eq!(result.language_driver_id, Some(0x02));
+
+        // An unknown driver ID falls back to Latin-1
+        let path = temp_dir.path().join("unknown.dbf");
+        fixtures::write_encoded_dbf(&path, 0x7F, b"Sao Paulo");
+        let result = DbcScanner::from_dbf_path(&path, None).unwrap().read_with_report().unwrap();
+        assert_eq!(result.detected_encoding, DbaseEncoding::Latin1);
+        assert_eq!(result.language_driver_id, Some(0x7F));
+    }
+
+    #[test]
+    fn test_trim_policy_apply() {
+        assert_eq!(TrimPolicy::None.apply("  SP  "), "  SP  ");
+        assert_eq!(TrimPolicy::Trailing.apply("  SP  "), "  SP");
+        assert_eq!(TrimPolicy::Both.apply("  SP  "), "SP");
+        assert_eq!(TrimPolicy::default(), TrimPolicy::Trailing);
+    }
+
+    #[test]
+    fn test_trim_policies_on_read() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("padded.dbf");
+        fixtures::write_encoded_dbf(&path, 0x00, b" Santos");
+
+        let read_with = |policy: TrimPolicy| {
+            let config = DbcConfig {
+                trim_strings: policy,
+                ..Default::default()
+            };
+            DbcScanner::from_dbf_path(&path, Some(config)).unwrap().read_with_report().unwrap()
+        };
+
+        // Whatever padding survives the reader, each policy only removes its own side
+        let untouched = read_with(TrimPolicy::None);
+        let raw = untouched.dataframe.column("MUNIC").unwrap().str().unwrap().get(0).unwrap().to_string();
+        assert!(!untouched.strings_trimmed());
+
+        let trailing = read_with(TrimPolicy::Trailing);
+        let value = trailing.dataframe.column("MUNIC").unwrap().str().unwrap().get(0).unwrap().to_string();
+        assert_eq!(value, raw.trim_end());
+        assert_eq!(trailing.strings_trimmed(), raw != raw.trim_end());
+
+        let both = read_with(TrimPolicy::Both);
+        let value = both.dataframe.column("MUNIC").unwrap().str().unwrap().get(0).unwrap().to_string();
+        assert_eq!(value, "Santos");
+    }
+
+    #[test]
+    fn test_empty_as_null() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let blank = temp_dir.path().join("blank.dbf");
+        let filled = temp_dir.path().join("filled.dbf");
+        fixtures::write_encoded_dbf(&blank, 0x00, b"    ");
+        fixtures::write_encoded_dbf(&filled, 0x00, b"Santos");
+
+        for policy in [TrimPolicy::None, TrimPolicy::Trailing, TrimPolicy::Both] {
+            let config = DbcConfig {
+                trim_strings: policy,
+                empty_as_null: true,
+                ..Default::default()
+            };
+
+            let df = read_dbf_with_config(&blank, config.clone()).unwrap();
+            assert_eq!(df.column("MUNIC").unwrap().null_count(), 1);
+
+            let df = read_dbf_with_config(&filled, config).unwrap();
+            let value = df.column("MUNIC").unwrap().str().unwrap().get(0).map(str::trim).map(str::to_string);
+            assert_eq!(value.as_deref(), Some("Santos"));
+        }
+
+        assert!(!DbcConfig::default().empty_as_null);
+    }
+
+    #[test]
+    fn test_quick_scan_dbase() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 25);
+
+        let df = quick_scan_dbase(&path).unwrap().collect().unwrap();
+        assert_eq!(df.shape(), (25, 2));
+        // Optimization is on: IDs 0..25 fit in Int8
+        assert_eq!(df.column("ID").unwrap().dtype(), &polars::prelude::DataType::Int8);
+    }
+
+    #[test]
+    fn test_rename_columns() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("basic.dbf");
+        fixtures::write_basic_dbf(&path);
+
+        let config = DbcConfig {
+            rename: HashMap::from([
+                ("CODIGO".to_string(), "codigo_municipio".to_string()),
+                ("DT_NASC".to_string(), "data_nascimento".to_string()),
+                ("N_AIH".to_string(), "numero_aih".to_string()), // not in the file: warned and skipped
+            ]),
+            ..Default::default()
+        };
+
+        let df = read_dbf_with_config(&path, config).unwrap();
+        let names: Vec<&str> = df.get_column_names().into_iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, vec!["codigo_municipio", "IDADE", "VALOR", "ATIVO", "data_nascimento"]);
+    }
+
+    #[test]
+    fn test_read_dbf_rows_matches_slice() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 100);
+
+        let full = read_dbf(&path).unwrap();
+        let window = read_dbf_rows(&path, 10..20).unwrap();
+        assert_eq!(window.height(), 10);
+        assert!(window.equals_missing(&full.slice(10, 10)));
+
+        // Windows running past the end are clamped
+        assert_eq!(read_dbf_rows(&path, 95..120).unwrap().height(), 5);
+        assert_eq!(read_dbf_rows(&path, 200..210).unwrap().height(), 0);
+    }
+
+    #[test]
+    fn test_common_columns_across_drifted_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let numbered = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&numbered, 5);
+        // Declares an extra VALOR column
+        let with_valor = temp_dir.path().join("with_valor.dbf");
+        fixtures::write_header_only_dbf(&with_valor);
+
+        let paths = vec![with_valor.clone(), numbered.clone()];
+        assert_eq!(common_columns(&paths).unwrap(), ["ID", "UF"]);
+        assert_eq!(common_columns(&paths[..1]).unwrap(), ["ID", "UF", "VALOR"]);
+        assert!(common_columns(&[]).unwrap().is_empty());
+
+        let df = read_common_columns(&paths, None).unwrap();
+        assert_eq!(df.get_column_names(), ["ID", "UF"]);
+        assert_eq!(df.height(), 5);
+    }
+
+    #[test]
+    fn test_bounded_read_stops_on_cancel_or_deadline() {
+        use std::time::Duration;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 3_000);
+        let later = Instant::now() + Duration::from_secs(600);
+
+        let full = read_dbase_bounded(&path, None, later, CancellationToken::new()).unwrap();
+        assert!(!full.truncated);
+        assert_eq!(full.dataframe.height(), 3_000);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let cancelled = read_dbase_bounded(&path, None, later, cancel).unwrap();
+        assert!(cancelled.truncated);
+        assert!(cancelled.dataframe.height() < 3_000);
+        assert_eq!(cancelled.dataframe.schema(), full.dataframe.schema());
+
+        let expired = read_dbase_bounded(&path, None, Instant::now(), CancellationToken::new()).unwrap();
+        assert!(expired.truncated);
+        assert!(expired.dataframe.height() < 3_000);
+    }
+
+    #[test]
+    fn test_bounded_read_ending_on_a_check_is_not_truncated() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let exact_path = temp_dir.path().join("exact.dbf");
+        let over_path = temp_dir.path().join("over.dbf");
+        // Interrupt at the second check, which falls right after the last record of the exact file
+        let second_check = || {
+            let checks = Cell::new(0);
+            move || {
+                checks.set(checks.get() + 1);
+                checks.get() >= 2
+            }
+        };
+
+        fixtures::write_numbered_dbf(&exact_path, INTERRUPT_CHECK_INTERVAL);
+        let exact = DbcScanner::from_dbf_path(&exact_path, None).unwrap().read_interruptible(&second_check()).unwrap();
+        assert!(!exact.truncated);
+        assert_eq!(exact.dataframe.height(), INTERRUPT_CHECK_INTERVAL);
+
+        fixtures::write_numbered_dbf(&over_path, INTERRUPT_CHECK_INTERVAL + 1);
+        let over = DbcScanner::from_dbf_path(&over_path, None).unwrap().read_interruptible(&second_check()).unwrap();
+        assert!(over.truncated);
+        assert_eq!(over.dataframe.height(), INTERRUPT_CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn test_dbase_row_count_fast_matches_iteration() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 137);
+
+        let mut reader = Reader::from_path(&path).unwrap();
+        let iterated = reader.iter_records().count() as u64;
+
+        assert_eq!(dbase_row_count_fast(&path).unwrap(), iterated);
+        assert_eq!(DbcScanner::from_dbf_path(&path, None).unwrap().record_count().unwrap(), iterated);
+    }
+
+    #[test]
+    fn test_verify_dbase_file_flags_truncated_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 137);
+        assert_eq!(verify_dbase_file(&path).unwrap(), 137);
+
+        // Same header, last records cut off
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 50]).unwrap();
+        assert_eq!(dbase_row_count_fast(&path).unwrap(), 137);
+        assert!(matches!(verify_dbase_file(&path), Err(DbcError::RecordParsingError(_))));
+    }
+
+    #[test]
+    fn test_convert_dbf_to_parquet_streaming() {
+        use polars::prelude::{ParquetReader, SerReader};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        let output = temp_dir.path().join("numbered.parquet");
+        fixtures::write_numbered_dbf(&path, 250);
+
+        let config = DbcConfig {
+            chunk_size: 100,
+            ..DbcConfig::default()
+        };
+        let result = convert_dbf_to_parquet_streaming(&path, &output, Some(config)).unwrap();
+        assert_eq!(result.rows_written, 250);
+        assert_eq!(result.batches, 3);
+        assert_eq!(result.output_size_bytes, std::fs::metadata(&output).unwrap().len());
+
+        let written = ParquetReader::new(std::fs::File::open(&output).unwrap()).finish().unwrap();
+        assert!(written.equals_missing(&read_dbf(&path).unwrap()));
+    }
+
+    #[test]
+    fn test_read_all_emits_tracing_span() {
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::Layer;
+
+        /// Collects `name field=value` for every span field set at creation or recorded later
+        #[derive(Clone, Default)]
+        struct CaptureSpans(Arc<Mutex<Vec<String>>>);
+
+        struct FieldVisitor<'a>(&'a str, &'a Mutex<Vec<String>>);
+
+        impl tracing::field::Visit for FieldVisitor<'_> {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                self.1.lock().unwrap().push(format!("{} {}={:?}", self.0, field.name(), value));
+            }
+        }
+
+        impl<S> Layer<S> for CaptureSpans
+        where
+            S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+        {
+            fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, _id: &tracing::span::Id, _ctx: Context<'_, S>) {
+                attrs.record(&mut FieldVisitor(attrs.metadata().name(), &self.0));
+            }
+
+            fn on_record(&self, id: &tracing::span::Id, values: &tracing::span::Record<'_>, ctx: Context<'_, S>) {
+                let name = ctx.span(id).map(|span| span.name()).unwrap_or("?");
+                values.record(&mut FieldVisitor(name, &self.0));
+            }
+        }
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 25);
+
+        let capture = CaptureSpans::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let df = tracing::subscriber::with_default(subscriber, || read_dbf(&path).unwrap());
+        assert_eq!(df.height(), 25);
+
+        let fields = capture.0.lock().unwrap().clone();
+        let size = std::fs::metadata(&path).unwrap().len();
+        assert!(fields.iter().any(|f| f.starts_with("read_dbase path=") && f.ends_with("numbered.dbf")), "{fields:?}");
+        assert!(fields.contains(&format!("read_dbase size={}", size)), "{fields:?}");
+        assert!(fields.contains(&"read_dbase rows=25".to_string()), "{fields:?}");
+        assert!(fields.iter().any(|f| f.starts_with("read_dbase duration_ms=")), "{fields:?}");
+    }
+
+    #[test]
+    fn test_dbc_to_dbf_file_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let original = temp_dir.path().join("numbered.dbf");
+        let dbc = temp_dir.path().join("RDSP2401.dbc");
+        fixtures::write_numbered_dbf(&original, 40);
+        fixtures::write_dbc_from_dbf(&original, &dbc);
+
+        let output = temp_dir.path().join("nested").join("raw").join("RDSP2401.dbf");
+        let written = dbc_to_dbf_file(&dbc, &output).unwrap();
+        assert_eq!(written, std::fs::metadata(&output).unwrap().len());
+        assert_eq!(std::fs::read(&output).unwrap(), std::fs::read(&original).unwrap());
+
+        let df = read_dbf(&output).unwrap();
+        assert_eq!(df.height(), 40);
+        assert!(df.equals_missing(&read_dbc(&dbc).unwrap()));
+
+        let missing = temp_dir.path().join("missing.dbc");
+        assert!(dbc_to_dbf_file(&missing, temp_dir.path().join("missing.dbf")).is_err());
+    }
+
+    #[test]
+    fn test_mislabeled_dbase_files_are_read_by_content() {
+        use crate::models::dbase_utils::{detect_dbase_format, DbaseFormat};
+        use polars::prelude::DataType;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dbf = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&dbf, 12);
+        // DBC content under a .dbf name, and DBF content under a .dbc name
+        let dbc_named_dbf = temp_dir.path().join("RDSP2401.dbf");
+        fixtures::write_dbc_from_dbf(&dbf, &dbc_named_dbf);
+        let dbf_named_dbc = temp_dir.path().join("RDSP2402.dbc");
+        std::fs::copy(&dbf, &dbf_named_dbc).unwrap();
+        let empty_dbc = temp_dir.path().join("empty.dbc");
+        let empty_dbf = temp_dir.path().join("empty.dbf");
+        fixtures::write_header_only_dbf(&empty_dbf);
+        fixtures::write_dbc_from_dbf(&empty_dbf, &empty_dbc);
+        let garbage = temp_dir.path().join("garbage.dbc");
+        std::fs::write(&garbage, b"not a dbase file").unwrap();
+
+        assert_eq!(detect_dbase_format(&dbf), DbaseFormat::Dbf);
+        assert_eq!(detect_dbase_format(&dbc_named_dbf), DbaseFormat::Dbc);
+        assert_eq!(detect_dbase_format(&dbf_named_dbc), DbaseFormat::Dbf);
+        assert_eq!(detect_dbase_format(&empty_dbf), DbaseFormat::Dbf);
+        assert_eq!(detect_dbase_format(&empty_dbc), DbaseFormat::Dbc);
+        assert_eq!(detect_dbase_format(&garbage), DbaseFormat::Unknown);
+        assert_eq!(detect_dbase_format(temp_dir.path().join("missing.dbf")), DbaseFormat::Unknown);
+
+        let ids = |df: &DataFrame| -> Vec<Option<i64>> {
+            df.column("ID").unwrap().cast(&DataType::Int64).unwrap().i64().unwrap().into_iter().collect()
+        };
+        let expected = ids(&read_dbf(&dbf).unwrap());
+        for mislabeled in [&dbc_named_dbf, &dbf_named_dbc] {
+            let df = quick_scan_dbase(mislabeled).unwrap().collect().unwrap();
+            assert_eq!(ids(&df), expected);
+            assert_eq!(dbase_row_count_fast(mislabeled).unwrap(), 12);
+        }
+    }
+
+    #[test]
+    fn test_column_order_is_stable_across_strategies() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("numbered.dbf");
+        fixtures::write_numbered_dbf(&path, 2_500);
+
+        let sequential = read_dbf_with_config(&path, DbcConfig { num_threads: Some(1), ..Default::default() }).unwrap();
+        let parallel = read_dbf_with_config(&path, DbcConfig { num_threads: Some(4), ..Default::default() }).unwrap();
+        let batched = read_dbf_rows(&path, 1_000..2_500).unwrap();
+
+        let declared = vec!["ID", "UF"];
+        for df in [&sequential, &parallel, &batched] {
+            let names: Vec<&str> = df.get_column_names().into_iter().map(|name| name.as_str()).collect();
+            assert_eq!(names, declared);
+            assert_eq!(df.schema(), sequential.schema());
+        }
+        assert!(sequential.equals_missing(&parallel));
+
+        // Requested order doesn't matter, and values stay with their column
+        let selected = read_dbf_columns(&path, &["UF", "ID"]).unwrap();
+        let names: Vec<&str> = selected.get_column_names().into_iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, declared);
+        assert!(selected.equals_missing(&sequential));
+    }
 }
@@ -1,3 +1,11 @@
+pub mod code_table;
+pub mod compact;
 pub mod dbase_pl;
+pub mod partition;
+pub mod validation;
 
+pub use code_table::*;
+pub use compact::*;
 pub use dbase_pl::*;
+pub use partition::*;
+pub use validation::*;
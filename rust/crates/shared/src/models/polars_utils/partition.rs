@@ -0,0 +1,74 @@
+//! Splitting combined DATASUS frames into per-state frames
+
+use std::collections::{BTreeSet, HashMap};
+
+use polars::prelude::{DataFrame, DataType, IdxCa, IdxSize};
+
+use crate::models::geo_utils::UFS;
+use crate::models::polars_utils::dbase_pl::{DbcError, DbcResult};
+
+/// Key of the frame collecting rows whose UF isn't a known state
+pub const INVALID_UF_BUCKET: &str = "INVALID";
+
+/// Split `df` into one frame per distinct UF in `uf_col`
+///
+/// UFs are matched case-insensitively against [`UFS`] and keyed by their
+/// upper-case abbreviation. Rows with unknown or null UFs go to the
+/// [`INVALID_UF_BUCKET`] frame, or fail with [`DbcError::UnknownUfs`] when
+/// `strict` is set. Row order is kept within each frame.
+pub fn split_by_uf(df: &DataFrame, uf_col: &str, strict: bool) -> DbcResult<HashMap<String, DataFrame>> {
+    let values = df.column(uf_col)?.cast(&DataType::String)?;
+    let mut rows: HashMap<String, Vec<IdxSize>> = HashMap::new();
+    let mut unknown = BTreeSet::new();
+
+    for (idx, value) in values.str()?.into_iter().enumerate() {
+        let uf = value.map(|value| value.trim().to_uppercase()).unwrap_or_default();
+        let key = if UFS.contains_key(uf.as_str()) {
+            uf
+        } else {
+            unknown.insert(uf);
+            INVALID_UF_BUCKET.to_string()
+        };
+        rows.entry(key).or_default().push(idx as IdxSize);
+    }
+
+    if strict && !unknown.is_empty() {
+        return Err(DbcError::UnknownUfs(unknown.into_iter().collect()));
+    }
+
+    rows.into_iter()
+        .map(|(uf, indices)| Ok((uf, df.take(&IdxCa::from_vec("idx".into(), indices))?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_split_by_uf() {
+        let df = df!(
+            "UF" => [Some("SP"), Some("RJ"), Some("sp"), Some("XX"), None],
+            "N_AIH" => ["1", "2", "3", "4", "5"],
+        ).unwrap();
+
+        let frames = split_by_uf(&df, "UF", false).unwrap();
+        let mut keys: Vec<&str> = frames.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["INVALID", "RJ", "SP"]);
+
+        let ids = |uf: &str| -> Vec<Option<String>> {
+            frames[uf].column("N_AIH").unwrap().str().unwrap().into_iter().map(|v| v.map(str::to_string)).collect()
+        };
+        assert_eq!(ids("SP"), [Some("1".to_string()), Some("3".to_string())]);
+        assert_eq!(ids("RJ"), [Some("2".to_string())]);
+        assert_eq!(ids("INVALID"), [Some("4".to_string()), Some("5".to_string())]);
+
+        match split_by_uf(&df, "UF", true) {
+            Err(DbcError::UnknownUfs(ufs)) => assert_eq!(ufs, ["", "XX"]),
+            other => panic!("expected UnknownUfs, got {other:?}"),
+        }
+        assert!(split_by_uf(&df, "UF_ZI", false).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! Data-quality checks for DATASUS frames, expressed as Polars predicates
+
+use polars::prelude::*;
+
+use crate::models::geo_utils::{get_state_info, StateNotFoundError};
+use crate::models::regex_patterns::DataSusFileInfo;
+
+/// Offending rows kept per rule in a [`ValidationReport`]
+pub const VALIDATION_SAMPLE_ROWS: usize = 5;
+
+/// A named predicate every row is expected to satisfy
+///
+/// Rows where the expression is null (e.g. a null input column) are not
+/// counted as violations; pair with [`not_null_rule`] when nulls matter.
+#[derive(Debug, Clone)]
+pub struct ValidationRule {
+    /// Name shown in the report
+    pub name: String,
+    /// Boolean expression that must be true
+    pub expr: Expr,
+}
+
+impl ValidationRule {
+    /// Create a rule from a name and a boolean expression
+    pub fn new(name: impl Into<String>, expr: Expr) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+        }
+    }
+}
+
+/// Outcome of one rule
+#[derive(Debug, Clone)]
+pub struct RuleResult {
+    /// Name of the rule
+    pub rule: String,
+    /// Rows where the rule's expression is false
+    pub violations: usize,
+    /// Up to [`VALIDATION_SAMPLE_ROWS`] offending rows
+    pub sample: DataFrame,
+}
+
+/// Outcome of [`validate_dataframe`], one entry per rule in the order given
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    /// Rows checked
+    pub rows: usize,
+    /// Per-rule results
+    pub results: Vec<RuleResult>,
+}
+
+impl ValidationReport {
+    /// Whether every rule held on every row
+    pub fn is_valid(&self) -> bool {
+        self.results.iter().all(|result| result.violations == 0)
+    }
+
+    /// Result of the rule called `name`
+    pub fn get(&self, name: &str) -> Option<&RuleResult> {
+        self.results.iter().find(|result| result.rule == name)
+    }
+
+    /// Rules with at least one violation
+    pub fn failed(&self) -> impl Iterator<Item = &RuleResult> {
+        self.results.iter().filter(|result| result.violations > 0)
+    }
+}
+
+/// Check every rule against `df`, counting violations and sampling offending rows
+///
+/// Fails if a rule refers to a column the frame doesn't have.
+pub fn validate_dataframe(df: &DataFrame, rules: &[ValidationRule]) -> PolarsResult<ValidationReport> {
+    let results = rules
+        .iter()
+        .map(|rule| {
+            let offending = df.clone().lazy().filter(rule.expr.clone().not()).collect()?;
+            Ok(RuleResult {
+                rule: rule.name.clone(),
+                violations: offending.height(),
+                sample: offending.head(Some(VALIDATION_SAMPLE_ROWS)),
+            })
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    Ok(ValidationReport {
+        rows: df.height(),
+        results,
+    })
+}
+
+/// `column` must not be null
+pub fn not_null_rule(column: &str) -> ValidationRule {
+    ValidationRule::new(format!("{column} not null"), col(column).is_not_null())
+}
+
+/// `column`, read as an integer, must be within `min..=max`
+///
+/// Works on text columns such as `ANO_CMPT` (C 4); unparseable values count as null.
+pub fn year_in_range_rule(column: &str, min: i32, max: i32) -> ValidationRule {
+    let year = col(column).cast(DataType::Int32);
+    ValidationRule::new(
+        format!("{column} in {min}..={max}"),
+        year.clone().gt_eq(lit(min)).and(year.lt_eq(lit(max))),
+    )
+}
+
+/// `column`, read as an integer, must be a month number (1 to 12)
+pub fn month_in_range_rule(column: &str) -> ValidationRule {
+    let month = col(column).cast(DataType::Int32);
+    ValidationRule::new(
+        format!("{column} in 1..=12"),
+        month.clone().gt_eq(lit(1)).and(month.lt_eq(lit(12))),
+    )
+}
+
+/// `column` holds IBGE municipality codes of the state `uf` (they start with its state code)
+pub fn municipality_in_uf_rule(column: &str, uf: &str) -> Result<ValidationRule, StateNotFoundError> {
+    let state = get_state_info(uf)?;
+    Ok(ValidationRule::new(
+        format!("{column} in {}", state.uf),
+        col(column).cast(DataType::String).str().starts_with(lit(state.code.to_string())),
+    ))
+}
+
+/// Structure of a SIGTAP procedure code, as a regex over the trimmed value
+///
+/// Ten digits: group, subgroup and form of organization (two each), a
+/// three-digit sequence and a check digit. Groups start at `01`.
+pub const PROCEDURE_CODE_PATTERN: &str = r"^\s*(0[1-9]|[1-9][0-9])[0-9]{8}\s*$";
+
+/// Whether `code` is structurally a SIGTAP procedure code (e.g. `0301010072`)
+///
+/// Only the shape is checked; whether the code exists in the SIGTAP table
+/// for a given competence needs that table. Surrounding spaces are ignored,
+/// as DBF text fields are padded.
+pub fn is_valid_procedure_code(code: &str) -> bool {
+    let code = code.trim();
+    code.len() == 10 && code.bytes().all(|b| b.is_ascii_digit()) && !code.starts_with("00")
+}
+
+/// `column` holds SIGTAP procedure codes, per [`is_valid_procedure_code`]
+///
+/// The column must be read as text (`PROC_REA`, `PA_PROC_ID` are C 10): a
+/// numeric cast has already dropped the leading zero every code has.
+pub fn procedure_code_rule(column: &str) -> ValidationRule {
+    ValidationRule::new(
+        format!("{column} is a procedure code"),
+        col(column).cast(DataType::String).str().contains(lit(PROCEDURE_CODE_PATTERN), true),
+    )
+}
+
+/// Rules for SIH RD (AIH) files: competence matches the file name, `MUNIC_MOV` is in its UF
+pub fn sih_rd_rules(file: &DataSusFileInfo) -> Result<Vec<ValidationRule>, StateNotFoundError> {
+    let year = i32::from(file.full_year());
+    let month = i32::from(file.month);
+    Ok(vec![
+        ValidationRule::new(format!("ANO_CMPT = {year}"), col("ANO_CMPT").cast(DataType::Int32).eq(lit(year))),
+        ValidationRule::new(format!("MES_CMPT = {month}"), col("MES_CMPT").cast(DataType::Int32).eq(lit(month))),
+        municipality_in_uf_rule("MUNIC_MOV", &file.uf_code)?,
+    ])
+}
+
+/// Rules for SIA PA files: `PA_CMP` (YYYYMM) matches the file name, `PA_UFMUN` is in its UF
+pub fn sia_pa_rules(file: &DataSusFileInfo) -> Result<Vec<ValidationRule>, StateNotFoundError> {
+    let competence = format!("{}{:02}", file.full_year(), file.month);
+    Ok(vec![
+        ValidationRule::new(format!("PA_CMP = {competence}"), col("PA_CMP").cast(DataType::String).eq(lit(competence))),
+        municipality_in_uf_rule("PA_UFMUN", &file.uf_code)?,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_year_rule_flags_out_of_range_rows() {
+        let df = df!(
+            "ANO_CMPT" => [Some("2023"), Some("1899"), None, Some("2031"), Some("2008")],
+            "N_AIH" => ["1", "2", "3", "4", "5"],
+        ).unwrap();
+
+        let report = validate_dataframe(&df, &[year_in_range_rule("ANO_CMPT", 1990, 2030)]).unwrap();
+        assert_eq!(report.rows, 5);
+        assert!(!report.is_valid());
+
+        let result = report.get("ANO_CMPT in 1990..=2030").unwrap();
+        assert_eq!(result.violations, 2); // the null year is not a violation
+        let offending: Vec<Option<&str>> = result.sample.column("N_AIH").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(offending, [Some("2"), Some("4")]);
+
+        let with_nulls = validate_dataframe(&df, &[not_null_rule("ANO_CMPT")]).unwrap();
+        assert_eq!(with_nulls.results[0].violations, 1);
+    }
+
+    #[test]
+    fn test_sih_rd_rules() {
+        let file = DataSusFileInfo::parse("RDSP2301.dbc").unwrap();
+        let df = df!(
+            "ANO_CMPT" => ["2023", "2023", "2022"],
+            "MES_CMPT" => ["01", "01", "01"],
+            "MUNIC_MOV" => ["355030", "330455", "350950"],
+        ).unwrap();
+
+        let report = validate_dataframe(&df, &sih_rd_rules(&file).unwrap()).unwrap();
+        let failed: Vec<(&str, usize)> = report.failed().map(|result| (result.rule.as_str(), result.violations)).collect();
+        assert_eq!(failed, [("ANO_CMPT = 2023", 1), ("MUNIC_MOV in SP", 1)]);
+
+        assert!(validate_dataframe(&df, &[not_null_rule("UF_ZI")]).is_err());
+        assert!(municipality_in_uf_rule("MUNIC_MOV", "XX").is_err());
+    }
+
+    #[test]
+    fn test_procedure_code_validation() {
+        let codes = [
+            ("0301010072", true),
+            ("0411010034 ", true),
+            ("0202010473", true),
+            ("301010072", false),
+            ("03010100720", false),
+            ("03010A0072", false),
+            ("0001010072", false),
+            ("", false),
+        ];
+        for (code, valid) in codes {
+            assert_eq!(is_valid_procedure_code(code), valid, "{code:?}");
+        }
+
+        let df = df!("PROC_REA" => codes.map(|(code, _)| Some(code)).into_iter().chain([None]).collect::<Vec<_>>()).unwrap();
+        let report = validate_dataframe(&df, &[procedure_code_rule("PROC_REA")]).unwrap();
+        let result = report.get("PROC_REA is a procedure code").unwrap();
+        assert_eq!(result.violations, codes.iter().filter(|(_, valid)| !valid).count());
+    }
+}
@@ -1,5 +1,6 @@
 use regex::Regex;
 use once_cell::sync::Lazy;
+use crate::models::date_utils::Period;
 
 /// Regex pattern for DATASUS files following the pattern:
 /// [group_name][uf_code][year(2 digits)][month(2 digits)].dbc
@@ -60,6 +61,11 @@ impl DataSusFileInfo {
         }
     }
     
+    /// Get the monthly partition period this file covers
+    pub fn period(&self) -> Period {
+        Period::YearMonth(self.full_year() as i32, self.month as u32)
+    }
+    
     /// Generate a filename with the given components
     pub fn generate_filename(group_name: &str, uf_code: &str, year: u8, month: u8) -> String {
         format!("{}{}{:02}{:02}.dbc", group_name, uf_code, year, month)
@@ -108,6 +114,7 @@ mod tests {
         assert_eq!(info.year, 23);
         assert_eq!(info.month, 1);
         assert_eq!(info.full_year(), 2023);
+        assert_eq!(info.period(), Period::YearMonth(2023, 1));
         
         // Test case insensitive extension
         let info2 = DataSusFileInfo::parse("RDSP2212.DBC").unwrap();
@@ -0,0 +1,165 @@
+//! Retry with exponential backoff, shared by the FTP, HTTP and storage clients
+//!
+//! Each subsystem decides which of its own errors are worth retrying; the
+//! policy only decides how many times and how long to wait in between.
+
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Whether a failed operation should be attempted again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retryable {
+    /// The failure is likely transient (dropped connection, busy server)
+    Yes,
+    /// Retrying won't help (missing file, bad credentials)
+    No,
+}
+
+impl From<bool> for Retryable {
+    fn from(retryable: bool) -> Self {
+        if retryable { Retryable::Yes } else { Retryable::No }
+    }
+}
+
+/// How many times to retry and how long to wait between attempts
+///
+/// The delay before retry `n` (zero-based) is `base_backoff * 2^n`, capped at
+/// `max_backoff`, then shortened by a random fraction of up to `jitter` so
+/// concurrent clients don't retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub base_backoff: Duration,
+    /// Longest delay between two attempts
+    pub max_backoff: Duration,
+    /// Fraction of each delay that may be randomly cut, from 0.0 (none) to 1.0
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: 0.1,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Exponential backoff without jitter: `base_backoff * 2^retry`, capped at `max_backoff`
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.base_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// The delay actually slept before retry `retry`, with jitter applied
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
+        }
+        backoff.mul_f64(1.0 - rand::thread_rng().gen_range(0.0..=jitter))
+    }
+
+    /// Run `operation` until it succeeds, fails with an error `classify` rejects, or retries run out
+    ///
+    /// Returns the last error when giving up.
+    pub async fn retry<F, Fut, T, E>(&self, classify: impl Fn(&E) -> Retryable, mut operation: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut retries = 0;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    if retries >= self.max_retries || classify(&error) == Retryable::No {
+                        return Err(error);
+                    }
+                    tokio::time::sleep(self.delay(retries)).await;
+                    retries += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetryPolicy {
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        let delays: Vec<u128> = (0..6).map(|retry| policy.backoff(retry).as_millis()).collect();
+        assert_eq!(delays, [100, 200, 400, 800, 1_000, 1_000]);
+        assert_eq!(policy.backoff(64), Duration::from_secs(1));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+
+        let jittered = RetryPolicy { jitter: 0.5, ..policy };
+        for _ in 0..20 {
+            let delay = jittered.delay(2);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400), "{delay:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_at_max_retries_or_permanent_error() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            base_backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+
+        let mut attempts = 0;
+        let result: Result<(), &str> = policy
+            .retry(|_| Retryable::Yes, || {
+                attempts += 1;
+                async { Err("busy") }
+            })
+            .await;
+        assert_eq!(result, Err("busy"));
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = policy
+            .retry(|_| Retryable::Yes, || {
+                attempts += 1;
+                let attempt = attempts;
+                async move { if attempt < 2 { Err("busy") } else { Ok(attempt) } }
+            })
+            .await;
+        assert_eq!(result, Ok(2));
+
+        let mut attempts = 0;
+        let result: Result<(), &str> = policy
+            .retry(|error: &&str| (*error != "missing").into(), || {
+                attempts += 1;
+                async { Err("missing") }
+            })
+            .await;
+        assert_eq!(result, Err("missing"));
+        assert_eq!(attempts, 1);
+    }
+}
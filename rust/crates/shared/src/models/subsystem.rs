@@ -1,4 +1,9 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
 use once_cell::sync::Lazy;
+use crate::models::date_utils::Period;
+use crate::models::utils::closest_match;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SubsystemMetadata {
@@ -217,3 +222,113 @@ pub static SINASC: Lazy<Subsystem> = Lazy::new(|| {
         },
     )
 });
+
+/// Every known subsystem
+pub fn all_subsystems() -> [&'static Subsystem; 9] {
+    [&SIA, &SIH, &CIHA, &CNES, &IBGE, &PNI, &SIM, &SINAN, &SINASC]
+}
+
+/// Suggest the subsystem code closest to a mistyped `code` (at most 2 edits away)
+pub fn suggest_subsystem(code: &str) -> Option<&'static str> {
+    closest_match(code, all_subsystems().into_iter().map(|subsystem| subsystem.name.as_str()), 2)
+}
+
+/// How often a DATASUS group publishes a file per UF
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Periodicity {
+    /// One file per month (e.g. `RDSP2301.dbc`)
+    Monthly,
+    /// One file per year (e.g. `DOSP2023.dbc`)
+    Yearly,
+}
+
+impl Periodicity {
+    /// Periodicity of a single period
+    pub fn of(period: &Period) -> Self {
+        match period {
+            Period::Year(_) => Periodicity::Yearly,
+            Period::YearMonth(..) => Periodicity::Monthly,
+        }
+    }
+}
+
+/// Known periodicity per `(subsystem, group)`, seeded with the DATASUS defaults
+static GROUP_PERIODICITY: Lazy<RwLock<HashMap<(String, String), Periodicity>>> = Lazy::new(|| {
+    let known: [(&str, &[&str], Periodicity); 8] = [
+        ("SIH", &["RD", "RJ", "ER", "SP", "CH", "CM"], Periodicity::Monthly),
+        ("SIA", &["PA", "BI", "AD", "AM", "AN", "AQ", "AR", "ATD", "PS", "SAD"], Periodicity::Monthly),
+        ("CIHA", &["CIHA"], Periodicity::Monthly),
+        ("CNES", &["LT", "ST", "DC", "EQ", "SR", "HB", "PF", "EP", "RC", "IN", "EE", "EF", "GM"], Periodicity::Monthly),
+        ("SIM", &["DO", "DOEXT", "DOFET", "DOINF", "DOMAT"], Periodicity::Yearly),
+        ("SINASC", &["DN", "DNEX"], Periodicity::Yearly),
+        ("IBGE", &["POP"], Periodicity::Yearly),
+        ("PNI", &["CPNI", "DPNI"], Periodicity::Yearly),
+    ];
+    let map = known
+        .into_iter()
+        .flat_map(|(subsystem, groups, periodicity)| {
+            groups.iter().map(move |group| ((subsystem.to_string(), group.to_string()), periodicity))
+        })
+        .collect();
+    RwLock::new(map)
+});
+
+/// Expected periodicity of `group` in `subsystem` (case-insensitive), if known
+pub fn group_periodicity(subsystem: &str, group: &str) -> Option<Periodicity> {
+    let key = (subsystem.to_ascii_uppercase(), group.to_ascii_uppercase());
+    GROUP_PERIODICITY.read().unwrap_or_else(|e| e.into_inner()).get(&key).copied()
+}
+
+/// Declare or override the periodicity of `group` in `subsystem`
+pub fn set_group_periodicity(subsystem: &str, group: &str, periodicity: Periodicity) {
+    let key = (subsystem.to_ascii_uppercase(), group.to_ascii_uppercase());
+    GROUP_PERIODICITY.write().unwrap_or_else(|e| e.into_inner()).insert(key, periodicity);
+}
+
+/// Compare a periodicity inferred from file names with the known one, warning on a mismatch
+///
+/// A yearly `DOSP2012.dbc` also matches the monthly `YYMM` naming (as December
+/// 2020), so an inferred periodicity is only trusted when this returns true.
+/// Unknown groups are accepted.
+pub fn check_group_periodicity(subsystem: &str, group: &str, inferred: Periodicity) -> bool {
+    match group_periodicity(subsystem, group) {
+        Some(expected) if expected != inferred => {
+            log::warn!(
+                "{} {} files look {:?}, but the group is published {:?}",
+                subsystem, group, inferred, expected
+            );
+            false
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_periodicity() {
+        assert_eq!(group_periodicity("SIH", "RD"), Some(Periodicity::Monthly));
+        assert_eq!(group_periodicity("cnes", "st"), Some(Periodicity::Monthly));
+        assert_eq!(group_periodicity("SIM", "DO"), Some(Periodicity::Yearly));
+        assert_eq!(group_periodicity("SIM", "RD"), None);
+
+        assert!(check_group_periodicity("SIH", "RD", Periodicity::of(&Period::YearMonth(2023, 1))));
+        assert!(!check_group_periodicity("SIM", "DO", Periodicity::of(&Period::YearMonth(2020, 12))));
+        assert!(check_group_periodicity("SINAN", "DENG", Periodicity::Monthly));
+
+        set_group_periodicity("SINAN", "DENG", Periodicity::Yearly);
+        assert_eq!(group_periodicity("sinan", "deng"), Some(Periodicity::Yearly));
+        assert!(!check_group_periodicity("SINAN", "DENG", Periodicity::Monthly));
+    }
+
+    #[test]
+    fn test_suggest_subsystem() {
+        assert_eq!(suggest_subsystem("SIHH"), Some("SIH"));
+        assert_eq!(suggest_subsystem("sinsac"), Some("SINASC"));
+        assert_eq!(suggest_subsystem("CNESS"), Some("CNES"));
+        assert_eq!(suggest_subsystem("SIH"), Some("SIH"));
+        assert_eq!(suggest_subsystem("ANVISA"), None);
+    }
+}
@@ -12,16 +12,37 @@ pub type PathLike = PathBuf;
 /// Constants
 pub const DEFAULT_CACHE_DIR: &str = "pysus";
 
+/// Environment variable that overrides the cache directory
+pub const CACHE_DIR_ENV: &str = "ARROW_SUS_CACHE_DIR";
+
+/// Cache directory used when nothing overrides it
+///
+/// In order: `ARROW_SUS_CACHE_DIR`, then the legacy `PYSUS_CACHEPATH`, then
+/// [`DEFAULT_CACHE_DIR`] under the platform cache directory (`$XDG_CACHE_HOME`
+/// or `~/.cache` on Linux, `%LOCALAPPDATA%` on Windows), then under the home
+/// directory, then under the working directory. Empty variables are ignored.
+pub fn resolve_cache_dir() -> PathBuf {
+    resolve_cache_dir_with(|name| env::var(name).ok(), dirs::cache_dir(), dirs::home_dir())
+}
+
+/// [`resolve_cache_dir`] with the environment and platform directories supplied by the caller
+fn resolve_cache_dir_with(
+    var: impl Fn(&str) -> Option<String>,
+    platform_cache_dir: Option<PathBuf>,
+    home_dir: Option<PathBuf>,
+) -> PathBuf {
+    [CACHE_DIR_ENV, "PYSUS_CACHEPATH"]
+        .into_iter()
+        .find_map(|name| var(name).filter(|value| !value.is_empty()))
+        .map(PathBuf::from)
+        .or_else(|| platform_cache_dir.map(|dir| dir.join(DEFAULT_CACHE_DIR)))
+        .or_else(|| home_dir.map(|home| home.join(DEFAULT_CACHE_DIR)))
+        .unwrap_or_else(|| PathBuf::from(".").join(DEFAULT_CACHE_DIR))
+}
+
 lazy_static! {
-    /// Cache path - equivalent to CACHEPATH in Python
-    pub static ref CACHE_PATH: String = {
-        env::var("PYSUS_CACHEPATH")
-            .unwrap_or_else(|_| {
-                dirs::home_dir()
-                    .map(|home| home.join(DEFAULT_CACHE_DIR).to_string_lossy().to_string())
-                    .unwrap_or_else(|| format!("./{}", DEFAULT_CACHE_DIR))
-            })
-    };
+    /// Cache path - equivalent to CACHEPATH in Python, resolved by [`resolve_cache_dir`]
+    pub static ref CACHE_PATH: String = resolve_cache_dir().to_string_lossy().to_string();
 
     /// Cache path as PathBuf - equivalent to __cachepath__ in Python
     pub static ref CACHE_PATH_BUF: PathBuf = {
@@ -80,6 +101,54 @@ pub fn vec_from_slice<T: Clone>(items: &[T]) -> Vec<T> {
     items.to_vec()
 }
 
+/// Levenshtein edit distance between two strings, counted in characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
+
+/// Closest candidate to `input` (case-insensitive) within `max_distance` edits
+///
+/// Returns `None` when nothing is close enough or when several candidates tie
+/// for closest, since an ambiguous suggestion doesn't help.
+pub fn closest_match<'a, I>(input: &str, candidates: I, max_distance: usize) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input = input.to_uppercase();
+    let mut best: Option<(&'a str, usize)> = None;
+    let mut tied = false;
+
+    for candidate in candidates {
+        let distance = edit_distance(&input, &candidate.to_uppercase());
+        if distance > max_distance {
+            continue;
+        }
+        match best {
+            Some((_, best_distance)) if distance > best_distance => {}
+            Some((_, best_distance)) if distance == best_distance => tied = true,
+            _ => {
+                best = Some((candidate, distance));
+                tied = false;
+            }
+        }
+    }
+
+    if tied { None } else { best.map(|(candidate, _)| candidate) }
+}
+
 /// Utility functions for working with paths
 pub mod path_utils {
     use super::*;
@@ -174,6 +243,44 @@ mod tests {
         assert_eq!(slice_to_vec(&arr), vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("SIH", "SIH"), 0);
+        assert_eq!(edit_distance("SIHH", "SIH"), 1);
+        assert_eq!(edit_distance("SHS", "SIH"), 2);
+        assert_eq!(edit_distance("", "SIA"), 3);
+        assert_eq!(edit_distance("São", "Sao"), 1);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let codes = ["SIA", "SIH", "SIM"];
+        assert_eq!(closest_match("sih", codes, 2), Some("SIH"));
+        assert_eq!(closest_match("SIHH", codes, 2), Some("SIH"));
+        assert_eq!(closest_match("SI", codes, 2), None); // equally close to all three
+        assert_eq!(closest_match("XYZW", codes, 2), None);
+    }
+
+    #[test]
+    fn test_cache_dir_env_override_wins() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+        let platform = Some(PathBuf::from("/home/user/.cache"));
+        let home = Some(PathBuf::from("/home/user"));
+
+        let both = env(&[(CACHE_DIR_ENV, "/data/arrow-sus"), ("PYSUS_CACHEPATH", "/data/pysus")]);
+        assert_eq!(resolve_cache_dir_with(both, platform.clone(), home.clone()), PathBuf::from("/data/arrow-sus"));
+
+        let legacy = env(&[(CACHE_DIR_ENV, ""), ("PYSUS_CACHEPATH", "/data/pysus")]);
+        assert_eq!(resolve_cache_dir_with(legacy, platform.clone(), home.clone()), PathBuf::from("/data/pysus"));
+
+        let unset = env(&[]);
+        assert_eq!(resolve_cache_dir_with(unset, platform, home.clone()), PathBuf::from("/home/user/.cache/pysus"));
+        assert_eq!(resolve_cache_dir_with(unset, None, home), PathBuf::from("/home/user/pysus"));
+        assert_eq!(resolve_cache_dir_with(unset, None, None), PathBuf::from("./pysus"));
+    }
+
     #[test]
     fn test_cache_path_exists() {
         // Test that cache path is created
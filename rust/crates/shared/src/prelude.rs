@@ -0,0 +1,43 @@
+//! The types and functions most programs need, in one import
+//!
+//! ```rust
+//! use shared::prelude::*;
+//!
+//! let config = DbcConfig::default();
+//! let state = get_state_info("SP").unwrap();
+//! assert_eq!(state.code, 35);
+//! # let _ = config;
+//! ```
+//!
+//! Included:
+//! - reading DBC/DBF files: [`DbcConfig`], [`DbcReadResult`], [`DbcScanner`],
+//!   [`DbaseEncoding`], the `read_*`/`scan_*` functions and [`DbcError`]/[`DbcResult`]
+//! - describing and converting them: [`describe_dbase_file`], [`DbaseFileSummary`],
+//!   [`convert_dbc_to_parquet_streaming`], [`compact_to_parquet`]
+//! - browsing the FTP server: [`Directory`], [`File`], [`FileSystemProvider`],
+//!   [`FtpFileSystemProvider`], [`LocalFileSystemProvider`]
+//! - downloading: [`FtpDownloader`], [`DownloadConfig`], [`DownloadResult`],
+//!   [`OverwritePolicy`], [`DataLocation`], [`read_dbase_location`]
+//! - catalog lookups: [`Subsystem`], [`all_subsystems`], [`GroupInfo`],
+//!   [`DataSusFileInfo`], [`StateBR`], [`get_state_info`]
+//! - errors: [`SharedError`], [`SharedResult`]
+//!
+//! Everything else stays under [`crate::models`].
+
+pub use crate::errors::{SharedError, SharedResult};
+pub use crate::models::dbase_utils::DbaseEncoding;
+pub use crate::models::directory::{Directory, FileSystemProvider, FtpFileSystemProvider, LocalFileSystemProvider};
+pub use crate::models::download::{DownloadConfig, DownloadResult, FtpDownloader, OverwritePolicy};
+pub use crate::models::file::File;
+pub use crate::models::file_info::FileInfo;
+pub use crate::models::geo_utils::{get_state_info, StateBR};
+pub use crate::models::group_info::GroupInfo;
+pub use crate::models::location::{read_dbase_location, DataLocation};
+pub use crate::models::polars_utils::compact::compact_to_parquet;
+pub use crate::models::polars_utils::dbase_pl::{
+    convert_dbc_to_parquet_streaming, describe_dbase_file, read_dbc, read_dbc_with_config, read_dbf,
+    read_dbf_with_config, scan_dbc, scan_dbf, DbaseFileSummary, DbcConfig, DbcError, DbcReadResult, DbcResult,
+    DbcScanner,
+};
+pub use crate::models::regex_patterns::DataSusFileInfo;
+pub use crate::models::subsystem::{all_subsystems, Subsystem};